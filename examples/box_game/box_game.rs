@@ -171,7 +171,7 @@ pub fn move_cube_system(
     let dt = time.delta().as_secs_f32();
 
     for (mut t, mut v, p) in query.iter_mut() {
-        let input = inputs[p.handle].0 .0;
+        let input = inputs.by_handle(p.handle).0 .0;
         // set velocity through key presses
         if input & INPUT_UP != 0 && input & INPUT_DOWN == 0 {
             v.z -= ACCELERATION * dt;
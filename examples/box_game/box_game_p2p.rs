@@ -69,8 +69,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .rollback_resource_with_copy::<FrameCount>()
         // Same with the Velocity Component
         .rollback_component_with_copy::<Velocity>()
-        // Transform only implement Clone, so instead we'll use that to snapshot and rollback with
-        .rollback_component_with_clone::<Transform>()
+        // Transform is rolled back so often that it has a dedicated helper, which also adds it to the checksum
+        .rollback_transform()
         .insert_resource(opt)
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
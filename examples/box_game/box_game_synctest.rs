@@ -48,8 +48,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .rollback_resource_with_copy::<FrameCount>()
         // Same with the Velocity Component
         .rollback_component_with_copy::<Velocity>()
-        // Transform only implement Clone, so instead we'll use that to snapshot and rollback with
-        .rollback_component_with_clone::<Transform>()
+        // Transform is rolled back so often that it has a dedicated helper, which also adds it to the checksum
+        .rollback_transform()
         .add_systems(Startup, setup_system)
         // these systems will be executed as part of the advance frame update
         .add_systems(GgrsSchedule, (move_cube_system, increase_frame_system))
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_ggrs::prelude::*;
+
+mod box_game;
+use box_game::*;
+
+const FPS: usize = 60;
+const NUM_PLAYERS: usize = 2;
+const CHECK_DISTANCE: usize = 7;
+
+// Browsers provide no command line, so unlike the other examples, everything is fixed here
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // create a GGRS session
+    let mut sess_build = SessionBuilder::<BoxConfig>::new()
+        .with_num_players(NUM_PLAYERS)
+        .with_check_distance(CHECK_DISTANCE)
+        .with_input_delay(2); // (optional) set input delay for the local player
+
+    // add players
+    for i in 0..NUM_PLAYERS {
+        sess_build = sess_build.add_player(PlayerType::Local, i)?;
+    }
+
+    // start the GGRS session
+    let sess = sess_build.start_synctest_session()?;
+
+    App::new()
+        .add_plugins(GgrsPlugin::<BoxConfig>::default())
+        // define frequency of rollback game logic update
+        .set_rollback_schedule_fps(FPS)
+        // on wasm32, the GgrsPlugin already limits this to avoid a burst of catch-up frames
+        // when a throttled tab regains focus, but it can be tuned like any other setting
+        .set_max_frame_delta(std::time::Duration::from_millis(100))
+        // this system will be executed as part of input reading
+        .add_systems(ReadInputs, read_local_inputs)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                // fill the browser window
+                fit_canvas_to_parent: true,
+                ..default()
+            }),
+            ..default()
+        }))
+        .rollback_resource_with_copy::<FrameCount>()
+        .rollback_component_with_copy::<Velocity>()
+        .rollback_transform()
+        .add_systems(Startup, setup_system)
+        // these systems will be executed as part of the advance frame update
+        .add_systems(GgrsSchedule, (move_cube_system, increase_frame_system))
+        // add your GGRS session
+        .insert_resource(Session::SyncTest(sess))
+        // register a resource that will be rolled back
+        .insert_resource(FrameCount { frame: 0 })
+        .run();
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use bevy::{app::ScheduleRunnerPlugin, log::LogPlugin, prelude::*, utils::HashMap};
+use bevy_ggrs::{prelude::*, LocalInputs, LocalPlayers, RollbackFrameCount};
+use bytemuck::{Pod, Zeroable};
+use clap::Parser;
+use ggrs::UdpNonBlockingSocket;
+use std::{net::SocketAddr, time::Duration};
+
+/// Headless peer for bevy_ggrs
+///
+/// Runs the same deterministic simulation as any other peer, but only relies on
+/// [`MinimalPlugins`], so it can be deployed on a machine without a window or GPU.
+///
+/// ## Basic usage:
+///
+/// Headless peer:
+///
+/// cargo run --example headless_server -- --local-port 7000 --players localhost 127.0.0.1:7001
+///
+/// Other peer:
+///
+/// cargo run --example headless_server -- --local-port 7001 --players 127.0.0.1:7000 localhost
+#[derive(Parser, Resource)]
+struct Args {
+    /// The udp port to bind to for this peer.
+    #[clap(short, long)]
+    local_port: u16,
+
+    /// Address and port for the players. Order is significant. Put yourself as
+    /// "localhost".
+    #[clap(short, long, num_args = 1..)]
+    players: Vec<String>,
+}
+
+const FPS: usize = 60;
+
+type Config = GgrsConfig<ScriptedInput>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable)]
+struct ScriptedInput(i8);
+
+#[derive(Component, Clone, Copy)]
+struct Player {
+    handle: usize,
+}
+
+#[derive(Component, Clone, Copy, Default, Hash)]
+struct Position(i32);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let num_players = args.players.len();
+    assert!(num_players > 0);
+
+    let mut session_builder = SessionBuilder::<Config>::new()
+        .with_num_players(num_players)
+        .with_desync_detection_mode(ggrs::DesyncDetection::On { interval: 10 })
+        .with_input_delay(2);
+
+    for (i, player_addr) in args.players.iter().enumerate() {
+        if player_addr == "localhost" {
+            session_builder = session_builder.add_player(PlayerType::Local, i)?;
+        } else {
+            let remote_addr: SocketAddr = player_addr.parse()?;
+            session_builder = session_builder.add_player(PlayerType::Remote(remote_addr), i)?;
+        }
+    }
+
+    let socket = UdpNonBlockingSocket::bind_to_port(args.local_port)?;
+    let session = session_builder.start_p2p_session(socket)?;
+
+    App::new()
+        // No window, no renderer: just enough to tick the app at a fixed rate
+        .add_plugins(
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+                1.0 / FPS as f64,
+            ))),
+        )
+        .add_plugins(LogPlugin::default())
+        .add_plugins(GgrsPlugin::<Config>::default())
+        .set_rollback_schedule_fps(FPS)
+        .add_systems(ReadInputs, read_scripted_inputs)
+        .rollback_component_with_copy::<Position>()
+        .checksum_component_with_hash::<Position>()
+        .insert_resource(args)
+        .insert_resource(Session::P2P(session))
+        .add_systems(Startup, spawn_players)
+        .add_systems(GgrsSchedule, move_players)
+        .add_systems(Update, print_events_system)
+        .run();
+
+    Ok(())
+}
+
+/// Without a keyboard, local players simply walk back and forth.
+fn read_scripted_inputs(
+    mut commands: Commands,
+    local_players: Res<LocalPlayers>,
+    frame: Res<RollbackFrameCount>,
+) {
+    let direction = if (frame.0 / FPS as i32) % 2 == 0 {
+        1
+    } else {
+        -1
+    };
+
+    let local_inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| (handle, ScriptedInput(direction)))
+        .collect::<HashMap<_, _>>();
+
+    commands.insert_resource(LocalInputs::<Config>(local_inputs));
+}
+
+fn spawn_players(mut commands: Commands, args: Res<Args>) {
+    for handle in 0..args.players.len() {
+        commands
+            .spawn((Player { handle }, Position::default()))
+            .add_rollback();
+    }
+}
+
+fn move_players(mut players: Query<(&Player, &mut Position)>, inputs: Res<PlayerInputs<Config>>) {
+    for (player, mut position) in &mut players {
+        position.0 += inputs.by_handle(player.handle).0 .0 as i32;
+    }
+}
+
+fn print_events_system(mut session: ResMut<Session<Config>>) {
+    if let Session::P2P(s) = session.as_mut() {
+        for event in s.events() {
+            match event {
+                GgrsEvent::DesyncDetected { .. } => error!("GGRS event: {event:?}"),
+                _ => info!("GGRS event: {event:?}"),
+            }
+        }
+    }
+}
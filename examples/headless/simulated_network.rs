@@ -0,0 +1,181 @@
+use bevy::{log::LogPlugin, prelude::*, time::TimeUpdateStrategy, utils::HashMap};
+use bevy_ggrs::{
+    prelude::*, start_local_p2p_sessions, LocalInputs, LocalPlayers, NetworkConditions,
+    RollbackFrameCount, RollbackStats, SimulatedClock,
+};
+use bytemuck::{Pod, Zeroable};
+use clap::Parser;
+use std::time::Duration;
+
+/// Two peers in a single process, connected through a simulated network
+///
+/// Both peers run headless, exchanging inputs in memory with artificial latency, jitter and
+/// packet loss. This exercises prediction and rollback without a second machine. Peers change
+/// direction at different times, so every change is mispredicted by the other peer. Players are
+/// spawned in the `GgrsSetup` schedule, so both peers start from an identical frame 0.
+///
+/// ## Basic usage:
+///
+/// cargo run --example simulated_network -- --latency-ms 80 --jitter-ms 20 --packet-loss 0.05
+#[derive(Parser)]
+struct Args {
+    /// One-way latency added to every message, in milliseconds.
+    #[clap(long, default_value_t = 50)]
+    latency_ms: u64,
+
+    /// Maximum random deviation from the latency, in milliseconds.
+    #[clap(long, default_value_t = 10)]
+    jitter_ms: u64,
+
+    /// Probability of dropping a message, between 0 and 1.
+    #[clap(long, default_value_t = 0.0)]
+    packet_loss: f64,
+
+    /// Seed for the simulated delays and drops.
+    #[clap(long, default_value_t = 0)]
+    seed: u64,
+
+    /// How long to run for, in seconds.
+    #[clap(long, default_value_t = 5)]
+    seconds: u64,
+}
+
+const FPS: usize = 60;
+const NUM_PLAYERS: usize = 2;
+
+type Config = GgrsConfig<ScriptedInput, usize>;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Pod, Zeroable)]
+struct ScriptedInput(i8);
+
+#[derive(Component, Clone, Copy)]
+struct Player {
+    handle: usize,
+}
+
+#[derive(Component, Clone, Copy, Default, Hash)]
+struct Position(i32);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let conditions = NetworkConditions {
+        latency: Duration::from_millis(args.latency_ms),
+        jitter: Duration::from_millis(args.jitter_ms),
+        packet_loss: args.packet_loss,
+        seed: args.seed,
+    };
+
+    let mut apps = [create_app(true), create_app(false)];
+    let clock = SimulatedClock::default();
+
+    let sessions = start_local_p2p_sessions::<Config>(
+        &apps[0].world,
+        NUM_PLAYERS,
+        conditions,
+        &clock,
+        |builder| {
+            Ok(builder.with_input_delay(2).map(|builder| {
+                builder.with_desync_detection_mode(ggrs::DesyncDetection::On { interval: 10 })
+            }))
+        },
+    )?;
+
+    for (app, session) in apps.iter_mut().zip(sessions) {
+        app.insert_resource(session);
+    }
+
+    for _ in 0..args.seconds as usize * FPS {
+        for app in &mut apps {
+            app.update();
+        }
+
+        // simulated delays follow the clock, but GGRS's own timers still follow the wall clock
+        clock.advance(Duration::from_secs_f64(1.0 / FPS as f64));
+        std::thread::sleep(Duration::from_secs_f64(1.0 / FPS as f64));
+    }
+
+    for (handle, app) in apps.iter_mut().enumerate() {
+        let frame = app.world.resource::<RollbackFrameCount>().0;
+        let stats = app.world.resource::<RollbackStats>();
+
+        info!(
+            "Peer {handle} reached frame {frame} after {} rollback(s)",
+            stats.load_count()
+        );
+    }
+
+    Ok(())
+}
+
+fn create_app(log: bool) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        // Each app is updated once per loop iteration, so time advances by exactly one frame
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / FPS as f64,
+        )))
+        .add_plugins(GgrsPlugin::<Config>::default())
+        .set_rollback_schedule_fps(FPS)
+        .add_systems(ReadInputs, read_scripted_inputs)
+        .rollback_component_with_copy::<Position>()
+        .checksum_component_with_hash::<Position>()
+        .add_systems(GgrsSetup, spawn_players)
+        .add_systems(GgrsSchedule, move_players)
+        .add_systems(Update, print_events_system);
+
+    // Logging can only be initialized once per process
+    if log {
+        app.add_plugins(LogPlugin::default());
+    }
+
+    app
+}
+
+/// Each local player turns around at a different interval, so remote predictions are often wrong.
+fn read_scripted_inputs(
+    mut commands: Commands,
+    local_players: Res<LocalPlayers>,
+    frame: Res<RollbackFrameCount>,
+) {
+    let local_inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| {
+            let interval = 20 + 7 * handle as i32;
+            let direction = if (frame.0 / interval) % 2 == 0 { 1 } else { -1 };
+
+            (handle, ScriptedInput(direction))
+        })
+        .collect::<HashMap<_, _>>();
+
+    commands.insert_resource(LocalInputs::<Config>(local_inputs));
+}
+
+/// Runs once when the session starts, so both peers spawn identical players before frame 0 is saved.
+fn spawn_players(mut commands: Commands, players: Res<GgrsPlayers>) {
+    for handle in 0..players.num_players {
+        commands
+            .spawn((Player { handle }, Position::default()))
+            .add_rollback();
+    }
+}
+
+fn move_players(mut players: Query<(&Player, &mut Position)>, inputs: Res<PlayerInputs<Config>>) {
+    for (player, mut position) in &mut players {
+        position.0 += inputs.by_handle(player.handle).0 .0 as i32;
+    }
+}
+
+fn print_events_system(mut session: ResMut<Session<Config>>) {
+    if let Session::P2P(s) = session.as_mut() {
+        for event in s.events() {
+            match event {
+                GgrsEvent::DesyncDetected { .. } => error!("GGRS event: {event:?}"),
+                _ => info!("GGRS event: {event:?}"),
+            }
+        }
+    }
+}
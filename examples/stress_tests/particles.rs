@@ -1,4 +1,6 @@
-use bevy::{math::vec3, prelude::*, utils::HashMap, window::WindowResolution};
+use bevy::{
+    math::vec3, prelude::*, transform::TransformSystem, utils::HashMap, window::WindowResolution,
+};
 use bevy_ggrs::{checksum_hasher, prelude::*, LocalInputs, LocalPlayers};
 use clap::Parser;
 use ggrs::{DesyncDetection, UdpNonBlockingSocket};
@@ -164,14 +166,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .add_systems(ReadInputs, read_local_inputs);
 
     if args.reflect {
-        // SpriteBundle types
+        // SpriteBundle types which affect the simulation. The rest only affect rendering, so
+        // are restored by `add_render_components` instead of being rolled back.
         app.rollback_component_with_reflect::<Sprite>()
             .rollback_component_with_reflect::<Transform>()
-            .rollback_component_with_reflect::<GlobalTransform>()
-            .rollback_component_with_reflect::<Handle<Image>>()
-            .rollback_component_with_reflect::<Visibility>()
-            .rollback_component_with_reflect::<InheritedVisibility>()
-            .rollback_component_with_reflect::<ViewVisibility>()
             // Also add our own types
             .rollback_component_with_reflect::<Velocity>()
             .rollback_component_with_reflect::<Ttl>()
@@ -182,14 +180,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     } else {
         // clone/copy-based rollback
 
-        // SpriteBundle types
+        // SpriteBundle types which affect the simulation
         app.rollback_component_with_clone::<Sprite>()
             .rollback_component_with_clone::<Transform>()
-            .rollback_component_with_clone::<GlobalTransform>()
-            .rollback_component_with_clone::<Handle<Image>>()
-            .rollback_component_with_clone::<Visibility>()
-            .rollback_component_with_clone::<InheritedVisibility>()
-            .rollback_component_with_clone::<ViewVisibility>()
             // Also add our own types
             .rollback_component_with_copy::<Velocity>()
             .rollback_component_with_copy::<Ttl>()
@@ -238,6 +231,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .insert_resource(ClearColor(Color::BLACK))
         .insert_resource(ParticleRng(GameRng::seed_from_u64(123)))
         .add_systems(Update, print_events_system)
+        .add_systems(
+            PostUpdate,
+            add_render_components.before(TransformSystem::TransformPropagate),
+        )
         .run();
 
     Ok(())
@@ -273,6 +270,21 @@ fn spawn_particles(mut commands: Commands, args: Res<Args>, mut rng: ResMut<Part
     }
 }
 
+/// Particles respawned by a rollback only have their rolled back components, so add back the
+/// remaining [`SpriteBundle`] components, which are not rolled back.
+fn add_render_components(
+    mut commands: Commands,
+    particles: Query<Entity, (With<Sprite>, Without<Handle<Image>>)>,
+) {
+    for entity in &particles {
+        commands.entity(entity).insert((
+            Handle::<Image>::default(),
+            GlobalTransform::default(),
+            VisibilityBundle::default(),
+        ));
+    }
+}
+
 fn update_particles(mut particles: Query<(&mut Transform, &mut Velocity)>, time: Res<Time>) {
     let time_step = time.delta_seconds();
     let gravity = Vec3::NEG_Y * 200.0;
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use ggrs::{Config, InputStatus, PlayerHandle};
+
+use crate::{Rollback, RollbackFrameCount, RollbackKind, RollbackOrdered, RollbackRegistry};
+
+/// A [`Resource`] listing players controlled by AI, whose inputs are produced by a function of
+/// the rollback state rather than read from a device. See [`GgrsPlugin::with_ai_players`](`crate::GgrsPlugin::with_ai_players`).
+///
+/// The function is called on every peer whenever a frame is advanced, including while
+/// re-simulating, with a [`RollbackView`] of the [`World`] as it was at the end of the previous
+/// frame. Its result replaces the input GGRS provides for the player in
+/// [`PlayerInputs`](`crate::PlayerInputs`). It must therefore be deterministic, which the
+/// [`RollbackView`] helps with by only exposing state which is rolled back, such as [`Rollback`]
+/// entities, rollback resources or a rolled back random number generator, since peers would
+/// otherwise disagree on the input and desync.
+///
+/// Each AI player must still be added to the session, usually as a local player of the host.
+/// That peer submits the function's result as the player's input, so remote peers usually
+/// predict it correctly, and does not need to provide it from the [`ReadInputs`](`crate::ReadInputs`)
+/// schedule.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{ggrs::PlayerHandle, prelude::*, RollbackView};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// #[derive(Resource, Clone, Copy)]
+/// struct BallPosition(i32);
+///
+/// /// Moves towards the ball, reading only rollback state
+/// fn chase_ball(_handle: PlayerHandle, world: &RollbackView) -> u8 {
+///     let ball = world.resource::<BallPosition>();
+///
+///     (ball.0 > 0) as u8
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<MyConfig>::default().with_ai_players([2, 3], chase_ball))
+///     .rollback_resource_with_copy::<BallPosition>();
+/// ```
+#[derive(Resource)]
+pub struct AiPlayers<C: Config> {
+    handles: Vec<PlayerHandle>,
+    input: fn(PlayerHandle, &RollbackView) -> C::Input,
+}
+
+impl<C: Config> Clone for AiPlayers<C> {
+    fn clone(&self) -> Self {
+        Self {
+            handles: self.handles.clone(),
+            input: self.input,
+        }
+    }
+}
+
+impl<C: Config> AiPlayers<C> {
+    /// Creates a list of AI players with the provided `handles`, whose inputs are produced by
+    /// `input`.
+    pub fn new(
+        handles: impl IntoIterator<Item = PlayerHandle>,
+        input: fn(PlayerHandle, &RollbackView) -> C::Input,
+    ) -> Self {
+        let mut handles = handles.into_iter().collect::<Vec<_>>();
+        handles.sort_unstable();
+        handles.dedup();
+
+        Self { handles, input }
+    }
+
+    /// The handles of every AI player, in order.
+    pub fn handles(&self) -> &[PlayerHandle] {
+        &self.handles
+    }
+
+    /// Returns `true` if the player with the provided `handle` is controlled by AI.
+    pub fn is_ai(&self, handle: PlayerHandle) -> bool {
+        self.handles.binary_search(&handle).is_ok()
+    }
+
+    /// Produces the input of the AI player with the provided `handle` from the rollback state
+    /// of `world`.
+    pub fn input(&self, handle: PlayerHandle, world: &World) -> C::Input {
+        (self.input)(handle, &RollbackView::new(world))
+    }
+}
+
+/// Replaces the inputs of AI players with the inputs produced from the current [`World`].
+pub(crate) fn apply_ai_inputs<C: Config>(world: &World, inputs: &mut [(C::Input, InputStatus)]) {
+    let Some(ai_players) = world.get_resource::<AiPlayers<C>>() else {
+        return;
+    };
+
+    for &handle in &ai_players.handles {
+        if let Some((input, _)) = inputs.get_mut(handle) {
+            *input = ai_players.input(handle, world);
+        }
+    }
+}
+
+/// A read-only view of the rollback state of a [`World`], passed to the [`AiPlayers`] input
+/// function. Only rollback resources and the rollback components of [`Rollback`] entities can be
+/// read, and entities are visited in [`RollbackOrdered`] order, so anything read is identical on
+/// every peer.
+///
+/// In debug builds, reading a type which has not been registered for rollback as the expected
+/// [`RollbackKind`] in the [`RollbackRegistry`] panics, since it would usually differ between peers.
+pub struct RollbackView<'w> {
+    world: &'w World,
+}
+
+impl<'w> RollbackView<'w> {
+    pub(crate) fn new(world: &'w World) -> Self {
+        Self { world }
+    }
+
+    /// The [`RollbackFrameCount`] of the frame being advanced from.
+    pub fn frame(&self) -> i32 {
+        self.world.resource::<RollbackFrameCount>().0
+    }
+
+    /// The rollback [`Resource`] `R`, if it exists.
+    pub fn get_resource<R: Resource>(&self) -> Option<&'w R> {
+        self.check_registered::<R>(RollbackKind::Resource);
+        self.world.get_resource::<R>()
+    }
+
+    /// The rollback [`Resource`] `R`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `R` does not exist.
+    pub fn resource<R: Resource>(&self) -> &'w R {
+        self.get_resource::<R>().unwrap_or_else(|| {
+            panic!(
+                "Resource {} requested by an AI player does not exist",
+                std::any::type_name::<R>()
+            )
+        })
+    }
+
+    /// Every [`Rollback`] entity with the rollback [`Component`] `T`, along with its value, in
+    /// [`RollbackOrdered`] order.
+    pub fn iter<T: Component>(&self) -> impl Iterator<Item = (Rollback, &'w T)> + 'w {
+        self.check_registered::<T>(RollbackKind::Component);
+
+        let items = self
+            .world
+            .iter_entities()
+            .filter_map(|entity| Some((*entity.get::<Rollback>()?, entity.get::<T>()?)))
+            .collect::<Vec<_>>();
+
+        let items = match self.world.get_resource::<RollbackOrdered>() {
+            Some(order) => order.sorted(items, |&(rollback, _)| rollback),
+            None => items,
+        };
+
+        items.into_iter()
+    }
+
+    fn check_registered<T: 'static>(&self, kind: RollbackKind) {
+        debug_assert!(
+            self.world
+                .get_resource::<RollbackRegistry>()
+                .is_some_and(|registry| registry.is_registered::<T>(kind)),
+            "{} was read by an AI player, but is not registered for {:?} rollback, so it may differ between peers",
+            std::any::type_name::<T>(),
+            kind
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::{prelude::*, reflect::GetTypeRegistration};
+
+/// A [`Component`] which can be registered for rollback. Implemented for every [`Component`], it
+/// is used as a bound of [`GgrsApp`](`crate::GgrsApp`) methods so that a missing derive is
+/// reported clearly by the compiler, rather than as a chain of unsatisfied bounds.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a `Component`, so it cannot be rolled back as one",
+    label = "not a `Component`",
+    note = "consider adding `#[derive(Component)]` to `{Self}`"
+)]
+pub trait RollbackComponent: Component {}
+
+impl<T: Component> RollbackComponent for T {}
+
+/// A [`Resource`] which can be registered for rollback. Implemented for every [`Resource`], see
+/// [`RollbackComponent`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a `Resource`, so it cannot be rolled back as one",
+    label = "not a `Resource`",
+    note = "consider adding `#[derive(Resource)]` to `{Self}`"
+)]
+pub trait RollbackResource: Resource {}
+
+impl<T: Resource> RollbackResource for T {}
+
+/// A type which can be snapshot using [reflection](`Reflect`), and registered in the
+/// [`AppTypeRegistry`]. Implemented for every such type, see [`RollbackComponent`].
+///
+/// # Examples
+/// ```rust,compile_fail,E0277
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// // error: `Health` is not reflected, so it cannot be rolled back with reflection
+/// #[derive(Component, Default)]
+/// struct Health(u32);
+///
+/// # let mut app = App::new();
+/// app.rollback_component_with_reflect::<Health>();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not reflected, so it cannot be rolled back with reflection",
+    label = "not reflected",
+    note = "consider adding `#[derive(Reflect)]` to `{Self}`",
+    note = "or roll it back with `_with_copy` or `_with_clone` registration methods instead"
+)]
+pub trait RollbackReflect: Reflect + GetTypeRegistration {}
+
+impl<T: Reflect + GetTypeRegistration> RollbackReflect for T {}
+
+/// A type which can be created from the [`World`], so reflection based snapshots can be loaded
+/// onto entities or into resources which do not currently have a value. Implemented for every
+/// [`FromWorld`] type, see [`RollbackComponent`].
+///
+/// # Examples
+/// ```rust,compile_fail,E0277
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// // error: `Health` cannot be created from the `World` to load reflected snapshots
+/// #[derive(Component, Reflect)]
+/// struct Health(u32);
+///
+/// # let mut app = App::new();
+/// app.rollback_component_with_reflect::<Health>();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be created from the `World` to load reflected snapshots",
+    label = "no `FromWorld` or `Default`",
+    note = "consider adding `#[derive(Default)]` to `{Self}`, or implementing `FromWorld`"
+)]
+pub trait RollbackFromWorld: FromWorld {}
+
+impl<T: FromWorld> RollbackFromWorld for T {}
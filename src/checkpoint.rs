@@ -0,0 +1,229 @@
+use std::any::TypeId;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    ComponentGroups, ErasedSnapshots, GgrsComponentSnapshots, LoadWorld, Rollback,
+    RollbackFrameCount, RollbackOrdered, SaveWorld, SnapshotStorages,
+};
+
+/// Saves a snapshot of the current [`World`] for the current [`RollbackFrameCount`] by running
+/// the [`SaveWorld`] schedule, exactly as GGRS would. Returns the frame the snapshot was saved for.
+///
+/// The snapshot is stored alongside those requested by GGRS, replacing any already saved for this
+/// frame, and is discarded once the session confirms a later frame. It is therefore suited to
+/// short lived checkpoints; to persist state for longer, capture a [`WorldSnapshot`] instead.
+///
+/// This requires exclusive access to the [`World`], and must not be called from within the
+/// [`LoadWorld`], [`SaveWorld`], or [`AdvanceWorld`](`crate::AdvanceWorld`) schedules.
+pub fn capture_snapshot(world: &mut World) -> i32 {
+    let frame = world
+        .get_resource::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+        .0;
+
+    world.run_schedule(SaveWorld);
+
+    frame
+}
+
+/// Loads the snapshot saved for `frame` into the [`World`] by running the [`LoadWorld`] schedule,
+/// exactly as GGRS would during a rollback. Returns `false` without modifying the [`World`] if no
+/// snapshot is stored for `frame`. Like a rollback, this discards any snapshots taken after `frame`.
+///
+/// GGRS is not informed of the load, so doing this during a [`Session`](`crate::Session`) will
+/// desync unless every peer does the same. The same scheduling restrictions as
+/// [`capture_snapshot`] apply.
+pub fn apply_snapshot(world: &mut World, frame: i32) -> bool {
+    let stored = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .is_some_and(|snapshots| snapshots.frames().any(|stored| stored == frame));
+
+    if !stored {
+        return false;
+    }
+
+    world
+        .get_resource_mut::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+        .0 = frame;
+
+    world.run_schedule(LoadWorld);
+
+    true
+}
+
+/// An owned snapshot of every rollback type in a [`World`], which can be applied any number of
+/// times later, such as to restart a round from a checkpoint.
+///
+/// Unlike [`capture_snapshot`], the snapshot is kept outside of the [`World`], so it is not
+/// discarded as the session confirms later frames, and neither the snapshots stored for GGRS
+/// nor those GGRS keeps itself are modified. It can only be applied to the [`World`] it was
+/// captured from, or one with the same rollback types registered.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, WorldSnapshot};
+/// #
+/// #[derive(Resource, Clone, Copy)]
+/// struct Score(u32);
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+///     .rollback_resource_with_copy::<Score>()
+///     .insert_resource(Score(3));
+///
+/// let mut checkpoint = WorldSnapshot::capture(&mut app.world);
+///
+/// app.world.resource_mut::<Score>().0 = 10;
+/// checkpoint.apply(&mut app.world);
+///
+/// assert_eq!(app.world.resource::<Score>().0, 3);
+/// ```
+pub struct WorldSnapshot {
+    frame: i32,
+    storages: HashMap<TypeId, ErasedSnapshots>,
+}
+
+impl WorldSnapshot {
+    /// Captures the current [`World`] for the current [`RollbackFrameCount`] by running the
+    /// [`SaveWorld`] schedule into empty storages, leaving the stored snapshots untouched.
+    ///
+    /// The same scheduling restrictions as [`capture_snapshot`] apply.
+    pub fn capture(world: &mut World) -> Self {
+        let frame = world
+            .get_resource::<RollbackFrameCount>()
+            .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+            .0;
+
+        // group snapshots are only reused from the storage the previous save was made into
+        let last_save = world
+            .get_resource::<ComponentGroups>()
+            .and_then(|groups| groups.last_save);
+
+        let stored = SnapshotStorages::swap_all(world, default());
+        world.run_schedule(SaveWorld);
+        let storages = SnapshotStorages::swap_all(world, stored);
+
+        if let Some(mut groups) = world.get_resource_mut::<ComponentGroups>() {
+            groups.last_save = last_save;
+        }
+
+        Self { frame, storages }
+    }
+
+    /// The [`RollbackFrameCount`] this snapshot was captured for.
+    pub fn frame(&self) -> i32 {
+        self.frame
+    }
+
+    /// Loads this snapshot into the [`World`] by running the [`LoadWorld`] schedule, and sets
+    /// the [`RollbackFrameCount`] to the captured [`frame`](`WorldSnapshot::frame`). The stored
+    /// snapshots are left untouched, and this snapshot can be applied again.
+    ///
+    /// GGRS is not informed of the load, so the same caveats as [`apply_snapshot`] apply.
+    pub fn apply(&mut self, world: &mut World) {
+        world
+            .get_resource_mut::<RollbackFrameCount>()
+            .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
+            .0 = self.frame;
+
+        let stored = SnapshotStorages::swap_all(world, std::mem::take(&mut self.storages));
+        world.run_schedule(LoadWorld);
+        self.storages = SnapshotStorages::swap_all(world, stored);
+    }
+}
+
+/// Spawns [`Rollback`] entities from externally stored data, such as a saved game, into a
+/// [`World`] which may not contain any of them yet. Each entity is spawned standing in for the
+/// `original` [`Entity`] it had when it was stored. Once every entity has been spawned,
+/// [`finish`](`EntityInstantiator::finish`) maps references to the original entities onto the new
+/// ones, using the same [`LoadWorldSet::Mapping`](`crate::LoadWorldSet::Mapping`) systems as a
+/// rollback, and saves a snapshot for the current [`RollbackFrameCount`].
+///
+/// Entities are ordered in [`RollbackOrdered`] in the order they are spawned, so spawn them in
+/// the order they had in [`RollbackOrdered`] when they were stored to keep checksums stable.
+/// The same scheduling restrictions as [`capture_snapshot`] apply.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, ecs::entity::{MapEntities, EntityMapper}};
+/// # use bevy_ggrs::{prelude::*, EntityInstantiator};
+/// #
+/// #[derive(Component, Clone)]
+/// struct Target(Entity);
+///
+/// impl MapEntities for Target {
+///     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+///         self.0 = entity_mapper.get_or_reserve(self.0);
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+///     .rollback_component_with_clone::<Target>()
+///     .update_component_with_map_entities::<Target>();
+///
+/// // Two entities which were stored as 4v0 and 7v0, targeting each other
+/// let (first, second) = (Entity::from_raw(4), Entity::from_raw(7));
+///
+/// let mut instantiator = EntityInstantiator::new(&mut app.world);
+/// instantiator.spawn(first, Target(second));
+/// instantiator.spawn(second, Target(first));
+/// let map = instantiator.finish();
+///
+/// let target = app.world.get::<Target>(map[&first]).unwrap();
+/// assert_eq!(target.0, map[&second]);
+/// ```
+pub struct EntityInstantiator<'w> {
+    world: &'w mut World,
+    map: HashMap<Entity, Entity>,
+}
+
+impl<'w> EntityInstantiator<'w> {
+    /// Prepares to spawn [`Rollback`] entities into the provided [`World`].
+    pub fn new(world: &'w mut World) -> Self {
+        Self {
+            world,
+            map: HashMap::default(),
+        }
+    }
+
+    /// Spawns a new [`Rollback`] entity with the provided [`Bundle`], standing in for `original`.
+    /// Returns the newly spawned [`Entity`].
+    pub fn spawn(&mut self, original: Entity, bundle: impl Bundle) -> Entity {
+        let entity = self.world.spawn(bundle).id();
+        let rollback = Rollback::new(entity);
+
+        self.world.entity_mut(entity).insert(rollback);
+        self.world
+            .get_resource_or_insert_with::<RollbackOrdered>(default)
+            .push(rollback);
+
+        self.map.insert(original, entity);
+
+        entity
+    }
+
+    /// Maps references to the original entities onto the spawned ones, and saves a snapshot so the
+    /// spawned entities are restored by rollbacks to the current frame. Returns the mapping from
+    /// each original [`Entity`] to its spawned replacement.
+    pub fn finish(self) -> HashMap<Entity, Entity> {
+        let Self { world, map } = self;
+
+        // loading requires a snapshot of the current frame, which still holds unmapped references
+        capture_snapshot(world);
+
+        world.insert_resource(InstantiatedEntityMap(map.clone()));
+        world.run_schedule(LoadWorld);
+
+        capture_snapshot(world);
+
+        map
+    }
+}
+
+/// Replaces the [`RollbackEntityMap`](`crate::RollbackEntityMap`) of the next load, see [`EntityInstantiator`].
+#[derive(Resource)]
+pub(crate) struct InstantiatedEntityMap(pub(crate) HashMap<Entity, Entity>);
@@ -0,0 +1,56 @@
+//! Tools for inspecting snapshot history during development.
+//!
+//! Everything here bypasses GGRS entirely. Loading a snapshot this way desyncs the local
+//! [`World`] from every other client, so these must never be used in a live [`Session`](`crate::Session`).
+
+use bevy::prelude::*;
+
+use crate::{apply_snapshot, GgrsComponentSnapshots, RollbackFrameCount};
+
+/// Frames which currently have a snapshot stored, newest first.
+///
+/// With a [`SnapshotKeyframeInterval`](`crate::SnapshotKeyframeInterval`) above `1`,
+/// only keyframes are listed.
+pub fn available_frames(world: &World) -> Vec<i32> {
+    world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .map(|snapshots| snapshots.frames().collect())
+        .unwrap_or_default()
+}
+
+/// Loads the snapshot for `frame` into the [`World`], see [`apply_snapshot`]. Returns `false`
+/// without modifying the [`World`] if no snapshot is stored for `frame`, see [`available_frames`].
+///
+/// WARNING: This is intended for debugging only. GGRS is not informed of the load, so the
+/// current [`Session`](`crate::Session`) will desync.
+pub fn debug_load(world: &mut World, frame: i32) -> bool {
+    if !available_frames(world).contains(&frame) {
+        return false;
+    }
+
+    warn!("Loading snapshot for frame {frame} outside of GGRS, the session will desync");
+
+    apply_snapshot(world, frame)
+}
+
+/// Steps the [`World`] back by one frame, loading the snapshot of the frame before the current
+/// [`RollbackFrameCount`] and making it the current frame. Returns
+/// `false` without modifying the [`World`] if that frame has no snapshot stored, such as once the
+/// oldest stored frame has been reached. Calling this repeatedly allows scrubbing backwards
+/// through recent history, such as from a debugging UI.
+///
+/// Like any load, this discards every snapshot after the loaded frame, so frames stepped over can
+/// only be returned to by advancing the simulation again.
+///
+/// WARNING: This is intended for debugging only. GGRS is not informed of the load, so the
+/// current [`Session`](`crate::Session`) will desync.
+pub fn debug_step_back(world: &mut World) -> bool {
+    let Some(frame) = world
+        .get_resource::<RollbackFrameCount>()
+        .map(|frame| frame.0)
+    else {
+        return false;
+    };
+
+    debug_load(world, frame - 1)
+}
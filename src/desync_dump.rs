@@ -0,0 +1,332 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    fmt::Write,
+    io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{prelude::*, utils::HashMap};
+use ggrs::Config;
+
+use crate::{
+    Checksum, ChecksumDiagnostics, ConfirmedFrameCount, GgrsComponentSnapshots, PlayerInputHistory,
+    Rollback, RollbackFrameCount, RollbackOrdered, DEFAULT_FPS,
+};
+
+/// A [`Resource`] which causes a report to be written to [`path`](`DesyncDump::path`) whenever a
+/// [`SyncTestSession`](`ggrs::SyncTestSession`) detects mismatched checksums, before the error is
+/// logged or [`StrictErrors`](`crate::StrictErrors`) panics. Each report replaces the previous one.
+/// See [`GgrsPlugin::with_desync_dump`](`crate::GgrsPlugin::with_desync_dump`) and
+/// [`write_desync_dump`] for what is included.
+#[derive(Resource, Clone)]
+pub struct DesyncDump {
+    path: PathBuf,
+    write: fn(&World, &Path, &str) -> io::Result<()>,
+}
+
+impl DesyncDump {
+    /// Creates a [`DesyncDump`] which writes reports produced by [`write_desync_dump`] to `path`.
+    pub fn new<C: Config>(path: impl Into<PathBuf>) -> Self
+    where
+        C::Input: bytemuck::Pod,
+    {
+        Self {
+            path: path.into(),
+            write: |world, path, reason| write_desync_dump::<C>(world, path, reason),
+        }
+    }
+
+    /// The file reports are written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes a report for `reason`, if a [`DesyncDump`] has been configured.
+    pub(crate) fn write_if_enabled(world: &World, reason: &str) {
+        let Some(dump) = world.get_resource::<Self>() else {
+            return;
+        };
+
+        match (dump.write)(world, &dump.path, reason) {
+            Ok(()) => warn!("Wrote desync dump to {}", dump.path.display()),
+            Err(error) => warn!(
+                "Unable to write desync dump to {}: {error}",
+                dump.path.display()
+            ),
+        }
+    }
+}
+
+/// The first and most recent values saved for a single frame, as listed in a desync report.
+struct SavedValues {
+    first: String,
+    last: String,
+}
+
+/// A [`Resource`] recording the values of every [`Rollback`] entity each time a frame is saved,
+/// so a desync report can include the values stored for the frame which mismatched. Like
+/// [`ChecksumDiagnostics`], frames are kept across rollbacks so re-saved frames can be compared
+/// against their original.
+#[derive(Resource, Default)]
+pub(crate) struct DesyncDumpHistory {
+    frames: BTreeMap<i32, SavedValues>,
+}
+
+impl DesyncDumpHistory {
+    /// An exclusive system which records the values of the frame currently being saved.
+    pub(crate) fn record(world: &mut World) {
+        let frame = world.resource::<RollbackFrameCount>().0;
+
+        let mut values = String::new();
+        write_entities(world, &mut values);
+
+        let mut history = world.resource_mut::<Self>();
+
+        match history.frames.entry(frame) {
+            Entry::Occupied(mut saved) => saved.get_mut().last = values,
+            Entry::Vacant(entry) => {
+                entry.insert(SavedValues {
+                    first: values.clone(),
+                    last: values,
+                });
+            }
+        }
+
+        while history.frames.len() > DEFAULT_FPS {
+            history.frames.pop_first();
+        }
+    }
+
+    /// Forgets every recorded frame, since frames of the next [`Session`](`crate::Session`) are unrelated.
+    pub(crate) fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// The earliest recorded frame which was saved again with different values, if any.
+    fn mismatched_frame(&self) -> Option<i32> {
+        self.frames
+            .iter()
+            .find(|(_, saved)| saved.first != saved.last)
+            .map(|(&frame, _)| frame)
+    }
+}
+
+/// Writes a plain text report of the current [`World`] to `path` for post-mortem analysis of a
+/// desync, replacing any existing file. The report contains:
+/// - `reason`, the current [`RollbackFrameCount`], [`ConfirmedFrameCount`] and [`Checksum`].
+/// - Every [`Rollback`] entity, with the value of each [`Component`] registered in the
+///   [`AppTypeRegistry`] for [reflection](`Reflect`). Other components are listed by name only.
+/// - The checksum contribution of each component for every stored snapshot, if the
+///   [`ChecksumDiagnosticsPlugin`](`crate::ChecksumDiagnosticsPlugin`) has been added.
+/// - The values stored for the earliest frame which was saved again with different values, as
+///   first and last saved, and for the frame before it, if a [`DesyncDump`] is configured.
+/// - The bytes of every input in the [`PlayerInputHistory`], if enabled.
+///
+/// This is called automatically for a [`SyncTestSession`](`ggrs::SyncTestSession`) when a
+/// [`DesyncDump`] is configured. A [`P2PSession`](`ggrs::P2PSession`) reports desyncs as a
+/// [`GgrsEvent::DesyncDetected`](`ggrs::GgrsEvent::DesyncDetected`) instead, so call this when
+/// handling the event.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, write_desync_dump};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn dump_desyncs(world: &mut World) {
+///     let mut session = world.resource_mut::<Session<MyConfig>>();
+///
+///     let Session::P2P(session) = session.as_mut() else {
+///         return;
+///     };
+///
+///     let desyncs = session
+///         .events()
+///         .filter(|event| matches!(event, GgrsEvent::DesyncDetected { .. }))
+///         .collect::<Vec<_>>();
+///
+///     for desync in desyncs {
+///         let reason = format!("{desync:?}");
+///
+///         if let Err(error) = write_desync_dump::<MyConfig>(world, "desync.txt", &reason) {
+///             error!("Unable to write desync dump: {error}");
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_systems(Update, dump_desyncs);
+/// ```
+pub fn write_desync_dump<C: Config>(
+    world: &World,
+    path: impl AsRef<Path>,
+    reason: &str,
+) -> io::Result<()>
+where
+    C::Input: bytemuck::Pod,
+{
+    std::fs::write(path, desync_report::<C>(world, reason))
+}
+
+fn desync_report<C: Config>(world: &World, reason: &str) -> String
+where
+    C::Input: bytemuck::Pod,
+{
+    // writing to a String cannot fail
+    let mut report = String::new();
+
+    let frame = world
+        .get_resource::<RollbackFrameCount>()
+        .map(|frame| frame.0);
+    let confirmed = world
+        .get_resource::<ConfirmedFrameCount>()
+        .map(|&frame| i32::from(frame));
+    let checksum = world.get_resource::<Checksum>().map(|checksum| checksum.0);
+    let stored_frames = world
+        .get_resource::<GgrsComponentSnapshots<Entity>>()
+        .map(|snapshots| snapshots.frames().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let _ = writeln!(report, "bevy_ggrs desync dump");
+    let _ = writeln!(report, "Reason: {reason}");
+    let _ = writeln!(report, "Frame: {frame:?}");
+    let _ = writeln!(report, "Confirmed frame: {confirmed:?}");
+    let _ = writeln!(report, "Checksum: {checksum:x?}");
+    let _ = writeln!(report, "Stored snapshots: {stored_frames:?}");
+
+    write_entities(world, &mut report);
+
+    if let Some(history) = world.get_resource::<DesyncDumpHistory>() {
+        write_stored_values(history, &mut report);
+    }
+
+    if let Some(diagnostics) = world.get_resource::<ChecksumDiagnostics>() {
+        let _ = writeln!(report, "\nChecksum contributions");
+
+        for &frame in &stored_frames {
+            let Some(breakdown) = diagnostics.breakdown(frame) else {
+                continue;
+            };
+
+            let mut contributions = breakdown.iter().collect::<Vec<_>>();
+            contributions
+                .sort_by_key(|&(&(rollback, type_name), _)| (type_name, format!("{rollback:?}")));
+
+            let _ = writeln!(report, "  Frame {frame}");
+
+            for ((rollback, type_name), checksum) in contributions {
+                let _ = writeln!(report, "    {type_name} on {rollback:?}: {checksum:016x}");
+            }
+        }
+    }
+
+    if let Some(history) = world.get_resource::<PlayerInputHistory<C>>() {
+        let _ = writeln!(report, "\nInput history, newest first");
+
+        for frames_ago in 0..history.len() {
+            let _ = write!(report, "  {frames_ago} frame(s) ago:");
+
+            for handle in 0.. {
+                let Some((input, status)) = history.get(handle, frames_ago) else {
+                    break;
+                };
+
+                let _ = write!(
+                    report,
+                    " {handle}: {:02x?} ({status:?})",
+                    bytemuck::bytes_of(input)
+                );
+            }
+
+            let _ = writeln!(report);
+        }
+    }
+
+    report
+}
+
+/// Lists the values stored for the mismatched frame and the frame before it.
+fn write_stored_values(history: &DesyncDumpHistory, report: &mut String) {
+    let _ = writeln!(report, "\nStored values");
+
+    let Some(mismatched) = history.mismatched_frame() else {
+        let _ = writeln!(report, "  No frame was saved again with different values");
+        return;
+    };
+
+    let mut write_values = |label: String, values: &str| {
+        let _ = writeln!(report, "  {label}");
+
+        for line in values.lines().filter(|line| !line.is_empty()) {
+            let _ = writeln!(report, "  {line}");
+        }
+    };
+
+    if let Some(previous) = history.frames.get(&(mismatched - 1)) {
+        write_values(
+            format!("Frame {}, last saved", mismatched - 1),
+            &previous.last,
+        );
+    }
+
+    let saved = &history.frames[&mismatched];
+    write_values(format!("Frame {mismatched}, first saved"), &saved.first);
+    write_values(format!("Frame {mismatched}, last saved"), &saved.last);
+}
+
+/// Lists every [`Rollback`] entity in [`RollbackOrdered`] order, with its components.
+fn write_entities(world: &World, report: &mut String) {
+    let order = world
+        .get_resource::<RollbackOrdered>()
+        .map(|ordered| {
+            ordered
+                .iter_sorted()
+                .enumerate()
+                .map(|(order, rollback)| (rollback, order))
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let mut entities = world
+        .iter_entities()
+        .filter_map(|entity| Some((*entity.get::<Rollback>()?, entity)))
+        .collect::<Vec<_>>();
+
+    entities.sort_by_key(|(rollback, _)| order.get(rollback).copied().unwrap_or(usize::MAX));
+
+    let registry = world
+        .get_resource::<AppTypeRegistry>()
+        .map(|registry| registry.read());
+
+    let _ = writeln!(report, "\nRollback entities: {}", entities.len());
+
+    for (rollback, entity) in entities {
+        let _ = writeln!(report, "  {rollback:?} as {:?}", entity.id());
+
+        let mut components = entity
+            .archetype()
+            .components()
+            .filter_map(|id| world.components().get_info(id))
+            .collect::<Vec<_>>();
+
+        components.sort_by_key(|info| info.name());
+
+        for info in components {
+            let value = info
+                .type_id()
+                .zip(registry.as_ref())
+                .and_then(|(type_id, registry)| registry.get(type_id))
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .and_then(|reflect| reflect.reflect(entity));
+
+            match value {
+                Some(value) => {
+                    let _ = writeln!(report, "    {}: {value:?}", info.name());
+                }
+                None => {
+                    let _ = writeln!(report, "    {} (not reflected)", info.name());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::{any::TypeId, borrow::Cow, fmt};
+
+use bevy::{
+    ecs::schedule::InternedScheduleLabel,
+    input::{gamepad::GamepadButton, touch::Touches},
+    prelude::*,
+    time::{Real, Virtual},
+};
+
+use crate::{GgrsSchedule, RollbackKind, RollbackRegistry, RollbackSchedules};
+
+/// A system in one of the [`RollbackSchedules`] which accesses a [`Resource`] in a way that is likely
+/// to cause a desync. See [`check_determinism`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeterminismWarning {
+    /// Name of the offending system.
+    pub system: Cow<'static, str>,
+    /// Name of the [`Resource`] it accesses.
+    pub resource: String,
+    /// Why the access is likely to cause a desync.
+    pub reason: &'static str,
+}
+
+impl fmt::Display for DeterminismWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} accesses {} in the GgrsSchedule, but {}",
+            self.system, self.resource, self.reason
+        )
+    }
+}
+
+/// Resources which differ between peers and must not be read while advancing a rollback frame.
+fn nondeterministic_resources() -> [(TypeId, &'static str); 6] {
+    const CLOCK: &str = "it follows the local clock. Use Res<Time> instead, which is rolled back";
+    const DEVICE: &str = "it reflects local devices. Read inputs in the ReadInputs schedule and use PlayerInputs instead";
+
+    [
+        (TypeId::of::<Time<Real>>(), CLOCK),
+        (TypeId::of::<Time<Virtual>>(), CLOCK),
+        (TypeId::of::<Input<KeyCode>>(), DEVICE),
+        (TypeId::of::<Input<MouseButton>>(), DEVICE),
+        (TypeId::of::<Input<GamepadButton>>(), DEVICE),
+        (TypeId::of::<Touches>(), DEVICE),
+    ]
+}
+
+/// Inspects the resource access of every system in the [`GgrsSchedule`], and any other
+/// [`RollbackSchedules`], returning a warning for each access which is likely to be nondeterministic:
+/// - Reading a local clock, such as [`Time<Real>`], or a local input device, such as [`Input<KeyCode>`].
+/// - Mutating a [`Resource`] which has not been registered for rollback, other than [`Events`].
+///
+/// This is a heuristic. It cannot see access through [`Commands`] or exclusive systems, and a
+/// clean result does not guarantee determinism. The schedules are initialized if required.
+/// See [`GgrsPlugin::with_determinism_lint`](`crate::GgrsPlugin::with_determinism_lint`) to run
+/// this automatically.
+pub fn check_determinism(world: &mut World) -> Vec<DeterminismWarning> {
+    let labels = world
+        .get_resource::<RollbackSchedules>()
+        .map(|schedules| schedules.iter().collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![GgrsSchedule.intern()]);
+
+    labels
+        .into_iter()
+        .flat_map(|label| check_schedule_determinism(world, label))
+        .collect()
+}
+
+fn check_schedule_determinism(
+    world: &mut World,
+    label: InternedScheduleLabel,
+) -> Vec<DeterminismWarning> {
+    let Some(mut schedule) = world.resource_mut::<Schedules>().remove(label) else {
+        return Vec::new();
+    };
+
+    if let Err(error) = schedule.initialize(world) {
+        warn!("Unable to check {label:?} for determinism: {error}");
+        world.resource_mut::<Schedules>().insert(schedule);
+        return Vec::new();
+    }
+
+    let components = world.components();
+    let registry = world.get_resource::<RollbackRegistry>();
+
+    let nondeterministic = nondeterministic_resources()
+        .into_iter()
+        .filter_map(|(type_id, reason)| Some((components.get_resource_id(type_id)?, reason)))
+        .collect::<Vec<_>>();
+
+    let mut warnings = Vec::new();
+
+    for (_, system) in schedule.systems().expect("Schedule was initialized above") {
+        let access = system.component_access();
+
+        for &(id, reason) in &nondeterministic {
+            if access.has_read(id) {
+                warnings.push(DeterminismWarning {
+                    system: system.name(),
+                    resource: components.get_info(id).unwrap().name().to_string(),
+                    reason,
+                });
+            }
+        }
+
+        for id in access.writes() {
+            let Some(info) = components.get_info(id) else {
+                continue;
+            };
+
+            let Some(type_id) = info.type_id() else {
+                continue;
+            };
+
+            let is_resource = components.get_resource_id(type_id) == Some(id);
+            let is_event = info.name().starts_with("bevy_ecs::event::Events<");
+            let is_registered =
+                registry.is_some_and(|registry| registry.contains(type_id, RollbackKind::Resource));
+
+            if is_resource && !is_event && !is_registered {
+                warnings.push(DeterminismWarning {
+                    system: system.name(),
+                    resource: info.name().to_string(),
+                    reason:
+                        "it is not registered for rollback, so changes to it will not be undone",
+                });
+            }
+        }
+    }
+
+    world.resource_mut::<Schedules>().insert(schedule);
+
+    warnings
+}
@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use ggrs::{Config, InputStatus};
+
+/// A [`Resource`] choosing which input is provided in [`PlayerInputs`](`crate::PlayerInputs`) for
+/// players who have disconnected, marked [`InputStatus::Disconnected`]. Without it, the input
+/// provided by GGRS is used unchanged.
+///
+/// The [`InputStatus`] is left as [`Disconnected`](`InputStatus::Disconnected`), so gameplay can
+/// still tell these players apart. See
+/// [`GgrsPlugin::with_disconnect_input_policy`](`crate::GgrsPlugin::with_disconnect_input_policy`).
+///
+/// Rather than inserting this resource directly, use
+/// [`GgrsApp::set_disconnect_input_policy`](`crate::GgrsApp::set_disconnect_input_policy`), or the
+/// inputs repeated by [`DisconnectInputPolicy::Repeat`] are not rolled back, and re-simulated frames
+/// may repeat a different input.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, DisconnectInputPolicy};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// # let mut app = App::new();
+/// // Disconnected players keep doing whatever they were last doing
+/// app.add_plugins(
+///     GgrsPlugin::<MyConfig>::default()
+///         .with_disconnect_input_policy(DisconnectInputPolicy::Repeat),
+/// );
+/// ```
+#[derive(Resource)]
+pub enum DisconnectInputPolicy<C: Config> {
+    /// Repeat the last input received from the player before they disconnected.
+    Repeat,
+    /// Provide this input, such as one with no buttons pressed.
+    Neutral(C::Input),
+}
+
+impl<C: Config> Clone for DisconnectInputPolicy<C> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Repeat => Self::Repeat,
+            Self::Neutral(input) => Self::Neutral(*input),
+        }
+    }
+}
+
+/// The last input of each player which was not [`InputStatus::Disconnected`], for
+/// [`DisconnectInputPolicy::Repeat`]. Rolled back once registered by
+/// [`GgrsApp::set_disconnect_input_policy`](`crate::GgrsApp::set_disconnect_input_policy`), so
+/// re-simulated frames repeat the same input.
+#[derive(Resource)]
+pub(crate) struct LastConnectedInputs<C: Config>(Vec<Option<C::Input>>);
+
+impl<C: Config> Default for LastConnectedInputs<C> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<C: Config> Clone for LastConnectedInputs<C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Replaces the inputs of disconnected players according to the [`DisconnectInputPolicy`], if any.
+pub(crate) fn apply_disconnect_input_policy<C: Config>(
+    world: &mut World,
+    inputs: &mut [(C::Input, InputStatus)],
+) {
+    let Some(policy) = world.get_resource::<DisconnectInputPolicy<C>>().cloned() else {
+        return;
+    };
+
+    match policy {
+        DisconnectInputPolicy::Neutral(neutral) => {
+            for (input, status) in inputs.iter_mut() {
+                if *status == InputStatus::Disconnected {
+                    *input = neutral;
+                }
+            }
+        }
+        DisconnectInputPolicy::Repeat => {
+            let mut last = world.get_resource_or_insert_with(LastConnectedInputs::<C>::default);
+            last.0.resize(inputs.len(), None);
+
+            for ((input, status), last) in inputs.iter_mut().zip(last.0.iter_mut()) {
+                if *status == InputStatus::Disconnected {
+                    if let Some(last) = last {
+                        *input = *last;
+                    }
+                } else {
+                    *last = Some(*input);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use ggrs::{Config, InputStatus, PlayerHandle};
+
+/// A [`Resource`] holding the inputs of every player for the most recent frames, including the
+/// frame currently being advanced. Inputs are appended before each frame is advanced, and the
+/// history is rolled back with the rest of the [`World`], so it always matches the frame being
+/// simulated. This allows reading recent input deterministically, such as for the command
+/// inputs of a fighting game. See [`GgrsPlugin::with_input_history`](`crate::GgrsPlugin::with_input_history`).
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, PlayerInputHistory};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// const PUNCH: u8 = 1 << 0;
+///
+/// fn detect_double_punch(history: Res<PlayerInputHistory<MyConfig>>) {
+///     let punches = history
+///         .player(0)
+///         .take(10)
+///         .filter(|(input, _)| input & PUNCH != 0)
+///         .count();
+///
+///     if punches >= 2 {
+///         info!("Player 0 punched twice within 10 frames");
+///     }
+/// }
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<MyConfig>::default().with_input_history(10));
+/// # app.add_systems(GgrsSchedule, detect_double_punch);
+/// ```
+#[derive(Resource)]
+pub struct PlayerInputHistory<C: Config> {
+    capacity: usize,
+    /// Inputs of every player, newest frame first.
+    frames: VecDeque<Vec<(C::Input, InputStatus)>>,
+}
+
+impl<C: Config> Clone for PlayerInputHistory<C> {
+    fn clone(&self) -> Self {
+        Self {
+            capacity: self.capacity,
+            frames: self.frames.clone(),
+        }
+    }
+}
+
+impl<C: Config> PlayerInputHistory<C> {
+    /// Creates an empty history which keeps the inputs of the most recent `capacity` frames.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// The number of frames kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of frames currently stored, which is less than the [`capacity`](`Self::capacity`)
+    /// until enough frames have been advanced.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames have been advanced yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Get the input of the player with the provided `handle` from `frames_ago` frames before the
+    /// current frame, where `0` is the current frame. Returns [`None`] if that frame is no longer
+    /// stored, or `handle` does not belong to a player in the [`Session`](`crate::Session`).
+    pub fn get(&self, handle: PlayerHandle, frames_ago: usize) -> Option<&(C::Input, InputStatus)> {
+        self.frames.get(frames_ago)?.get(handle)
+    }
+
+    /// Iterate over the stored inputs of the player with the provided `handle`, newest first.
+    pub fn player(
+        &self,
+        handle: PlayerHandle,
+    ) -> impl Iterator<Item = &(C::Input, InputStatus)> + '_ {
+        self.frames
+            .iter()
+            .filter_map(move |inputs| inputs.get(handle))
+    }
+
+    /// Records the inputs of every player for the frame about to be advanced.
+    pub(crate) fn push(&mut self, inputs: &[(C::Input, InputStatus)]) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.frames.len() == self.capacity {
+            self.frames.pop_back();
+        }
+
+        self.frames.push_front(inputs.to_vec());
+    }
+}
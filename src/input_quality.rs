@@ -0,0 +1,129 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use ggrs::{InputStatus, PlayerHandle};
+
+/// A [`Resource`] counting how often the input of each player was [`Confirmed`](`InputStatus::Confirmed`),
+/// [`Predicted`](`InputStatus::Predicted`) or [`Disconnected`](`InputStatus::Disconnected`)
+/// when each of the most recent [`window`](`Self::window`) frames was first advanced. A remote
+/// player whose inputs are often predicted is sending them late, which is useful for connection
+/// quality indicators.
+///
+/// Frames re-simulated during a rollback are not counted again, and the counts are cleared when
+/// the [`Session`](`crate::Session`) is replaced. Defaults to a window of `60` frames; insert a
+/// new [`GgrsInputQuality`] to change it.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsInputQuality};
+/// #
+/// fn show_connection_quality(quality: Res<GgrsInputQuality>) {
+///     for handle in 0..quality.num_players() {
+///         let predicted = quality.predicted_ratio(handle) * 100.0;
+///         info!("Predicted {predicted:.0}% of player {handle}'s inputs");
+///     }
+/// }
+/// # let mut app = App::new();
+/// app.insert_resource(GgrsInputQuality::new(120))
+///     .add_systems(Update, show_connection_quality);
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct GgrsInputQuality {
+    window: usize,
+    /// The newest frame counted, if any.
+    newest_frame: Option<i32>,
+    /// Input status of every player, newest frame first.
+    frames: VecDeque<Vec<InputStatus>>,
+}
+
+impl Default for GgrsInputQuality {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
+impl GgrsInputQuality {
+    /// Creates empty counts over the most recent `window` frames.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            newest_frame: None,
+            frames: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// The number of frames counted over.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// The number of frames currently counted, which is less than the [`window`](`Self::window`)
+    /// until enough frames have been advanced.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames have been counted yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The number of players in the most recently counted frame.
+    pub fn num_players(&self) -> usize {
+        self.frames.front().map_or(0, Vec::len)
+    }
+
+    /// The number of counted frames in which the input of the player with the provided `handle`
+    /// had the provided `status`.
+    pub fn count(&self, handle: PlayerHandle, status: InputStatus) -> usize {
+        self.frames
+            .iter()
+            .filter(|statuses| statuses.get(handle) == Some(&status))
+            .count()
+    }
+
+    /// The number of counted frames in which the input of `handle` was confirmed.
+    pub fn confirmed(&self, handle: PlayerHandle) -> usize {
+        self.count(handle, InputStatus::Confirmed)
+    }
+
+    /// The number of counted frames in which the input of `handle` was predicted.
+    pub fn predicted(&self, handle: PlayerHandle) -> usize {
+        self.count(handle, InputStatus::Predicted)
+    }
+
+    /// The number of counted frames in which `handle` was disconnected.
+    pub fn disconnected(&self, handle: PlayerHandle) -> usize {
+        self.count(handle, InputStatus::Disconnected)
+    }
+
+    /// The fraction of counted frames, between `0` and `1`, in which the input of `handle` was
+    /// predicted. Returns `0` if no frames have been counted.
+    pub fn predicted_ratio(&self, handle: PlayerHandle) -> f32 {
+        if self.frames.is_empty() {
+            return 0.0;
+        }
+
+        self.predicted(handle) as f32 / self.frames.len() as f32
+    }
+
+    /// Counts the input statuses of `frame`, unless it has already been advanced before.
+    pub(crate) fn record(&mut self, frame: i32, statuses: impl IntoIterator<Item = InputStatus>) {
+        if self.newest_frame.is_some_and(|newest| frame <= newest) {
+            return;
+        }
+
+        self.newest_frame = Some(frame);
+
+        if self.window == 0 {
+            return;
+        }
+
+        if self.frames.len() == self.window {
+            self.frames.pop_back();
+        }
+
+        self.frames.push_front(statuses.into_iter().collect());
+    }
+}
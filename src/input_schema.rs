@@ -0,0 +1,143 @@
+use bevy::{prelude::*, utils::HashMap};
+use ggrs::{Config, PlayerHandle};
+
+use crate::PlayerInputs;
+
+/// A player-specific input which is packed into the single [`Config::Input`] type shared by every
+/// player, for asymmetric games where players in different roles have different controls.
+///
+/// Since the packed input does not say which role it was encoded for, decoding relies on the
+/// `Schema` associated with each player through a [`PlayerInputSchema`].
+pub trait TaggedInput<I>: Sized {
+    /// Describes which variant of this input a player sends, typically their role.
+    type Schema: Send + Sync + 'static;
+
+    /// Packs this input into the shared input type, for use in [`LocalInputs`](`crate::LocalInputs`).
+    fn encode(&self) -> I;
+
+    /// Unpacks an input which was [encoded](`TaggedInput::encode`) by a player using `schema`.
+    fn decode(schema: &Self::Schema, input: &I) -> Self;
+}
+
+/// A [`Resource`] associating each [`PlayerHandle`] with the `Schema` of their [`TaggedInput`],
+/// such as whether they are an attacker or a defender.
+///
+/// Every peer must associate the same schemas with the same handles, or inputs will be decoded
+/// differently on each peer and desync. Changing a schema mid-session, such as swapping roles
+/// between rounds, must happen inside the [`GgrsSchedule`](`crate::GgrsSchedule`), with this
+/// resource registered for rollback.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, PlayerInputSchema, TaggedInput};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// enum Role {
+///     Attacker,
+///     Defender,
+/// }
+///
+/// enum RoleInput {
+///     Attacker { fire: bool },
+///     Defender { shield: bool },
+/// }
+///
+/// impl TaggedInput<u8> for RoleInput {
+///     type Schema = Role;
+///
+///     fn encode(&self) -> u8 {
+///         match *self {
+///             RoleInput::Attacker { fire } => fire as u8,
+///             RoleInput::Defender { shield } => shield as u8,
+///         }
+///     }
+///
+///     fn decode(role: &Role, input: &u8) -> Self {
+///         match role {
+///             Role::Attacker => RoleInput::Attacker { fire: *input & 1 != 0 },
+///             Role::Defender => RoleInput::Defender { shield: *input & 1 != 0 },
+///         }
+///     }
+/// }
+///
+/// fn apply_inputs(inputs: Res<PlayerInputs<MyConfig>>, schema: Res<PlayerInputSchema<Role>>) {
+///     for (handle, input) in schema.decode_all::<MyConfig, RoleInput>(&inputs) {
+///         match input {
+///             RoleInput::Attacker { fire: true } => info!("Player {handle} fired"),
+///             RoleInput::Defender { shield: true } => info!("Player {handle} raised a shield"),
+///             _ => {}
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// app.insert_resource(PlayerInputSchema::new([(0, Role::Attacker), (1, Role::Defender)]));
+/// # app.add_systems(GgrsSchedule, apply_inputs);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct PlayerInputSchema<S> {
+    schemas: HashMap<PlayerHandle, S>,
+}
+
+impl<S> Default for PlayerInputSchema<S> {
+    fn default() -> Self {
+        Self { schemas: default() }
+    }
+}
+
+impl<S> PlayerInputSchema<S> {
+    /// Creates a new [`PlayerInputSchema`] from `(handle, schema)` pairs.
+    pub fn new(schemas: impl IntoIterator<Item = (PlayerHandle, S)>) -> Self {
+        Self {
+            schemas: schemas.into_iter().collect(),
+        }
+    }
+
+    /// Associates `handle` with `schema`, returning the schema previously associated with it.
+    pub fn insert(&mut self, handle: PlayerHandle, schema: S) -> Option<S> {
+        self.schemas.insert(handle, schema)
+    }
+
+    /// The schema associated with `handle`, if any.
+    pub fn get(&self, handle: PlayerHandle) -> Option<&S> {
+        self.schemas.get(&handle)
+    }
+
+    /// Decodes the input of the player with the provided `handle` using their schema.
+    /// Returns [`None`] if no schema is associated with `handle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` does not belong to a player in the [`Session`](`crate::Session`).
+    pub fn decode<T, D>(&self, inputs: &PlayerInputs<T>, handle: PlayerHandle) -> Option<D>
+    where
+        T: Config,
+        D: TaggedInput<T::Input, Schema = S>,
+    {
+        let schema = self.get(handle)?;
+        let (input, _) = inputs.by_handle(handle);
+
+        Some(D::decode(schema, input))
+    }
+
+    /// Decodes the input of every player with an associated schema, in order of [`PlayerHandle`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a schema is associated with a handle which does not belong to a player in the
+    /// [`Session`](`crate::Session`).
+    pub fn decode_all<T, D>(&self, inputs: &PlayerInputs<T>) -> Vec<(PlayerHandle, D)>
+    where
+        T: Config,
+        D: TaggedInput<T::Input, Schema = S>,
+    {
+        let mut handles = self.schemas.keys().copied().collect::<Vec<_>>();
+        handles.sort_unstable();
+
+        handles
+            .into_iter()
+            .filter_map(|handle| Some((handle, self.decode(inputs, handle)?)))
+            .collect()
+    }
+}
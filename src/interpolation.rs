@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use crate::{AdvanceWorld, AdvanceWorldSet, Rollback};
+
+/// How far the accumulated time is between the most recently advanced rollback frame and the
+/// next, from `0.0` to `1.0`. Use this to smooth rendering when the display refreshes faster
+/// than the [`RollbackFrameRate`](`crate::RollbackFrameRate`).
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct FrameOverstep(pub(crate) f32);
+
+impl FrameOverstep {
+    /// Fraction of a rollback frame which has elapsed since the last one was advanced.
+    pub fn fraction(&self) -> f32 {
+        self.0
+    }
+}
+
+/// The value of a [`Component`] `C` interpolated between the two most recently advanced
+/// frames by the [`FrameOverstep`]. This is updated in [`PostUpdate`] and is never rolled back,
+/// so it should only be read for presentation, never by the [`GgrsSchedule`](`crate::GgrsSchedule`).
+#[derive(Component, Debug, Clone, Copy, Deref)]
+pub struct Interpolated<C: Component>(pub C);
+
+/// The value of a [`Component`] `C` before the most recently advanced frame.
+#[derive(Component)]
+struct PreviousFrame<C: Component>(C);
+
+/// A [`Plugin`] which maintains an [`Interpolated`] copy of the [`Component`] `C` on every
+/// [`Rollback`] entity, blended between its value in the last two frames using the provided function.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentInterpolationPlugin, Interpolated};
+/// #
+/// # fn start(session: Session<GgrsConfig<u8>>) {
+/// # let mut app = App::new();
+/// #[derive(Component, Clone, Copy)]
+/// struct Health(f32);
+///
+/// fn lerp_health(from: &Health, to: &Health, fraction: f32) -> Health {
+///     Health(from.0 + (to.0 - from.0) * fraction)
+/// }
+///
+/// app.rollback_component_with_copy::<Health>();
+///
+/// // Health bars can now read Interpolated<Health> for smooth rendering
+/// app.add_plugins(ComponentInterpolationPlugin::<Health>(lerp_health));
+/// # }
+/// ```
+pub struct ComponentInterpolationPlugin<C: Component + Clone>(pub fn(&C, &C, f32) -> C);
+
+impl<C> ComponentInterpolationPlugin<C>
+where
+    C: Component + Clone,
+{
+    /// A [`System`] which records the value of `C` before each frame is advanced.
+    pub fn store_previous(
+        mut commands: Commands,
+        mut query: Query<(Entity, &C, Option<&mut PreviousFrame<C>>), With<Rollback>>,
+    ) {
+        for (entity, component, previous) in query.iter_mut() {
+            match previous {
+                Some(mut previous) => previous.0 = component.clone(),
+                None => {
+                    commands
+                        .entity(entity)
+                        .insert(PreviousFrame(component.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl<C> Plugin for ComponentInterpolationPlugin<C>
+where
+    C: Component + Clone,
+{
+    fn build(&self, app: &mut App) {
+        let lerp = self.0;
+
+        let interpolate = move |mut commands: Commands,
+                                overstep: Res<FrameOverstep>,
+                                mut query: Query<
+            (
+                Entity,
+                &C,
+                Option<&PreviousFrame<C>>,
+                Option<&mut Interpolated<C>>,
+            ),
+            With<Rollback>,
+        >| {
+            for (entity, current, previous, interpolated) in query.iter_mut() {
+                // entities recreated by a rollback have no previous value until the next frame
+                let value = match previous {
+                    Some(previous) => lerp(&previous.0, current, overstep.fraction()),
+                    None => current.clone(),
+                };
+
+                match interpolated {
+                    Some(mut interpolated) => interpolated.0 = value,
+                    None => {
+                        commands.entity(entity).insert(Interpolated(value));
+                    }
+                }
+            }
+        };
+
+        app.init_resource::<FrameOverstep>()
+            .add_systems(
+                AdvanceWorld,
+                Self::store_previous.in_set(AdvanceWorldSet::First),
+            )
+            .add_systems(PostUpdate, interpolate);
+    }
+}
@@ -6,32 +6,81 @@
 use bevy::{
     ecs::{
         entity::MapEntities,
-        schedule::{ExecutorKind, LogLevel, ScheduleBuildSettings, ScheduleLabel},
+        schedule::{
+            ExecutorKind, InternedScheduleLabel, LogLevel, ScheduleBuildSettings, ScheduleLabel,
+            SystemConfigs,
+        },
     },
     input::InputSystem,
     prelude::*,
+    reflect::{GetTypeRegistration, ReflectFromReflect, TypePath},
     utils::{Duration, HashMap},
 };
-use ggrs::{Config, InputStatus, P2PSession, PlayerHandle, SpectatorSession, SyncTestSession};
-use std::{fmt::Debug, hash::Hash, marker::PhantomData, net::SocketAddr};
+use ggrs::{
+    Config, GgrsRequest, InputStatus, P2PSession, PlayerHandle, SessionState, SpectatorSession,
+    SyncTestSession,
+};
+use std::{
+    any::TypeId, collections::BTreeMap, fmt::Debug, hash::Hash, marker::PhantomData,
+    net::SocketAddr, path::PathBuf,
+};
 
 pub use ggrs;
 
+pub use ai_input::*;
+pub use bounds::*;
+pub use checkpoint::*;
+#[cfg(feature = "debug")]
+pub use debug::*;
+pub use desync_dump::*;
+pub use determinism::*;
+pub use disconnect_input::*;
+pub use input_history::*;
+pub use input_quality::*;
+pub use input_schema::*;
+pub use interpolation::*;
+#[cfg(feature = "test-utils")]
+pub use network_simulation::*;
+pub use player_entities::*;
+pub use player_registry::*;
 pub use rollback::*;
+pub use session_builder::*;
 pub use snapshot::*;
 pub use time::*;
 
+pub(crate) mod ai_input;
+pub(crate) mod bounds;
+pub(crate) mod checkpoint;
+#[cfg(feature = "debug")]
+pub(crate) mod debug;
+pub(crate) mod desync_dump;
+pub(crate) mod determinism;
+pub(crate) mod disconnect_input;
+pub(crate) mod input_history;
+pub(crate) mod input_quality;
+pub(crate) mod input_schema;
+pub(crate) mod interpolation;
+#[cfg(feature = "test-utils")]
+pub(crate) mod network_simulation;
+pub(crate) mod player_entities;
+pub(crate) mod player_registry;
 pub(crate) mod rollback;
 pub(crate) mod schedule_systems;
+pub(crate) mod session_builder;
 pub(crate) mod snapshot;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 pub(crate) mod time;
 
 pub mod prelude {
     pub use crate::{
-        snapshot::prelude::*, AddRollbackCommandExtension, GgrsApp, GgrsConfig, GgrsPlugin,
-        GgrsSchedule, GgrsTime, PlayerInputs, ReadInputs, Rollback, Session,
+        snapshot::prelude::*, AddRollbackCommandExtension, GgrsApp, GgrsConfig,
+        GgrsConfirmedSchedule, GgrsInitialized, GgrsPlayers, GgrsPlugin, GgrsPredictionStalled,
+        GgrsSchedule, GgrsSessionStateChanged, GgrsSetup, GgrsSpectatorFellBehind, GgrsStageSet,
+        GgrsTime, InRollbackSchedule, PlayerInputs, ReadInputs, Rollback, Session,
+        SpawnRollbackCommandExtension,
     };
-    pub use ggrs::{GgrsEvent, PlayerType, SessionBuilder};
+    pub use ggrs::{GgrsEvent, PlayerType, SessionBuilder, SessionState};
 }
 
 /// A sensible default [GGRS Config](`ggrs::Config`) type suitable for most applications.
@@ -60,6 +109,143 @@ const DEFAULT_FPS: usize = 60;
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct GgrsSchedule;
 
+/// Label for a schedule which runs once for every newly confirmed frame, and never while
+/// re-simulating. Use this for logic which must only happen once per confirmed frame, such as
+/// awarding achievements or persisting loot.
+///
+/// When several frames are confirmed in the same update, this runs once for each of them in
+/// order, with the [`ConfirmedFrameCount`] set to the frame being confirmed. It runs after all
+/// requests for the update have been handled, so the [`World`] reflects the latest, possibly
+/// predicted, frame rather than the confirmed one. Unlike the [`GgrsSchedule`], nothing done here
+/// is rolled back.
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GgrsConfirmedSchedule;
+
+/// Label for a schedule which runs once each time a [`Session`] starts, before the first frame is
+/// saved or advanced. Use this to spawn the initial state of a match, such as players and the
+/// level, so it is identical on every peer and fully captured in the snapshot of frame `0`.
+///
+/// Unlike [`Startup`], this runs after the [`Session`] has been inserted, with the
+/// [`RollbackFrameCount`] reset to `0`, so it may depend on the [`GgrsPlayers`] of the new
+/// [`Session`]. Commands issued here are applied before the first save. Setup must only depend
+/// on state which is identical across peers, such as the number of players, and not on local
+/// state such as which players are local.
+///
+/// A [`Session`] inserted in place of a running one also starts over from frame `0`, exactly
+/// as if [`GgrsPlugin::shutdown`] had been called first, so this runs again.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// #[derive(Component)]
+/// struct Player {
+///     handle: usize,
+/// }
+///
+/// fn spawn_players(mut commands: Commands, players: Res<GgrsPlayers>) {
+///     for handle in 0..players.num_players {
+///         commands.spawn(Player { handle }).add_rollback();
+///     }
+/// }
+/// # let mut app = App::new();
+/// app.add_systems(GgrsSetup, spawn_players);
+/// ```
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct GgrsSetup;
+
+/// A [`Resource`] listing the schedules run, in order, every time a frame is advanced. This always
+/// starts with the [`GgrsSchedule`], followed by each schedule added with
+/// [`GgrsPlugin::with_rollback_schedule`]. Every one of them is part of the same rollback
+/// simulation, so the rules of the [`GgrsSchedule`] apply to all of them: ambiguities are
+/// reported as errors, and [`InRollbackSchedule`] exists while they run.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+/// struct PhysicsSchedule;
+///
+/// #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+/// struct GameplaySchedule;
+///
+/// # fn step_physics() {}
+/// # fn apply_damage() {}
+/// # let mut app = App::new();
+/// app.add_plugins(
+///     GgrsPlugin::<GgrsConfig<u8>>::default()
+///         .with_rollback_schedule(PhysicsSchedule)
+///         .with_rollback_schedule(GameplaySchedule),
+/// )
+/// .add_systems(PhysicsSchedule, step_physics)
+/// .add_systems(GameplaySchedule, apply_damage);
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct RollbackSchedules(Vec<InternedScheduleLabel>);
+
+impl RollbackSchedules {
+    fn new(additional: &[InternedScheduleLabel]) -> Self {
+        Self(
+            [GgrsSchedule.intern()]
+                .into_iter()
+                .chain(additional.iter().copied())
+                .collect(),
+        )
+    }
+
+    /// The labels of every schedule run when advancing a frame, in the order they are run.
+    pub fn iter(&self) -> impl Iterator<Item = InternedScheduleLabel> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl Default for RollbackSchedules {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+/// A [`SystemSet`] grouping systems in the [`GgrsSchedule`] whose relative order cannot affect
+/// rollback state, for example because every system in the group only adds to the same counter.
+///
+/// Ambiguities in the [`GgrsSchedule`] are reported as errors, so conflicting systems must
+/// otherwise be explicitly ordered. Systems in the same group may be ambiguous with each other,
+/// while ambiguities with any system outside the group are still reported.
+/// See [`GgrsApp::add_order_independent_ggrs_systems`].
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct OrderIndependent(pub &'static str);
+
+/// A [`SystemSet`] in [`PreUpdate`] containing the systems which drive GGRS, such as
+/// [`GgrsPlugin::run`], which reads inputs and advances, saves and loads the [`World`].
+///
+/// This runs after the [`InputSystem`], so inputs read in [`ReadInputs`] always reflect the
+/// window and device events of the current update. Order your own [`PreUpdate`] systems against
+/// it, such as a network plugin receiving messages before GGRS polls its session.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// # fn receive_messages() {}
+/// # fn react_to_rollback_frame() {}
+/// # let mut app = App::new();
+/// app.add_systems(PreUpdate, receive_messages.before(GgrsStageSet))
+///     .add_systems(PreUpdate, react_to_rollback_frame.after(GgrsStageSet));
+/// ```
+///
+/// When using [`GgrsPlugin::without_driver`], [`GgrsPlugin::run`] is not part of this set.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub struct GgrsStageSet;
+
+/// A marker [`Resource`] which only exists while the [`GgrsSchedule`] is running. Systems shared
+/// between the [`GgrsSchedule`] and regular schedules can use this to tell the two apart, either
+/// with `Option<Res<InRollbackSchedule>>` or the `resource_exists::<InRollbackSchedule>()` run condition.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct InRollbackSchedule;
+
 /// Defines the Session that the GGRS Plugin should expect as a resource.
 #[allow(clippy::large_enum_variant)]
 #[derive(Resource)]
@@ -69,16 +255,152 @@ pub enum Session<T: Config> {
     Spectator(SpectatorSession<T>),
 }
 
+impl<T: Config> Session<T> {
+    /// The underlying [`SyncTestSession`], if this is a [`Session::SyncTest`].
+    pub fn as_synctest(&self) -> Option<&SyncTestSession<T>> {
+        match self {
+            Session::SyncTest(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// The underlying [`SyncTestSession`], if this is a [`Session::SyncTest`].
+    pub fn as_synctest_mut(&mut self) -> Option<&mut SyncTestSession<T>> {
+        match self {
+            Session::SyncTest(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// The underlying [`P2PSession`], if this is a [`Session::P2P`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// # type MyConfig = GgrsConfig<u8>;
+    /// #
+    /// fn log_network_stats(session: Option<Res<Session<MyConfig>>>) {
+    ///     let Some(session) = session.as_deref().and_then(Session::as_p2p) else {
+    ///         return;
+    ///     };
+    ///
+    ///     for handle in session.remote_player_handles() {
+    ///         if let Ok(stats) = session.network_stats(handle) {
+    ///             info!("Player {handle}: {stats:?}");
+    ///         }
+    ///     }
+    /// }
+    /// # let mut app = App::new();
+    /// # app.add_systems(Update, log_network_stats);
+    /// ```
+    pub fn as_p2p(&self) -> Option<&P2PSession<T>> {
+        match self {
+            Session::P2P(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// The underlying [`P2PSession`], if this is a [`Session::P2P`].
+    pub fn as_p2p_mut(&mut self) -> Option<&mut P2PSession<T>> {
+        match self {
+            Session::P2P(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// The underlying [`SpectatorSession`], if this is a [`Session::Spectator`].
+    pub fn as_spectator(&self) -> Option<&SpectatorSession<T>> {
+        match self {
+            Session::Spectator(session) => Some(session),
+            _ => None,
+        }
+    }
+
+    /// The underlying [`SpectatorSession`], if this is a [`Session::Spectator`].
+    pub fn as_spectator_mut(&mut self) -> Option<&mut SpectatorSession<T>> {
+        match self {
+            Session::Spectator(session) => Some(session),
+            _ => None,
+        }
+    }
+}
+
 // TODO: more specific name to avoid conflicts?
+/// The inputs of every player for the frame currently being advanced, indexed by [`PlayerHandle`].
+/// This always contains exactly one entry per player in the [`Session`].
 #[derive(Resource, Deref, DerefMut)]
 pub struct PlayerInputs<T: Config>(Vec<(T::Input, InputStatus)>);
 
+impl<T: Config> PlayerInputs<T> {
+    /// Get the input and its [`InputStatus`] for the player with the provided `handle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` does not belong to a player in the [`Session`].
+    pub fn by_handle(&self, handle: PlayerHandle) -> &(T::Input, InputStatus) {
+        self.0.get(handle).unwrap_or_else(|| {
+            panic!(
+                "No input for player handle {handle}, the session only has {} player(s)",
+                self.0.len()
+            )
+        })
+    }
+
+    /// Returns `true` if the player with the provided `handle` has disconnected. From then on,
+    /// their input no longer reflects their actions, see [`InputStatus::Disconnected`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` does not belong to a player in the [`Session`].
+    pub fn is_disconnected(&self, handle: PlayerHandle) -> bool {
+        self.by_handle(handle).1 == InputStatus::Disconnected
+    }
+
+    /// Iterate over the handles of every player who has disconnected.
+    pub fn disconnected(&self) -> impl Iterator<Item = PlayerHandle> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, status))| *status == InputStatus::Disconnected)
+            .map(|(handle, _)| handle)
+    }
+}
+
+impl<T: Config> PlayerInputs<T>
+where
+    T::Input: bytemuck::Pod,
+{
+    /// The raw bytes of the input for the player with the provided `handle`, exactly as GGRS
+    /// encodes them. Useful for logging inputs to compare against a peer's log when
+    /// tracking down whether a desync is caused by inputs or by the simulation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` does not belong to a player in the [`Session`].
+    pub fn input_bytes(&self, handle: PlayerHandle) -> &[u8] {
+        bytemuck::bytes_of(&self.by_handle(handle).0)
+    }
+
+    /// The raw bytes of every player's input for this frame, concatenated in [`PlayerHandle`] order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|(input, _)| bytemuck::bytes_of(input))
+            .copied()
+            .collect()
+    }
+}
+
 #[derive(Resource, Copy, Clone, Debug)]
 struct FixedTimestepData {
     /// accumulated time. once enough time has been accumulated, an update is executed
     accumulator: Duration,
     /// boolean to see if we should run slow to let remote clients catch up
     run_slow: bool,
+    /// how many frames ahead of remote clients we are, used for time dilation
+    frames_ahead: i32,
 }
 
 impl Default for FixedTimestepData {
@@ -86,11 +408,94 @@ impl Default for FixedTimestepData {
         Self {
             accumulator: Duration::ZERO,
             run_slow: false,
+            frames_ahead: 0,
+        }
+    }
+}
+
+/// The timing state GGRS uses to decide how many frames to advance each update: the time
+/// accumulated towards the next frame, and whether to run slow to let remote clients catch up.
+///
+/// Recording this alongside the inputs of a replay, then restoring it before playback with the
+/// same [`Time`] deltas, such as through
+/// [`TimeUpdateStrategy`](`bevy::time::TimeUpdateStrategy`), reproduces the exact cadence at
+/// which frames were advanced and inputs were sampled. Use [`to_bytes`](`Self::to_bytes`) and
+/// [`from_bytes`](`Self::from_bytes`) to store it in a replay file.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::GgrsTimingState;
+/// #
+/// fn start_playback(world: &mut World, recorded: [u8; GgrsTimingState::BYTES]) {
+///     GgrsTimingState::from_bytes(recorded).apply(world);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GgrsTimingState {
+    /// Time accumulated towards the next frame.
+    pub accumulator: Duration,
+    /// Whether frames are advanced slower to let remote clients catch up.
+    pub run_slow: bool,
+    /// How many frames ahead of remote clients the [`Session`] was when last advanced.
+    pub frames_ahead: i32,
+}
+
+impl GgrsTimingState {
+    /// The length of the encoding produced by [`to_bytes`](`Self::to_bytes`).
+    pub const BYTES: usize = 17;
+
+    /// Captures the current timing state of the [`GgrsPlugin`]. Returns the default state if the
+    /// [`GgrsPlugin`] has not been added, or is currently running.
+    pub fn capture(world: &World) -> Self {
+        world
+            .get_resource::<FixedTimestepData>()
+            .map(|data| Self {
+                accumulator: data.accumulator,
+                run_slow: data.run_slow,
+                frames_ahead: data.frames_ahead,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Restores this timing state, so the next update advances frames exactly as it did when
+    /// the state was captured.
+    pub fn apply(self, world: &mut World) {
+        world.insert_resource(FixedTimestepData {
+            accumulator: self.accumulator,
+            run_slow: self.run_slow,
+            frames_ahead: self.frames_ahead,
+        });
+    }
+
+    /// Encodes this state as little endian bytes, independent of the platform.
+    pub fn to_bytes(&self) -> [u8; Self::BYTES] {
+        let mut bytes = [0; Self::BYTES];
+
+        bytes[0..8].copy_from_slice(&self.accumulator.as_secs().to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.accumulator.subsec_nanos().to_le_bytes());
+        bytes[12] = self.run_slow as u8;
+        bytes[13..17].copy_from_slice(&self.frames_ahead.to_le_bytes());
+
+        bytes
+    }
+
+    /// Decodes a state encoded by [`to_bytes`](`Self::to_bytes`).
+    pub fn from_bytes(bytes: [u8; Self::BYTES]) -> Self {
+        let secs = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        let nanos = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+
+        Self {
+            accumulator: Duration::new(secs, nanos),
+            run_slow: bytes[12] != 0,
+            frames_ahead: i32::from_le_bytes(bytes[13..17].try_into().unwrap()),
         }
     }
 }
 
-/// Keeps track of the current frame the rollback simulation is in
+/// Keeps track of the current frame the rollback simulation is in.
+///
+/// This is always available, including while the [`ReadInputs`] schedule runs.
 #[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct RollbackFrameCount(pub i32);
 
@@ -110,22 +515,462 @@ impl From<ConfirmedFrameCount> for i32 {
     }
 }
 
+/// An [`Event`] sent whenever the [`SessionState`] of the [`Session`] changes, such as when
+/// a [`P2PSession`] finishes synchronizing. A state of [`None`] means no [`Session`] was present.
+/// [`SyncTestSessions`](`SyncTestSession`) are always considered [`Running`](`SessionState::Running`).
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GgrsSessionStateChanged {
+    /// The state before the change.
+    pub from: Option<SessionState>,
+    /// The state after the change.
+    pub to: Option<SessionState>,
+}
+
+/// An [`Event`] sent once a newly inserted [`Session`] has been picked up by the [`GgrsPlugin`],
+/// before any of its frames are advanced, including one inserted in place of a running one. From
+/// this point, [`MaxPredictionWindow`] and [`GgrsPlayers`] reflect the new [`Session`]. See also
+/// [`SessionInitialized`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GgrsInitialized {
+    /// The maximum prediction window of the [`Session`].
+    pub max_prediction: usize,
+}
+
+/// A [`Resource`] which is `true` once the current [`Session`] has been picked up by the
+/// [`GgrsPlugin`], and `false` while there is no [`Session`]. Useful for run conditions which
+/// should not miss the [`GgrsInitialized`] event, such as in systems added later on.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionInitialized(pub bool);
+
+/// An [`Event`] sent whenever a frame could not be advanced because the [`Session`] would
+/// need to predict further ahead than its [`MaxPredictionWindow`] allows, usually because a
+/// remote peer has stopped sending inputs. See also [`PredictionStalled`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GgrsPredictionStalled {
+    /// The last frame which was advanced before stalling.
+    pub frame: i32,
+}
+
+/// A [`Resource`] which is `true` while the [`Session`] is stalled waiting for remote inputs,
+/// and is cleared as soon as a frame is advanced again. Useful for driving a
+/// "waiting for opponent" indicator. See also [`GgrsPredictionStalled`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PredictionStalled(pub bool);
+
+/// A [`Resource`] tracking the loads requested by GGRS, for debugging overlays such as a
+/// rollback timeline. Comparing the [`last_load_frame`](`Self::last_load_frame`) against the
+/// [`RollbackFrameCount`] shows how far back the last rollback reached. Loads performed through
+/// [`apply_snapshot`] are not counted.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RollbackStats {
+    last_load_frame: Option<i32>,
+    load_count: u64,
+}
+
+impl RollbackStats {
+    /// The frame GGRS most recently requested to load, if any.
+    pub fn last_load_frame(&self) -> Option<i32> {
+        self.last_load_frame
+    }
+
+    /// The number of loads GGRS has requested since the [`App`] started.
+    pub fn load_count(&self) -> u64 {
+        self.load_count
+    }
+
+    pub(crate) fn record_load(&mut self, frame: i32) {
+        self.last_load_frame = Some(frame);
+        self.load_count += 1;
+    }
+}
+
+/// A [`Resource`] separating the cost of re-simulating frames during a rollback from advancing
+/// new frames, for profiling. A frame is re-simulated when it is advanced again after a load,
+/// including frames re-simulated from a keyframe, see [`SnapshotKeyframeInterval`].
+///
+/// The per-update counts cover the most recent run of [`GgrsPlugin::run`], and are reset at the
+/// start of each run. Re-simulated frames are also tagged with `resimulating = true` on their
+/// `AdvanceWorld` tracing span, so profilers can attribute their cost.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsResimStats};
+/// #
+/// fn report_resimulation(stats: Res<GgrsResimStats>) {
+///     info!(
+///         "Re-simulated {} frames in {:?}",
+///         stats.resimulated_frames(),
+///         stats.resimulation_time()
+///     );
+/// }
+///
+/// // only expensive systems need to check, such as to skip particle effects
+/// fn spawn_impact_effects(stats: Res<GgrsResimStats>) {
+///     if stats.is_resimulating() {
+///         return;
+///     }
+///     // ...
+/// }
+/// # let mut app = App::new();
+/// app.add_systems(Update, report_resimulation)
+///     .add_systems(GgrsSchedule, spawn_impact_effects);
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GgrsResimStats {
+    resimulating: bool,
+    resimulated_frames: u32,
+    resimulation_time: Duration,
+    total_resimulated_frames: u64,
+    /// The newest frame advanced in the current [`Session`], if any.
+    newest_frame: Option<i32>,
+}
+
+impl GgrsResimStats {
+    /// Returns `true` while the frame being advanced is being re-simulated.
+    pub fn is_resimulating(&self) -> bool {
+        self.resimulating
+    }
+
+    /// The number of frames re-simulated during the most recent update.
+    pub fn resimulated_frames(&self) -> u32 {
+        self.resimulated_frames
+    }
+
+    /// The time spent running the rollback schedules for re-simulated frames during the most
+    /// recent update. This excludes loading snapshots, and saving them after each frame.
+    pub fn resimulation_time(&self) -> Duration {
+        self.resimulation_time
+    }
+
+    /// The number of frames re-simulated since the [`App`] started.
+    pub fn total_resimulated_frames(&self) -> u64 {
+        self.total_resimulated_frames
+    }
+
+    /// Clears the counts of the most recent update.
+    pub(crate) fn start_update(&mut self) {
+        self.resimulated_frames = 0;
+        self.resimulation_time = Duration::ZERO;
+    }
+
+    /// Starts advancing `frame`, returning `true` if it has been advanced before.
+    pub(crate) fn start_frame(&mut self, frame: i32) -> bool {
+        self.resimulating = self.newest_frame.is_some_and(|newest| frame <= newest);
+
+        if !self.resimulating {
+            self.newest_frame = Some(frame);
+        }
+
+        self.resimulating
+    }
+
+    /// Finishes advancing the current frame, which took `elapsed`.
+    pub(crate) fn finish_frame(&mut self, elapsed: Duration) {
+        if self.resimulating {
+            self.resimulated_frames += 1;
+            self.resimulation_time += elapsed;
+            self.total_resimulated_frames += 1;
+        }
+
+        self.resimulating = false;
+    }
+
+    /// Forgets the frames advanced by the previous [`Session`].
+    pub(crate) fn reset_session(&mut self) {
+        self.resimulating = false;
+        self.newest_frame = None;
+    }
+}
+
 /// The maximum prediction window for this [`Session`], provided as a concrete [`Resource`].
 #[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MaxPredictionWindow(usize);
 
+/// Only frames which are a multiple of this interval are snapshot. Loading any other frame
+/// loads the nearest earlier keyframe and re-simulates forward to it, trading CPU for memory and save cost.
+///
+/// Defaults to `1`, snapshotting every frame. See [`GgrsApp::set_snapshot_keyframe_interval`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SnapshotKeyframeInterval(usize);
+
+impl Default for SnapshotKeyframeInterval {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// A function deciding whether a snapshot should be kept once its frame has been confirmed,
+/// called with the snapshot's frame and the current [`RollbackFrameCount`]. Snapshots which
+/// could still be rolled back to by GGRS are always kept, regardless of this policy.
+///
+/// Without this resource, snapshots are discarded as soon as they are confirmed. The
+/// [`depth`](`GgrsSnapshots::depth`) of each [`GgrsSnapshots`] still limits how many are kept
+/// in total. See [`GgrsApp::set_snapshot_retention`].
+#[derive(Resource, Clone, Copy)]
+pub struct SnapshotRetention(pub fn(i32, i32) -> bool);
+
+/// A function called with every [`GgrsRequest`] before it is handled, allowing tooling to
+/// observe the stream of saves, loads, and advances without changing how they are handled.
+/// See [`GgrsApp::observe_requests`].
+#[derive(Resource)]
+pub struct GgrsRequestObserver<C: Config>(pub fn(&GgrsRequest<C>));
+
+/// A function producing the [`State`](`Config::State`) stored in GGRS's own
+/// [`GameStateCell`](`ggrs::GameStateCell`) for each saved frame, for interop with tooling that
+/// reads it. By default, nothing is stored, since the [`SaveWorld`] schedule keeps a faster
+/// in-memory copy. See [`GgrsApp::save_ggrs_state_with`].
+///
+/// This is called for every frame GGRS saves, after the [`SaveWorld`] schedule if it runs. This
+/// includes frames which are not snapshot, such as those between keyframes or in [`Lockstep`],
+/// so its cost is paid on every frame, including during re-simulation. Keep it as cheap as
+/// possible.
+#[derive(Resource)]
+pub struct GgrsStateSaver<C: Config>(pub fn(&World) -> C::State);
+
+/// When enabled, any error returned by GGRS while advancing a [`Session`] panics instead of
+/// being logged as a warning. [`PredictionThreshold`](`ggrs::GgrsError::PredictionThreshold`)
+/// is expected during normal play and is never treated as a failure. Entities added to rollback
+/// outside of the rollback schedules during a session, see [`Rollback`], also panic.
+///
+/// Intended for CI and automated determinism runs, where a desync should fail immediately.
+/// Defaults to `false`. See [`GgrsApp::set_strict_errors`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StrictErrors(pub bool);
+
+/// When enabled, every frame advanced with only confirmed inputs is advanced twice: once from the
+/// current state, and once more after loading a snapshot of that state, with identical inputs.
+/// If the two resulting [`Checksums`](`crate::Checksum`) differ, this panics.
+///
+/// This catches systems which are not idempotent, such as those depending on a [`Local`] or on a
+/// [`Resource`] which is not rolled back, without needing a
+/// [`SyncTestSession`](`ggrs::SyncTestSession`). Only state added to the checksum is compared.
+///
+/// With the [`ChecksumDiagnosticsPlugin`] added, each checksummed component is compared
+/// individually, and the first mismatching component type and [`Rollback`] entity are reported.
+///
+/// The first pass is saved into scratch storages, so the stored snapshots are left untouched, and
+/// it is not counted in the [`GgrsResimStats`] or [`GgrsInputQuality`].
+///
+/// This roughly doubles the cost of advancing each frame, and runs the [`GgrsSchedule`] twice per
+/// frame, so it is intended for debugging only. See [`GgrsPlugin::with_redundant_verification`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RedundantVerification(pub bool);
+
+/// When enabled, every [`Session`] built using a [`GgrsSessionBuilder`] has a max prediction
+/// window of `0`, so frames only advance once every input is known and are never rolled back.
+/// See [`GgrsPlugin::lockstep`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lockstep(pub bool);
+
+/// A seed shared by every peer in a [`Session`], for generating content such as maps or loot
+/// tables identically on every peer. Every peer must use the same seed, typically agreed on
+/// during matchmaking, or their worlds will desync.
+///
+/// This can be provided up front with [`GgrsPlugin::with_session_seed`], which also includes it in
+/// the [`Checksum`], so peers using different seeds are detected as desynced. It can instead be
+/// inserted once it is known, before the [`Session`] starts, in which case
+/// [`GgrsApp::checksum_resource_with_hash`] must be called to include it in the [`Checksum`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GgrsSessionSeed(pub u64);
+
+/// Describes how often the [`ReadInputs`] schedule is run when several rollback frames
+/// are advanced within a single app update. See [`GgrsApp::set_input_sampling`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSampling {
+    /// Run [`ReadInputs`] once for every rollback frame advanced.
+    ///
+    /// Beware that device state such as [`Input::just_pressed`] does not change between
+    /// rollback frames of the same app update, so a single button press may be read multiple times.
+    #[default]
+    PerStep,
+    /// Run [`ReadInputs`] at most once per app update, reusing the sampled [`LocalInputs`]
+    /// for every rollback frame advanced during that update.
+    PerTick,
+}
+
 /// Inputs from local players. You have to fill this resource in the ReadInputs schedule.
 #[derive(Resource)]
 pub struct LocalInputs<C: Config>(pub HashMap<PlayerHandle, C::Input>);
 
+/// A function which reads the input of a single local player, as an alternative to filling
+/// [`LocalInputs`] from the [`ReadInputs`] schedule. It is called once for every local player
+/// before the [`ReadInputs`] schedule runs, and the results are inserted as the [`LocalInputs`].
+///
+/// Since it only has shared access to the [`World`], it must be pure: the same world and handle
+/// must always produce the same input. With [`ParallelInput`], players are read concurrently.
+/// See [`GgrsApp::set_player_input_reader`].
+#[derive(Resource)]
+pub struct PlayerInputReader<C: Config>(pub fn(PlayerHandle, &World) -> C::Input);
+
+/// When enabled, the [`PlayerInputReader`] is called for every local player in parallel on the
+/// [`ComputeTaskPool`](`bevy::tasks::ComputeTaskPool`), which helps when reading input is
+/// expensive and there are several local players, such as couch co-op over netplay. Inputs are
+/// always submitted to the [`Session`] in order of [`PlayerHandle`], however they were gathered.
+///
+/// Has no effect without a [`PlayerInputReader`]. Defaults to `false`. See
+/// [`GgrsPlugin::with_parallel_input`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParallelInput(pub bool);
+
+/// A [`Resource`] which, while no [`Session`] exists, advances the [`GgrsSchedule`] exactly once
+/// per update, without a fixed timestep, snapshots or rollbacks. Every player is local, and
+/// [`Time`] inside the [`GgrsSchedule`] advances by the real time elapsed since the last update.
+/// This allows iterating on gameplay without the constraints of a fixed timestep.
+///
+/// WARNING: Local mode is not deterministic, and is intended for development only. Once a
+/// [`Session`] is inserted, frames are advanced as usual, starting over from the first frame.
+/// See [`GgrsPlugin::with_local_mode`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalMode {
+    num_players: usize,
+    /// Set while a frame is being advanced in local mode.
+    pub(crate) frame_delta: Option<Duration>,
+}
+
+impl LocalMode {
+    /// Creates a [`LocalMode`] for `num_players` local players.
+    pub fn new(num_players: usize) -> Self {
+        Self {
+            num_players,
+            frame_delta: None,
+        }
+    }
+
+    /// The number of players, all of which are local.
+    pub fn num_players(&self) -> usize {
+        self.num_players
+    }
+
+    /// The real time covered by the frame currently being advanced in local mode, or [`None`]
+    /// outside of a local frame.
+    pub fn frame_delta(&self) -> Option<Duration> {
+        self.frame_delta
+    }
+}
+
 /// Handles for the local players, you can use this when writing an input system.
 #[derive(Resource, Default)]
 pub struct LocalPlayers(pub Vec<PlayerHandle>);
 
-/// Label for the schedule which reads the inputs for the current frame
+/// The players participating in the current [`Session`], kept up to date by the [`GgrsPlugin`].
+/// Unlike [`LocalPlayers`], this is available outside of the rollback schedules, allowing UI
+/// and gameplay to branch on player identity without matching on the [`Session`].
+///
+/// In a [`SyncTestSession`], every player is local. In a [`SpectatorSession`], none are.
+#[derive(Resource, Default, Clone, Debug, PartialEq, Eq)]
+pub struct GgrsPlayers {
+    /// Total number of players in the [`Session`].
+    pub num_players: usize,
+    /// Handles of the players local to this client.
+    pub local: Vec<PlayerHandle>,
+}
+
+impl GgrsPlayers {
+    /// Returns `true` if the player with the provided `handle` is local to this client.
+    pub fn is_local(&self, handle: PlayerHandle) -> bool {
+        self.local.contains(&handle)
+    }
+}
+
+/// The maximum number of frames a [`SpectatorSession`] may advance in a single step while behind its
+/// host, allowing spectators to catch up quickly without affecting players. Advancing stops as soon
+/// as the spectator is no longer behind, so this only bounds the cost of catching up.
+///
+/// This is independent of the catch-up performed by GGRS itself, configured through
+/// [`SessionBuilder::with_catchup_speed`](`ggrs::SessionBuilder::with_catchup_speed`), which
+/// applies to each of these frames. Defaults to `1`. See [`GgrsPlugin::with_spectator_catchup`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpectatorCatchup(pub usize);
+
+impl Default for SpectatorCatchup {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// How a [`LoadGameState`](`ggrs::GgrsRequest::LoadGameState`) request is handled when the
+/// snapshot of the requested frame is no longer stored, for example because its slot was
+/// overwritten after the snapshot depth was reduced. See [`GgrsPlugin::on_missing_snapshot`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MissingSnapshotPolicy {
+    /// Panic, listing the frames which are still stored. This is the default.
+    #[default]
+    Panic,
+    /// Load the nearest earlier stored snapshot and re-simulate forward to the requested frame,
+    /// logging a warning. Inputs are recorded for every frame back to the oldest stored snapshot,
+    /// which adds a small cost to each frame. If no earlier snapshot can be re-simulated from, the
+    /// error is reported as with [`StrictErrors`], and the remaining requests are skipped.
+    Resimulate,
+}
+
+/// The number of frames a [`SpectatorSession`] may be behind its host, after catching up as far
+/// as [`SpectatorCatchup`] allows, before a [`GgrsSpectatorFellBehind`] event is sent. Disabled
+/// unless inserted. See [`GgrsPlugin::with_spectator_max_lag`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpectatorMaxLag(pub usize);
+
+/// An [`Event`] sent whenever a [`SpectatorSession`] advances but remains further behind its host
+/// than the [`SpectatorMaxLag`] allows, meaning it is not catching up quickly enough. Useful for
+/// showing a "reconnecting view" indicator.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GgrsSpectatorFellBehind {
+    /// How many frames the spectator is behind its host.
+    pub frames_behind: usize,
+}
+
+/// Connection statistics for a single spectator of a [`P2PSession`], see [`GgrsSpectatorStats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpectatorStats {
+    /// The handle the spectator was added with.
+    pub handle: PlayerHandle,
+    /// Round trip time to the spectator, in milliseconds.
+    pub ping: u128,
+    /// How many frames the spectator is behind this client.
+    pub frames_behind: i32,
+}
+
+/// The spectators connected to the current [`P2PSession`] and how far behind each one is,
+/// updated every time the session advances. Spectators which are not yet synchronized are
+/// not included. Spectators are added when building the session, such as through
+/// [`GgrsSessionBuilder::add_spectator`].
+#[derive(Resource, Default, Clone, Debug, PartialEq, Eq)]
+pub struct GgrsSpectatorStats {
+    /// Statistics for each synchronized spectator, in handle order.
+    pub spectators: Vec<SpectatorStats>,
+}
+
+/// Label for the schedule which reads the inputs for the current frame.
+///
+/// This schedule runs before GGRS is asked to advance, so [`RollbackFrameCount`] still holds
+/// the last frame that was advanced to; the inputs read here will be used to advance to the
+/// frame after it. It is never run while re-simulating frames during a rollback, so each
+/// frame's local inputs are read exactly once.
+///
+/// Like any schedule, its systems are only initialized when first run, so they may freely depend
+/// on resources inserted after the [`GgrsPlugin`] is added, such as by a later plugin.
+///
+/// This schedule is skipped entirely for [`P2PSessions`](`P2PSession`) without any [`LocalPlayers`].
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct ReadInputs;
 
+impl ReadInputs {
+    /// Replaces every system in the [`ReadInputs`] schedule with `systems`, such as to switch
+    /// from live input to a recorded replay, or to hand a player over to an AI. Since this requires
+    /// exclusive access to the [`World`], the swap always happens between frames.
+    ///
+    /// Inputs are exchanged with remote peers as normal, so swapping input systems only needs to be
+    /// coordinated across peers if the new system could produce inputs a peer would not expect.
+    ///
+    /// This must not be called from a system within the [`ReadInputs`] schedule itself.
+    pub fn replace_systems<M>(world: &mut World, systems: impl IntoSystemConfigs<M>) {
+        let mut schedule = Schedule::new(ReadInputs);
+        schedule.add_systems(systems);
+
+        world.resource_mut::<Schedules>().insert(schedule);
+    }
+}
+
 /// Label for the schedule which loads and overwrites a snapshot of the world.
 #[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct LoadWorld;
@@ -178,45 +1023,402 @@ pub struct AdvanceWorld;
 /// app.insert_resource(session);
 /// # }
 /// ```
+///
+/// # Lockstep
+///
+/// A [`P2PSession`] with a max prediction window of `0` waits for every input before
+/// advancing, so no frame is ever rolled back. In this case, the [`SaveWorld`] schedule is
+/// skipped entirely, removing the cost of snapshots and checksums. This requires a version of
+/// GGRS which accepts a prediction window of `0`.
+///
+/// Use [`GgrsPlugin::lockstep`] to build every session this way, which suits LAN play where
+/// latency is close to zero.
+///
+/// # Scheduling
+///
+/// By default, GGRS is driven by a system added to [`PreUpdate`], which other systems can be
+/// ordered against using the [`GgrsStageSet`]. To run it from somewhere else, such as a custom
+/// main loop, use [`GgrsPlugin::without_driver`] and add [`GgrsPlugin::run`] to a schedule of
+/// your choosing.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::prelude::*;
+/// #
+/// # type MyInputType = u8;
+/// #
+/// # let mut app = App::new();
+/// app.add_plugins(GgrsPlugin::<GgrsConfig<MyInputType>>::without_driver());
+///
+/// app.add_systems(FixedUpdate, GgrsPlugin::<GgrsConfig<MyInputType>>::run);
+/// ```
+///
+/// # Profiling
+///
+/// Each session update, request, and schedule run is wrapped in a `tracing` span carrying the
+/// frame it applies to, with re-simulated frames nested inside the `LoadWorld` span of their
+/// rollback. Enable Bevy's `trace` feature to see these spans in a profiler such as Tracy.
 pub struct GgrsPlugin<C: Config> {
+    /// whether to add [`GgrsPlugin::run`] to [`PreUpdate`]
+    add_driver: bool,
+    /// whether to run [`check_determinism`] once the [`App`] is built
+    determinism_lint: bool,
+    /// adds run conditions to [`GgrsPlugin::run`], such as [`GgrsPlugin::run_in_state`]
+    driver_conditions: Option<DriverConditions>,
+    /// inserted as the [`GgrsSessionSeed`], if provided
+    session_seed: Option<u64>,
+    /// inserted as the [`RedundantVerification`]
+    redundant_verification: bool,
+    /// inserted as the [`Lockstep`]
+    lockstep: bool,
+    /// inserted as the [`ParallelInput`]
+    parallel_input: bool,
+    /// inserted as the [`SpectatorCatchup`]
+    spectator_catchup: usize,
+    /// inserted as the [`SpectatorMaxLag`], if provided
+    spectator_max_lag: Option<usize>,
+    /// inserted as the [`MissingSnapshotPolicy`]
+    missing_snapshot: MissingSnapshotPolicy,
+    /// capacity of the [`PlayerInputHistory`], if enabled
+    input_history: Option<usize>,
+    /// inserted as the [`DisconnectInputPolicy`], if provided
+    disconnect_input_policy: Option<DisconnectInputPolicy<C>>,
+    /// inserted as the [`AiPlayers`], if provided
+    ai_players: Option<AiPlayers<C>>,
+    /// inserted as the [`DesyncDump`], if enabled
+    desync_dump: Option<DesyncDump>,
+    /// inserted as the [`LocalMode`], if enabled
+    local_mode: Option<LocalMode>,
+    /// appended to the [`RollbackSchedules`], in order
+    rollback_schedules: Vec<InternedScheduleLabel>,
     /// phantom marker for ggrs config
     _marker: PhantomData<C>,
 }
 
+/// Applied to the [`SystemConfigs`] of [`GgrsPlugin::run`].
+type DriverConditions = Box<dyn Fn(SystemConfigs) -> SystemConfigs + Send + Sync>;
+
 impl<C: Config> Default for GgrsPlugin<C> {
     fn default() -> Self {
-        Self { _marker: default() }
+        Self {
+            add_driver: true,
+            determinism_lint: false,
+            driver_conditions: None,
+            session_seed: None,
+            redundant_verification: false,
+            lockstep: false,
+            parallel_input: false,
+            spectator_catchup: 1,
+            spectator_max_lag: None,
+            missing_snapshot: MissingSnapshotPolicy::Panic,
+            input_history: None,
+            disconnect_input_policy: None,
+            ai_players: None,
+            desync_dump: None,
+            local_mode: None,
+            rollback_schedules: Vec::new(),
+            _marker: default(),
+        }
+    }
+}
+
+impl<C: Config> GgrsPlugin<C> {
+    /// Creates a [`GgrsPlugin`] which sets up all resources and schedules, but does not
+    /// add [`GgrsPlugin::run`] to the [`App`]. You are responsible for scheduling it yourself.
+    pub fn without_driver() -> Self {
+        Self {
+            add_driver: false,
+            ..default()
+        }
+    }
+
+    /// When enabled, [`check_determinism`] is run once the [`App`] has been built, logging a
+    /// warning for each system in the [`GgrsSchedule`] which is likely to cause a desync.
+    /// Disabled by default.
+    pub fn with_determinism_lint(mut self, enabled: bool) -> Self {
+        self.determinism_lint = enabled;
+        self
+    }
+
+    /// When enabled, every confirmed frame is simulated twice to check that the [`GgrsSchedule`]
+    /// is deterministic, see [`RedundantVerification`]. This roughly doubles the cost of each
+    /// frame, so it should only be enabled while debugging. Disabled by default.
+    pub fn with_redundant_verification(mut self, enabled: bool) -> Self {
+        self.redundant_verification = enabled;
+        self
+    }
+
+    /// Runs every [`Session`] built using a [`GgrsSessionBuilder`] in lockstep, without any
+    /// prediction, see [`Lockstep`]. No snapshots are saved, since nothing is ever rolled back,
+    /// but every frame waits for the inputs of all remote players.
+    pub fn lockstep(mut self) -> Self {
+        self.lockstep = true;
+        self
+    }
+
+    /// When enabled, the [`PlayerInputReader`] reads every local player in parallel, see
+    /// [`ParallelInput`]. Disabled by default.
+    pub fn with_parallel_input(mut self, enabled: bool) -> Self {
+        self.parallel_input = enabled;
+        self
+    }
+
+    /// Allows a [`SpectatorSession`] to advance up to `max_frames` frames per step while it is
+    /// behind its host, minimizing view latency. See [`SpectatorCatchup`].
+    pub fn with_spectator_catchup(mut self, max_frames: usize) -> Self {
+        self.spectator_catchup = max_frames;
+        self
+    }
+
+    /// Sends a [`GgrsSpectatorFellBehind`] event whenever a [`SpectatorSession`] remains more
+    /// than `max_frames` frames behind its host after catching up, see [`SpectatorMaxLag`].
+    /// Disabled by default.
+    pub fn with_spectator_max_lag(mut self, max_frames: usize) -> Self {
+        self.spectator_max_lag = Some(max_frames);
+        self
+    }
+
+    /// Chooses how to handle GGRS requesting a frame whose snapshot is no longer stored, see
+    /// [`MissingSnapshotPolicy`]. Panics by default.
+    pub fn on_missing_snapshot(mut self, policy: MissingSnapshotPolicy) -> Self {
+        self.missing_snapshot = policy;
+        self
+    }
+
+    /// Maintains a [`PlayerInputHistory`] of the inputs of the most recent `frames` frames, which
+    /// is rolled back with the rest of the [`World`]. Disabled by default.
+    pub fn with_input_history(mut self, frames: usize) -> Self {
+        self.input_history = Some(frames);
+        self
+    }
+
+    /// Chooses which input disconnected players provide, see [`DisconnectInputPolicy`]. By default,
+    /// the input provided by GGRS is used unchanged.
+    pub fn with_disconnect_input_policy(mut self, policy: DisconnectInputPolicy<C>) -> Self {
+        self.disconnect_input_policy = Some(policy);
+        self
+    }
+
+    /// Controls the players with the provided `handles` by AI, producing their inputs from the
+    /// rollback state using `input`, see [`AiPlayers`]. `input` must be deterministic.
+    pub fn with_ai_players(
+        mut self,
+        handles: impl IntoIterator<Item = PlayerHandle>,
+        input: fn(PlayerHandle, &RollbackView) -> C::Input,
+    ) -> Self {
+        self.ai_players = Some(AiPlayers::new(handles, input));
+        self
+    }
+
+    /// Writes a report of the [`World`] to `path` whenever a [`SyncTestSession`] detects
+    /// mismatched checksums, before the error is reported. See [`DesyncDump`]. Disabled by default.
+    pub fn with_desync_dump(mut self, path: impl Into<PathBuf>) -> Self
+    where
+        C::Input: bytemuck::Pod,
+    {
+        self.desync_dump = Some(DesyncDump::new::<C>(path));
+        self
+    }
+
+    /// While no [`Session`] exists, advances the [`GgrsSchedule`] once per update with
+    /// `num_players` local players and a variable timestep, see [`LocalMode`]. This is not
+    /// deterministic, and is intended for development only. Disabled by default.
+    pub fn with_local_mode(mut self, num_players: usize) -> Self {
+        self.local_mode = Some(LocalMode::new(num_players));
+        self
+    }
+
+    /// Runs `schedule` every time a frame is advanced, after the [`GgrsSchedule`] and any schedule
+    /// added before it, see [`RollbackSchedules`]. Use this to split a complex simulation into
+    /// phases, such as physics followed by gameplay.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `schedule` is the [`GgrsSchedule`], or has already been added.
+    pub fn with_rollback_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        let schedule = schedule.intern();
+
+        assert!(
+            schedule != GgrsSchedule.intern() && !self.rollback_schedules.contains(&schedule),
+            "{schedule:?} is already run when advancing a frame"
+        );
+
+        self.rollback_schedules.push(schedule);
+        self
+    }
+
+    /// Inserts the provided `seed` as the [`GgrsSessionSeed`], and includes it in the
+    /// [`Checksum`]. Every peer must use the same seed.
+    pub fn with_session_seed(mut self, seed: u64) -> Self {
+        self.session_seed = Some(seed);
+        self
+    }
+
+    /// Only runs [`GgrsPlugin::run`] while in the provided `state`. Outside of it, the
+    /// [`Session`] is neither polled nor advanced, so remote peers will eventually time out.
+    /// This has no effect when combined with [`GgrsPlugin::without_driver`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+    /// enum GameState {
+    ///     #[default]
+    ///     Menu,
+    ///     InGame,
+    /// }
+    ///
+    /// # let mut app = App::new();
+    /// app.add_state::<GameState>().add_plugins(
+    ///     GgrsPlugin::<GgrsConfig<u8>>::default().run_in_state(GameState::InGame),
+    /// );
+    /// ```
+    pub fn run_in_state<S: States>(mut self, state: S) -> Self {
+        let previous = self.driver_conditions.take();
+
+        self.driver_conditions = Some(Box::new(move |driver| {
+            let driver = match &previous {
+                Some(previous) => previous(driver),
+                None => driver,
+            };
+
+            driver.run_if(in_state(state.clone()))
+        }));
+
+        self
+    }
+
+    /// Exclusive system which polls the [`Session`], reads inputs, and runs the rollback
+    /// schedules as requested by GGRS.
+    pub fn run(world: &mut World) {
+        schedule_systems::run_ggrs_schedules::<C>(world);
+    }
+
+    /// Ends the current [`Session`], returning it so it can be dropped at a time of your choosing,
+    /// which closes its socket. All session state is reset and every snapshot is discarded, so
+    /// the next [`Session`] starts from the first frame exactly like the first one did. Entities
+    /// and resources belonging to the match are left untouched, so despawn them as usual.
+    ///
+    /// GGRS does not notify remote peers when a session is dropped. Instead, they stop receiving
+    /// messages, report the player as interrupted, and disconnect them once the timeout configured
+    /// through [`SessionBuilder::with_disconnect_timeout`](`ggrs::SessionBuilder::with_disconnect_timeout`)
+    /// passes. To leave a match cleanly:
+    /// 1. Stop sending gameplay inputs, optionally telling peers you are leaving out of band.
+    /// 2. Call this method, then drop the returned [`Session`].
+    /// 3. Remaining peers receive [`GgrsEvent::Disconnected`](`ggrs::GgrsEvent::Disconnected`)
+    ///    for your handle, and should shut their own session down once the match is over.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// # type MyConfig = GgrsConfig<u8>;
+    /// #
+    /// fn leave_match(mut commands: Commands) {
+    ///     commands.add(|world: &mut World| {
+    ///         if let Some(session) = GgrsPlugin::<MyConfig>::shutdown(world) {
+    ///             drop(session);
+    ///         }
+    ///     });
+    /// }
+    /// # App::new().add_systems(Update, leave_match);
+    /// ```
+    pub fn shutdown(world: &mut World) -> Option<Session<C>> {
+        schedule_systems::shutdown_session::<C>(world)
+    }
+
+    /// Steps the [`Session`] `frames` times, as fast as possible, returning the number of frames
+    /// the [`RollbackFrameCount`] advanced by. Unlike [`GgrsPlugin::run`], the elapsed [`Time`]
+    /// is ignored, which makes this suitable for benchmarks and other reproducible, scripted runs.
+    /// A [`SyncTestSession`] will still perform its rollbacks, so the cost of saving and loading
+    /// is included.
+    ///
+    /// A [`SyncTestSession`] always advances exactly `frames` frames. A [`P2PSession`] advances
+    /// fewer while it is synchronizing or stalled waiting for remote inputs, and a
+    /// [`SpectatorSession`] may advance more or fewer as it catches up or waits for the host.
+    ///
+    /// A newly inserted [`Session`] is picked up exactly as by [`GgrsPlugin::run`], so the
+    /// [`GgrsSetup`] schedule runs and [`GgrsInitialized`] is sent before its first frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no [`Session`] has been inserted.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, LocalInputs, LocalPlayers};
+    /// #
+    /// # type MyConfig = GgrsConfig<u8>;
+    /// #
+    /// fn scripted_inputs(mut commands: Commands, local_players: Res<LocalPlayers>) {
+    ///     let inputs = local_players.0.iter().map(|&handle| (handle, 1)).collect();
+    ///     commands.insert_resource(LocalInputs::<MyConfig>(inputs));
+    /// }
+    ///
+    /// let session = SessionBuilder::<MyConfig>::new()
+    ///     .with_num_players(1)
+    ///     .add_player(PlayerType::Local, 0)
+    ///     .unwrap()
+    ///     .start_synctest_session()
+    ///     .unwrap();
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins(MinimalPlugins)
+    ///     .add_plugins(GgrsPlugin::<MyConfig>::default())
+    ///     .add_systems(ReadInputs, scripted_inputs)
+    ///     .insert_resource(Session::SyncTest(session));
+    ///
+    /// let advanced = GgrsPlugin::<MyConfig>::run_n_frames(&mut app.world, 1_000);
+    /// assert_eq!(advanced, 1_000);
+    /// ```
+    pub fn run_n_frames(world: &mut World, frames: usize) -> usize {
+        schedule_systems::run_n_frames::<C>(world, frames)
     }
 }
 
 impl<C: Config> Plugin for GgrsPlugin<C> {
     fn build(&self, app: &mut App) {
         app.init_resource::<RollbackFrameCount>()
+            .init_resource::<RollbackFrameRate>()
             .init_resource::<ConfirmedFrameCount>()
             .init_resource::<MaxPredictionWindow>()
             .init_resource::<RollbackOrdered>()
             .init_resource::<LocalPlayers>()
+            .init_resource::<GgrsPlayers>()
+            .init_resource::<GgrsSpectatorStats>()
+            .add_event::<GgrsSessionStateChanged>()
+            .add_event::<GgrsInitialized>()
+            .add_event::<GgrsPredictionStalled>()
+            .add_event::<GgrsSpectatorFellBehind>()
+            .init_resource::<PredictionStalled>()
+            .init_resource::<SessionInitialized>()
+            .init_resource::<RollbackStats>()
+            .init_resource::<GgrsResimStats>()
+            .init_resource::<GgrsInputQuality>()
             .init_resource::<FixedTimestepData>()
+            .init_resource::<SnapshotKeyframeInterval>()
+            .init_resource::<InputSampling>()
+            .init_resource::<StrictErrors>()
+            .init_resource::<schedule_systems::RecordedInputs<C>>()
+            .init_resource::<schedule_systems::ConfirmedScheduleFrame>()
             .init_schedule(ReadInputs)
             .init_schedule(LoadWorld)
             .init_schedule(SaveWorld)
+            .init_schedule(GgrsConfirmedSchedule)
+            .init_schedule(GgrsSetup)
             .edit_schedule(AdvanceWorld, |schedule| {
                 // AdvanceWorld is mostly a facilitator for GgrsSchedule, so SingleThreaded avoids overhead
                 // This can be overridden if desired.
                 schedule.set_executor_kind(ExecutorKind::SingleThreaded);
             })
-            .edit_schedule(GgrsSchedule, |schedule| {
-                schedule.set_build_settings(ScheduleBuildSettings {
-                    ambiguity_detection: LogLevel::Error,
-                    ..default()
-                });
-            })
-            .add_systems(
-                PreUpdate,
-                schedule_systems::run_ggrs_schedules::<C>.after(InputSystem),
-            )
+            .insert_resource(RollbackSchedules::new(&self.rollback_schedules))
             .add_plugins((
                 SnapshotSetPlugin,
+                SnapshotMemoryPlugin,
                 ChecksumPlugin,
                 EntitySnapshotPlugin,
                 EntityChecksumPlugin,
@@ -226,7 +1428,125 @@ impl<C: Config> Plugin for GgrsPlugin<C> {
                 ComponentMapEntitiesPlugin::<Parent>::default(),
                 ComponentSnapshotPlugin::<ReflectStrategy<Children>>::default(),
                 ComponentMapEntitiesPlugin::<Children>::default(),
+                // peers running at different rates must not silently agree
+                ResourceChecksumPlugin::<RollbackFrameRate>::default(),
             ));
+
+        for &schedule in [GgrsSchedule.intern()]
+            .iter()
+            .chain(&self.rollback_schedules)
+        {
+            app.edit_schedule(schedule, |schedule| {
+                schedule.set_build_settings(ScheduleBuildSettings {
+                    ambiguity_detection: LogLevel::Error,
+                    ..default()
+                });
+            });
+        }
+
+        if let Some(seed) = self.session_seed {
+            app.insert_resource(GgrsSessionSeed(seed))
+                .checksum_resource_with_hash::<GgrsSessionSeed>();
+        }
+
+        if let Some(policy) = &self.disconnect_input_policy {
+            app.set_disconnect_input_policy(policy.clone());
+        }
+
+        if let Some(ai_players) = &self.ai_players {
+            app.insert_resource(ai_players.clone());
+        }
+
+        if let Some(frames) = self.input_history {
+            app.insert_resource(PlayerInputHistory::<C>::new(frames))
+                .rollback_resource_with_clone::<PlayerInputHistory<C>>();
+        }
+
+        if let Some(dump) = &self.desync_dump {
+            app.insert_resource(dump.clone())
+                .init_resource::<DesyncDumpHistory>()
+                .add_systems(
+                    SaveWorld,
+                    DesyncDumpHistory::record.before(SaveWorldSet::Snapshot),
+                );
+        }
+
+        if let Some(local_mode) = self.local_mode {
+            warn!("GGRS local mode is enabled. Frames advanced without a Session are not deterministic.");
+            app.insert_resource(local_mode);
+        }
+
+        app.insert_resource(RedundantVerification(self.redundant_verification))
+            .insert_resource(Lockstep(self.lockstep))
+            .insert_resource(ParallelInput(self.parallel_input))
+            .insert_resource(SpectatorCatchup(self.spectator_catchup))
+            .insert_resource(self.missing_snapshot);
+
+        if let Some(max_lag) = self.spectator_max_lag {
+            app.insert_resource(SpectatorMaxLag(max_lag));
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        app.init_resource::<MaxFrameDelta>();
+
+        app.configure_sets(PreUpdate, GgrsStageSet.after(InputSystem))
+            .add_systems(
+                PreUpdate,
+                PendingSession::<C>::poll
+                    .before(Self::run)
+                    .run_if(resource_exists::<PendingSession<C>>())
+                    .in_set(GgrsStageSet),
+            );
+
+        if self.add_driver {
+            let driver = Self::run.in_set(GgrsStageSet);
+
+            let driver = match &self.driver_conditions {
+                Some(conditions) => conditions(driver),
+                None => driver,
+            };
+
+            app.add_systems(PreUpdate, driver);
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        #[cfg(debug_assertions)]
+        warn_unregistered_reflect_types(&app.world);
+
+        if self.determinism_lint {
+            for warning in check_determinism(&mut app.world) {
+                warn!("{warning}");
+            }
+        }
+    }
+}
+
+/// Warns about every type snapshot using reflection which contains unregistered types, see
+/// [`RollbackRegistry::unregistered_reflect_types`].
+#[cfg(debug_assertions)]
+fn warn_unregistered_reflect_types(world: &World) {
+    let (Some(registry), Some(type_registry)) = (
+        world.get_resource::<RollbackRegistry>(),
+        world.get_resource::<AppTypeRegistry>(),
+    ) else {
+        return;
+    };
+
+    let mut unregistered = BTreeMap::<_, Vec<_>>::new();
+
+    for missing in registry.unregistered_reflect_types(&type_registry.read()) {
+        unregistered
+            .entry(missing.rollback_type)
+            .or_default()
+            .push(missing.type_path);
+    }
+
+    for (rollback_type, type_paths) in unregistered {
+        warn!(
+            "{rollback_type} is rolled back using reflection, but contains types which are not registered: {}. Register them using App::register_type so it can be fully reflected.",
+            type_paths.join(", ")
+        );
     }
 }
 
@@ -259,36 +1579,343 @@ pub trait GgrsApp {
     /// Registers a component type for saving and loading from the world. This
     /// uses [`reflection`](`Reflect`) based snapshots for rollback.
     ///
+    /// The type is also registered in the [`AppTypeRegistry`], so the same set of types
+    /// is available to `DynamicScene`-based persistence without a second registration.
+    /// Types nested within its fields are not, so in debug builds a warning lists any which
+    /// are missing, see [`RollbackRegistry::unregistered_reflect_types`].
+    /// To opt out, add a [`ComponentSnapshotPlugin`] using a [`ReflectStrategy`] directly.
+    ///
     /// NOTE: Unlike previous versions of `bevy_ggrs`, this will no longer automatically
     /// apply entity mapping through the [`MapEntities`](`bevy::ecs::entity::MapEntities`) trait.
     /// If you require this behavior, see [`ComponentMapEntitiesPlugin`].
     fn rollback_component_with_reflect<Type>(&mut self) -> &mut Self
     where
-        Type: Component + Reflect + FromWorld;
+        Type: RollbackComponent + RollbackReflect + RollbackFromWorld;
 
     /// Registers a resource type for saving and loading from the world. This
     /// uses [`reflection`](`Reflect`) based snapshots for rollback.
     ///
+    /// The type is also registered in the [`AppTypeRegistry`], so the same set of types
+    /// is available to `DynamicScene`-based persistence without a second registration.
+    /// Types nested within its fields are not, so in debug builds a warning lists any which
+    /// are missing, see [`RollbackRegistry::unregistered_reflect_types`].
+    /// To opt out, add a [`ResourceSnapshotPlugin`] using a [`ReflectStrategy`] directly.
+    ///
     /// NOTE: Unlike previous versions of `bevy_ggrs`, this will no longer automatically
     /// apply entity mapping through the [`MapEntities`](`bevy::ecs::entity::MapEntities`) trait.
     /// If you require this behavior, see [`ComponentMapEntitiesPlugin`].
     fn rollback_resource_with_reflect<Type>(&mut self) -> &mut Self
     where
-        Type: Resource + Reflect + FromWorld;
+        Type: RollbackResource + RollbackReflect + RollbackFromWorld;
+
+    /// Registers a component type for saving and loading from the world as part of the
+    /// [`ComponentGroup`] called `group`. This uses [`reflection`](`Reflect`) based snapshots,
+    /// and skips saving the group on entities where none of its components have changed, see
+    /// [`ComponentGroupSnapshotPlugin`]. Components which usually change together should share
+    /// a group.
+    ///
+    /// The type is also registered in the [`AppTypeRegistry`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// #[derive(Component, Reflect, Default)]
+    /// struct Position(Vec2);
+    ///
+    /// #[derive(Component, Reflect, Default)]
+    /// struct Velocity(Vec2);
+    ///
+    /// #[derive(Component, Reflect, Default)]
+    /// struct MaxHealth(u32);
+    ///
+    /// # let mut app = App::new();
+    /// app.rollback_component_with_reflect_in_group::<Position>("movement")
+    ///     .rollback_component_with_reflect_in_group::<Velocity>("movement")
+    ///     .rollback_component_with_reflect_in_group::<MaxHealth>("stats");
+    /// ```
+    fn rollback_component_with_reflect_in_group<Type>(&mut self, group: &'static str) -> &mut Self
+    where
+        Type: RollbackComponent + RollbackReflect + RollbackFromWorld;
+
+    /// Registers a component type for saving and loading from the world. This
+    /// uses [`FromReflect`] based snapshots for rollback, see [`FromReflectStrategy`].
+    ///
+    /// Prefer this over [`rollback_component_with_reflect`](`GgrsApp::rollback_component_with_reflect`)
+    /// for types whose snapshots cannot always be [applied](`Reflect::apply`) in place, such as
+    /// enums which change variant between frames. The type and its
+    /// [`ReflectFromReflect`] data are registered in the
+    /// [`AppTypeRegistry`].
+    fn rollback_component_with_from_reflect<Type>(&mut self) -> &mut Self
+    where
+        Type: Component + FromReflect + TypePath + GetTypeRegistration;
+
+    /// Registers a resource type for saving and loading from the world. This
+    /// uses [`FromReflect`] based snapshots for rollback, see [`FromReflectStrategy`].
+    ///
+    /// Prefer this over [`rollback_resource_with_reflect`](`GgrsApp::rollback_resource_with_reflect`)
+    /// for types whose snapshots cannot always be [applied](`Reflect::apply`) in place, such as
+    /// enums which change variant between frames. The type and its
+    /// [`ReflectFromReflect`] data are registered in the
+    /// [`AppTypeRegistry`].
+    fn rollback_resource_with_from_reflect<Type>(&mut self) -> &mut Self
+    where
+        Type: Resource + FromReflect + TypePath + GetTypeRegistration;
+
+    /// Registers a component type for saving and loading from the world, using a custom
+    /// [`Strategy`]. This allows hot or unusual types to be stored in whatever form is cheapest
+    /// for them, such as a packed or serialized representation, while other types keep using
+    /// [`Copy`], [`Clone`], or [`reflection`](`Reflect`) based snapshots.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, Strategy};
+    /// #
+    /// #[derive(Component)]
+    /// struct Facing(Vec2);
+    ///
+    /// // Only the angle is stored in each snapshot
+    /// struct AngleStrategy;
+    ///
+    /// impl Strategy for AngleStrategy {
+    ///     type Target = Facing;
+    ///     type Stored = f32;
+    ///
+    ///     fn store(target: &Facing) -> f32 {
+    ///         target.0.y.atan2(target.0.x)
+    ///     }
+    ///
+    ///     fn load(stored: &f32) -> Facing {
+    ///         Facing(Vec2::from_angle(*stored))
+    ///     }
+    /// }
+    ///
+    /// # let mut app = App::new();
+    /// app.rollback_component_with_strategy::<AngleStrategy>();
+    /// ```
+    fn rollback_component_with_strategy<S>(&mut self) -> &mut Self
+    where
+        S: Strategy + Send + Sync + 'static,
+        S::Target: Component,
+        S::Stored: Send + Sync + 'static;
+
+    /// Registers a component type for saving and loading from the world, storing only a delta
+    /// produced by `diff` each frame and reconstructing it using `apply`.
+    /// See [`ComponentDeltaSnapshotPlugin`].
+    fn rollback_component_with_delta<Type, Delta>(
+        &mut self,
+        diff: fn(&Type, &Type) -> Delta,
+        apply: fn(&mut Type, &Delta),
+    ) -> &mut Self
+    where
+        Type: Component + Clone,
+        Delta: Send + Sync + 'static;
+
+    /// Registers state which is not a [`Component`] or [`Resource`] for rollback, captured from
+    /// the [`World`] by `save` and restored by `load`. See [`WorldStateSnapshotPlugin`].
+    fn rollback_world_state<State>(
+        &mut self,
+        save: impl Fn(&World) -> State + Send + Sync + 'static,
+        load: impl Fn(&mut World, &State) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        State: Send + Sync + 'static;
+
+    /// Registers a resource type for saving and loading from the world, using a custom
+    /// [`Strategy`]. See [`GgrsApp::rollback_component_with_strategy`].
+    fn rollback_resource_with_strategy<S>(&mut self) -> &mut Self
+    where
+        S: Strategy + Send + Sync + 'static,
+        S::Target: Resource,
+        S::Stored: Send + Sync + 'static;
 
     /// Set the frequency that game updates should be performed at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is `0`.
     fn set_rollback_schedule_fps(&mut self, fps: usize) -> &mut Self;
 
+    /// Limit how much real time a single app update can add towards advancing rollback frames,
+    /// see [`MaxFrameDelta`].
+    fn set_max_frame_delta(&mut self, max_delta: Duration) -> &mut Self;
+
+    /// Enable proportional time dilation towards remote peers, see [`TimeDilation`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `gain` or `max_adjustment` is not finite, or if the `max_adjustment` is
+    /// negative or at least `1`.
+    fn set_time_dilation(&mut self, time_dilation: TimeDilation) -> &mut Self;
+
+    /// Set how much longer each rollback frame lasts while ahead of remote peers, see
+    /// [`RunSlowFactor`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is less than `1`, or not finite.
+    fn set_run_slow_factor(&mut self, factor: f64) -> &mut Self;
+
+    /// Read each local player's input with `reader`, see [`PlayerInputReader`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, LocalPlayers};
+    /// # use ggrs::PlayerHandle;
+    /// #
+    /// # type MyConfig = GgrsConfig<u8>;
+    /// #
+    /// // Must only read from the world, since players may be read in parallel
+    /// fn read_gamepad(handle: PlayerHandle, world: &World) -> u8 {
+    ///     let buttons = world.resource::<Input<GamepadButton>>();
+    ///     let gamepad = Gamepad::new(handle);
+    ///
+    ///     buttons.pressed(GamepadButton::new(gamepad, GamepadButtonType::South)) as u8
+    /// }
+    ///
+    /// # let mut app = App::new();
+    /// app.add_plugins(GgrsPlugin::<MyConfig>::default().with_parallel_input(true))
+    ///     .set_player_input_reader::<MyConfig>(read_gamepad);
+    /// ```
+    fn set_player_input_reader<C: Config>(
+        &mut self,
+        reader: fn(PlayerHandle, &World) -> C::Input,
+    ) -> &mut Self;
+
+    /// Set how often local inputs are sampled, see [`InputSampling`].
+    fn set_input_sampling(&mut self, input_sampling: InputSampling) -> &mut Self;
+
+    /// Panic on GGRS errors instead of logging them, see [`StrictErrors`].
+    fn set_strict_errors(&mut self, strict: bool) -> &mut Self;
+
+    /// Choose which input disconnected players provide, see [`DisconnectInputPolicy`]. Unlike
+    /// inserting the policy directly, this also rolls back the inputs repeated by
+    /// [`DisconnectInputPolicy::Repeat`].
+    fn set_disconnect_input_policy<C: Config>(
+        &mut self,
+        policy: DisconnectInputPolicy<C>,
+    ) -> &mut Self;
+
+    /// Also store state produced by `saver` in GGRS's own buffer, see [`GgrsStateSaver`].
+    fn save_ggrs_state_with<C: Config>(&mut self, saver: fn(&World) -> C::State) -> &mut Self;
+
+    /// Call `observer` with every [`GgrsRequest`] before it is handled, see [`GgrsRequestObserver`].
+    fn observe_requests<C: Config>(&mut self, observer: fn(&GgrsRequest<C>)) -> &mut Self;
+
+    /// Only snapshot every `interval` frames. When GGRS requests a frame between keyframes,
+    /// the nearest earlier keyframe is loaded and the remaining frames are re-simulated
+    /// using the inputs they were originally advanced with.
+    ///
+    /// Frames between keyframes are reported to GGRS without a checksum, so any
+    /// [desync detection](`ggrs::DesyncDetection`) interval should be a multiple of `interval`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero.
+    fn set_snapshot_keyframe_interval(&mut self, interval: usize) -> &mut Self;
+
+    /// Keep confirmed snapshots for which `retain` returns `true`, such as for a rewind
+    /// feature. See [`SnapshotRetention`].
+    ///
+    /// Retained snapshots share the [`depth`](`GgrsSnapshots::depth`) of each storage with the
+    /// snapshots GGRS may still roll back to, and the oldest are discarded first once it is
+    /// exceeded, so `retain` should keep well under 60 snapshots at the default depth.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// # let mut app = App::new();
+    /// // Keep every 10th frame from the last 5 seconds, 30 snapshots at most
+    /// app.set_snapshot_retention(|frame, current_frame| {
+    ///     current_frame - frame < 300 && frame % 10 == 0
+    /// });
+    /// ```
+    fn set_snapshot_retention(&mut self, retain: fn(i32, i32) -> bool) -> &mut Self;
+
+    /// Limit the estimated memory held by snapshots to `bytes`, reducing snapshot depth while
+    /// it is exceeded. Current usage can be read from the [`SnapshotMemoryUsage`].
+    /// See [`SnapshotMemoryLimit`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, SnapshotMemoryUsage};
+    /// #
+    /// # let mut app = App::new();
+    /// app.set_snapshot_memory_limit(64 * 1024 * 1024);
+    ///
+    /// fn report_memory(usage: Res<SnapshotMemoryUsage>) {
+    ///     info!("Snapshots hold {} bytes", usage.total_bytes());
+    /// }
+    /// # app.add_systems(Update, report_memory);
+    /// ```
+    fn set_snapshot_memory_limit(&mut self, bytes: usize) -> &mut Self;
+
     /// Adds a component type to the checksum generation pipeline using [`Hash`].
     fn checksum_component_with_hash<Type>(&mut self) -> &mut Self
     where
         Type: Component + Hash;
 
-    /// Updates a component after rollback using [`MapEntities`].
+    /// Registers [`Transform`] for [`Copy`] based rollback and adds it to the checksum
+    /// using [`transform_hasher`]. This avoids reflection entirely for the most commonly
+    /// rolled back component; compare with `--reflect` in the `particles` stress test.
+    fn rollback_transform(&mut self) -> &mut Self;
+
+    /// Recomputes [`GlobalTransform`] at the end of every load, so systems in the [`GgrsSchedule`]
+    /// see a [`GlobalTransform`] consistent with the loaded [`Transform`], see [`TransformPropagationPlugin`].
+    fn propagate_transforms_after_load(&mut self) -> &mut Self;
+
+    /// Registers the built-in Bevy components games most commonly need rolled back:
+    /// - [`Name`], using [`Clone`] based snapshots.
+    /// - [`Transform`], as with [`GgrsApp::rollback_transform`].
+    ///
+    /// [`Parent`] and [`Children`] are always rolled back by the [`GgrsPlugin`].
+    /// Do not also register any of these types individually.
+    fn rollback_bevy_builtins(&mut self) -> &mut Self;
+
+    /// Maintains an [`Interpolated`] copy of a component for rendering, see [`ComponentInterpolationPlugin`].
+    fn interpolate_component<Type>(&mut self, lerp: fn(&Type, &Type, f32) -> Type) -> &mut Self
+    where
+        Type: Component + Clone;
+
+    /// Registers a relationship between entities, where `A` refers to another entity which refers
+    /// back using `B`, for rollback using [`Clone`] based snapshots. Both sides are updated using
+    /// [`MapEntities`] after a rollback, and any side left referring to an entity which no longer
+    /// exists is removed unless pruned with [`GgrsApp::prune_relationship_with`], see
+    /// [`RollbackRelationshipPlugin`]. `A` and `B` may be the same type.
+    fn rollback_relationship<A, B>(&mut self) -> &mut Self
+    where
+        A: Component + Clone + MapEntities,
+        B: Component + Clone + MapEntities;
+
+    /// Prunes a side of a relationship registered with [`GgrsApp::rollback_relationship`] which
+    /// refers to an entity which no longer exists, rather than removing it, see
+    /// [`RollbackRelationshipPlugin`]. Each such reference is replaced with [`Entity::PLACEHOLDER`]
+    /// before `prune` is called, which should remove them and return `false` if the component
+    /// should be removed all the same. The component is also removed if any placeholder remains.
+    fn prune_relationship_with<Type>(&mut self, prune: fn(&mut Type) -> bool) -> &mut Self
+    where
+        Type: Component;
+
+    /// Updates a component after rollback using [`MapEntities`]. This does nothing if the
+    /// component is already updated, such as by a [`RollbackRelationshipPlugin`].
     fn update_component_with_map_entities<Type>(&mut self) -> &mut Self
     where
         Type: Component + MapEntities;
 
+    /// Updates a component after rollback using a fixup function, see [`ComponentPostLoadPlugin`].
+    fn update_component_with_post_load<Type>(
+        &mut self,
+        fixup: for<'a> fn(&'a mut Type),
+    ) -> &mut Self
+    where
+        Type: Component;
+
     /// Adds a resource type to the checksum generation pipeline using [`Hash`].
     fn checksum_resource_with_hash<Type>(&mut self) -> &mut Self
     where
@@ -308,27 +1935,247 @@ pub trait GgrsApp {
     fn checksum_resource<Type>(&mut self, hasher: for<'a> fn(&'a Type) -> u64) -> &mut Self
     where
         Type: Resource;
+
+    /// Adds systems to the [`GgrsSchedule`], where all game logic affected by rollback must run.
+    /// This is equivalent to `add_systems(GgrsSchedule, systems)`, but cannot accidentally target
+    /// [`Update`] instead.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// # fn move_players() {}
+    /// # fn apply_damage() {}
+    /// #
+    /// # let mut app = App::new();
+    /// app.add_ggrs_systems((move_players, apply_damage).chain());
+    /// ```
+    fn add_ggrs_systems<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> &mut Self;
+
+    /// Adds systems to the [`GgrsSchedule`] within the [`OrderIndependent`] `group`, allowing them
+    /// to be ambiguous with each other. Only use this when the order these systems run in
+    /// cannot change the resulting state, otherwise peers may desync.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::prelude::*;
+    /// #
+    /// #[derive(Resource, Default)]
+    /// struct Score(u32);
+    ///
+    /// fn score_goals(mut score: ResMut<Score>) { score.0 += 3; }
+    /// fn score_assists(mut score: ResMut<Score>) { score.0 += 1; }
+    ///
+    /// # let mut app = App::new();
+    /// // addition is commutative, so these systems do not need to be ordered
+    /// app.add_order_independent_ggrs_systems("scoring", (score_goals, score_assists));
+    /// ```
+    fn add_order_independent_ggrs_systems<M>(
+        &mut self,
+        group: &'static str,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self;
 }
 
 impl GgrsApp for App {
     fn set_rollback_schedule_fps(&mut self, fps: usize) -> &mut Self {
-        self.world.insert_resource(RollbackFrameRate(fps));
+        self.world.insert_resource(RollbackFrameRate::new(fps));
+
+        self
+    }
+
+    fn set_max_frame_delta(&mut self, max_delta: Duration) -> &mut Self {
+        self.world.insert_resource(MaxFrameDelta(max_delta));
+
+        self
+    }
+
+    fn set_time_dilation(&mut self, time_dilation: TimeDilation) -> &mut Self {
+        let TimeDilation {
+            gain,
+            max_adjustment,
+            ..
+        } = time_dilation;
+
+        assert!(
+            gain.is_finite(),
+            "Time dilation gain must be finite, got {gain}"
+        );
+        assert!(
+            max_adjustment.is_finite() && (0. ..1.).contains(&max_adjustment),
+            "Time dilation max adjustment must be at least 0 and less than 1, got {max_adjustment}"
+        );
+
+        self.world.insert_resource(time_dilation);
+
+        self
+    }
+
+    fn set_run_slow_factor(&mut self, factor: f64) -> &mut Self {
+        self.world.insert_resource(RunSlowFactor::new(factor));
+
+        self
+    }
+
+    fn set_player_input_reader<C: Config>(
+        &mut self,
+        reader: fn(PlayerHandle, &World) -> C::Input,
+    ) -> &mut Self {
+        self.world.insert_resource(PlayerInputReader::<C>(reader));
+
+        self
+    }
+
+    fn set_input_sampling(&mut self, input_sampling: InputSampling) -> &mut Self {
+        self.world.insert_resource(input_sampling);
+
+        self
+    }
+
+    fn set_strict_errors(&mut self, strict: bool) -> &mut Self {
+        self.world.insert_resource(StrictErrors(strict));
+
+        self
+    }
+
+    fn set_disconnect_input_policy<C: Config>(
+        &mut self,
+        policy: DisconnectInputPolicy<C>,
+    ) -> &mut Self {
+        use disconnect_input::LastConnectedInputs;
+
+        // the policy may be replaced later, but the repeated inputs are only registered once
+        if !self.is_plugin_added::<ResourceSnapshotPlugin<CloneStrategy<LastConnectedInputs<C>>>>()
+        {
+            self.init_resource::<LastConnectedInputs<C>>()
+                .rollback_resource_with_clone::<LastConnectedInputs<C>>();
+        }
+
+        self.world.insert_resource(policy);
+
+        self
+    }
+
+    fn save_ggrs_state_with<C: Config>(&mut self, saver: fn(&World) -> C::State) -> &mut Self {
+        self.world.insert_resource(GgrsStateSaver::<C>(saver));
+
+        self
+    }
+
+    fn observe_requests<C: Config>(&mut self, observer: fn(&GgrsRequest<C>)) -> &mut Self {
+        self.world.insert_resource(GgrsRequestObserver(observer));
+
+        self
+    }
+
+    fn set_snapshot_retention(&mut self, retain: fn(i32, i32) -> bool) -> &mut Self {
+        self.world.insert_resource(SnapshotRetention(retain));
+
+        self
+    }
+
+    fn set_snapshot_memory_limit(&mut self, bytes: usize) -> &mut Self {
+        self.world.insert_resource(SnapshotMemoryLimit(bytes));
+
+        self
+    }
+
+    fn set_snapshot_keyframe_interval(&mut self, interval: usize) -> &mut Self {
+        assert!(
+            interval > 0,
+            "Snapshot keyframe interval must be at least 1"
+        );
+
+        self.world
+            .insert_resource(SnapshotKeyframeInterval(interval));
 
         self
     }
 
     fn rollback_component_with_reflect<Type>(&mut self) -> &mut Self
     where
-        Type: Component + Reflect + FromWorld,
+        Type: RollbackComponent + RollbackReflect + RollbackFromWorld,
     {
-        self.add_plugins(ComponentSnapshotPlugin::<ReflectStrategy<Type>>::default())
+        self.register_type::<Type>()
+            .add_plugins(ComponentSnapshotPlugin::<ReflectStrategy<Type>>::default())
     }
 
     fn rollback_resource_with_reflect<Type>(&mut self) -> &mut Self
     where
-        Type: Resource + Reflect + FromWorld,
+        Type: RollbackResource + RollbackReflect + RollbackFromWorld,
+    {
+        self.register_type::<Type>()
+            .add_plugins(ResourceSnapshotPlugin::<ReflectStrategy<Type>>::default())
+    }
+
+    fn rollback_component_with_reflect_in_group<Type>(&mut self, group: &'static str) -> &mut Self
+    where
+        Type: RollbackComponent + RollbackReflect + RollbackFromWorld,
+    {
+        self.register_type::<Type>();
+        snapshot::add_to_component_group::<Type>(self, group);
+        self
+    }
+
+    fn rollback_component_with_from_reflect<Type>(&mut self) -> &mut Self
+    where
+        Type: Component + FromReflect + TypePath + GetTypeRegistration,
+    {
+        self.register_type::<Type>()
+            .register_type_data::<Type, ReflectFromReflect>()
+            .add_plugins(ComponentSnapshotPlugin::<FromReflectStrategy<Type>>::default())
+    }
+
+    fn rollback_resource_with_from_reflect<Type>(&mut self) -> &mut Self
+    where
+        Type: Resource + FromReflect + TypePath + GetTypeRegistration,
+    {
+        self.register_type::<Type>()
+            .register_type_data::<Type, ReflectFromReflect>()
+            .add_plugins(ResourceSnapshotPlugin::<FromReflectStrategy<Type>>::default())
+    }
+
+    fn rollback_component_with_strategy<S>(&mut self) -> &mut Self
+    where
+        S: Strategy + Send + Sync + 'static,
+        S::Target: Component,
+        S::Stored: Send + Sync + 'static,
     {
-        self.add_plugins(ResourceSnapshotPlugin::<ReflectStrategy<Type>>::default())
+        self.add_plugins(ComponentSnapshotPlugin::<S>::default())
+    }
+
+    fn rollback_component_with_delta<Type, Delta>(
+        &mut self,
+        diff: fn(&Type, &Type) -> Delta,
+        apply: fn(&mut Type, &Delta),
+    ) -> &mut Self
+    where
+        Type: Component + Clone,
+        Delta: Send + Sync + 'static,
+    {
+        self.add_plugins(ComponentDeltaSnapshotPlugin::new(diff, apply))
+    }
+
+    fn rollback_world_state<State>(
+        &mut self,
+        save: impl Fn(&World) -> State + Send + Sync + 'static,
+        load: impl Fn(&mut World, &State) + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        State: Send + Sync + 'static,
+    {
+        self.add_plugins(WorldStateSnapshotPlugin::new(save, load))
+    }
+
+    fn rollback_resource_with_strategy<S>(&mut self) -> &mut Self
+    where
+        S: Strategy + Send + Sync + 'static,
+        S::Target: Resource,
+        S::Stored: Send + Sync + 'static,
+    {
+        self.add_plugins(ResourceSnapshotPlugin::<S>::default())
     }
 
     fn rollback_component_with_copy<Type>(&mut self) -> &mut Self
@@ -366,13 +2213,70 @@ impl GgrsApp for App {
         self.add_plugins(ComponentChecksumPlugin::<Type>::default())
     }
 
+    fn rollback_transform(&mut self) -> &mut Self {
+        self.rollback_component_with_copy::<Transform>()
+            .checksum_component::<Transform>(transform_hasher)
+    }
+
+    fn propagate_transforms_after_load(&mut self) -> &mut Self {
+        self.add_plugins(TransformPropagationPlugin)
+    }
+
+    fn rollback_bevy_builtins(&mut self) -> &mut Self {
+        self.rollback_component_with_clone::<Name>()
+            .rollback_transform()
+    }
+
+    fn interpolate_component<Type>(&mut self, lerp: fn(&Type, &Type, f32) -> Type) -> &mut Self
+    where
+        Type: Component + Clone,
+    {
+        self.add_plugins(ComponentInterpolationPlugin::<Type>(lerp))
+    }
+
+    fn rollback_relationship<A, B>(&mut self) -> &mut Self
+    where
+        A: Component + Clone + MapEntities,
+        B: Component + Clone + MapEntities,
+    {
+        self.rollback_component_with_clone::<A>();
+
+        if TypeId::of::<A>() != TypeId::of::<B>() {
+            self.rollback_component_with_clone::<B>();
+        }
+
+        self.add_plugins(RollbackRelationshipPlugin::<A, B>::default())
+    }
+
+    fn prune_relationship_with<Type>(&mut self, prune: fn(&mut Type) -> bool) -> &mut Self
+    where
+        Type: Component,
+    {
+        self.insert_resource(snapshot::RelationshipPrune::<Type>(prune))
+    }
+
     fn update_component_with_map_entities<Type>(&mut self) -> &mut Self
     where
         Type: Component + MapEntities,
     {
+        // relationships map both of their sides already
+        if self.is_plugin_added::<ComponentMapEntitiesPlugin<Type>>() {
+            return self;
+        }
+
         self.add_plugins(ComponentMapEntitiesPlugin::<Type>::default())
     }
 
+    fn update_component_with_post_load<Type>(
+        &mut self,
+        fixup: for<'a> fn(&'a mut Type),
+    ) -> &mut Self
+    where
+        Type: Component,
+    {
+        self.add_plugins(ComponentPostLoadPlugin::<Type>(fixup))
+    }
+
     fn checksum_resource_with_hash<Type>(&mut self) -> &mut Self
     where
         Type: Resource + Hash,
@@ -400,4 +2304,19 @@ impl GgrsApp for App {
     {
         self.add_plugins(ResourceChecksumPlugin::<Type>(hasher))
     }
+
+    fn add_ggrs_systems<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> &mut Self {
+        self.add_systems(GgrsSchedule, systems)
+    }
+
+    fn add_order_independent_ggrs_systems<M>(
+        &mut self,
+        group: &'static str,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let set = OrderIndependent(group);
+
+        self.configure_sets(GgrsSchedule, set.ambiguous_with(set))
+            .add_systems(GgrsSchedule, systems.in_set(set))
+    }
 }
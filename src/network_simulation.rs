@@ -0,0 +1,323 @@
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use bevy::{prelude::*, utils::Duration};
+use ggrs::{Config, GgrsError, Message, NonBlockingSocket, PlayerType};
+
+use crate::{GgrsSessionBuilder, Session};
+
+/// Artificial network conditions applied by a [`SimulatedSocket`].
+///
+/// Delays and drops are drawn from a generator seeded with [`seed`](`Self::seed`), so the same
+/// messages are affected in the same way on every run. Delivery follows a [`SimulatedClock`],
+/// so a delay spans the same number of frames however quickly the [`App`] is updated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NetworkConditions {
+    /// One-way delay added to every message.
+    pub latency: Duration,
+    /// Maximum random deviation from the [`latency`](`Self::latency`), in either direction.
+    /// Jitter allows messages to arrive out of order.
+    pub jitter: Duration,
+    /// Probability of dropping a message, between `0.0` and `1.0`. Any other value is rejected by
+    /// [`SimulatedSocket::new`].
+    pub packet_loss: f64,
+    /// Seed for the random delays and drops.
+    pub seed: u64,
+}
+
+impl Default for NetworkConditions {
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            packet_loss: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+/// A clock deciding when messages delayed by a [`SimulatedSocket`] are delivered. It starts at
+/// zero and only moves when [`advance`](`Self::advance`)d, usually by one frame per update of
+/// every [`App`]. Clones share the same time, so every socket of a simulated network should use
+/// clones of a single clock.
+///
+/// Only the simulated delays follow this clock. GGRS's own timers, such as those resending
+/// synchronization requests, detecting disconnects and measuring ping, still use the wall clock.
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedClock(Arc<AtomicU64>);
+
+impl SimulatedClock {
+    /// The time elapsed since the clock was created, as advanced so far.
+    pub fn now(&self) -> Duration {
+        Duration::from_nanos(self.0.load(Ordering::Acquire))
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+
+        self.0.fetch_add(nanos, Ordering::AcqRel);
+    }
+}
+
+/// A message waiting to be passed on to the inner socket of a [`SimulatedSocket`].
+struct InFlight<A> {
+    /// The [`SimulatedClock`] time at which the message is due.
+    deliver_at: Duration,
+    address: A,
+    message: Message,
+}
+
+/// A [`NonBlockingSocket`] which wraps another socket, delaying and dropping outgoing messages
+/// according to its [`NetworkConditions`]. This exercises prediction and rollback without a real
+/// network, and works with any transport, such as [`LocalSocket`] or GGRS's
+/// [`UdpNonBlockingSocket`](`ggrs::UdpNonBlockingSocket`).
+///
+/// Delayed messages are passed on whenever the socket is used once they are due according to its
+/// [`SimulatedClock`]. GGRS uses the socket at least once per update. Intended for development only.
+pub struct SimulatedSocket<S, A> {
+    inner: S,
+    conditions: NetworkConditions,
+    clock: SimulatedClock,
+    rng: u64,
+    in_flight: Vec<InFlight<A>>,
+}
+
+impl<S, A> SimulatedSocket<S, A> {
+    /// Wraps `inner`, applying `conditions` to every message sent through it, with delays
+    /// measured by `clock`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`packet_loss`](`NetworkConditions::packet_loss`) is not between `0.0` and
+    /// `1.0`.
+    pub fn new(inner: S, conditions: NetworkConditions, clock: SimulatedClock) -> Self {
+        let packet_loss = conditions.packet_loss;
+        assert!(
+            (0.0..=1.0).contains(&packet_loss),
+            "Packet loss must be between 0 and 1, got {packet_loss}"
+        );
+
+        Self {
+            inner,
+            conditions,
+            clock,
+            rng: conditions.seed,
+            in_flight: Vec::new(),
+        }
+    }
+
+    /// The [`NetworkConditions`] applied by this socket.
+    pub fn conditions(&self) -> NetworkConditions {
+        self.conditions
+    }
+
+    /// The number of messages which have been sent, but not yet passed on to the inner socket.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// A uniformly distributed value in `[0, 1)`, using SplitMix64.
+    fn next_unit(&mut self) -> f64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn delay(&mut self) -> Duration {
+        let deviation = self.conditions.jitter.as_secs_f64() * (2.0 * self.next_unit() - 1.0);
+        let delay = self.conditions.latency.as_secs_f64() + deviation;
+
+        Duration::from_secs_f64(delay.max(0.0))
+    }
+}
+
+impl<S, A> SimulatedSocket<S, A>
+where
+    S: NonBlockingSocket<A>,
+    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+{
+    /// Passes every message which is due on to the inner socket, earliest first.
+    fn deliver_due(&mut self) {
+        let now = self.clock.now();
+
+        let (mut due, pending) = std::mem::take(&mut self.in_flight)
+            .into_iter()
+            .partition::<Vec<_>, _>(|message| message.deliver_at <= now);
+
+        self.in_flight = pending;
+
+        due.sort_by_key(|message| message.deliver_at);
+
+        for InFlight {
+            address, message, ..
+        } in due
+        {
+            self.inner.send_to(&message, &address);
+        }
+    }
+}
+
+impl<S, A> NonBlockingSocket<A> for SimulatedSocket<S, A>
+where
+    S: NonBlockingSocket<A>,
+    A: Clone + PartialEq + Eq + Hash + Send + Sync,
+{
+    fn send_to(&mut self, msg: &Message, addr: &A) {
+        if self.next_unit() >= self.conditions.packet_loss {
+            let deliver_at = self.clock.now() + self.delay();
+
+            self.in_flight.push(InFlight {
+                deliver_at,
+                address: addr.clone(),
+                message: msg.clone(),
+            });
+        }
+
+        self.deliver_due();
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(A, Message)> {
+        self.deliver_due();
+        self.inner.receive_all_messages()
+    }
+}
+
+/// Messages waiting to be received by each [`LocalSocket`], indexed by address.
+type Mailboxes = Arc<Mutex<Vec<Vec<(usize, Message)>>>>;
+
+/// An in-memory [`NonBlockingSocket`] connecting sessions within the same process, such as
+/// several [`Apps`](`App`) in a test. Sockets are addressed by their index in
+/// [`LocalSocket::connected`].
+pub struct LocalSocket {
+    address: usize,
+    mailboxes: Mailboxes,
+}
+
+impl LocalSocket {
+    /// Creates `count` sockets which can all reach each other.
+    pub fn connected(count: usize) -> Vec<Self> {
+        let mailboxes: Mailboxes = Arc::new(Mutex::new(vec![Vec::new(); count]));
+
+        (0..count)
+            .map(|address| Self {
+                address,
+                mailboxes: mailboxes.clone(),
+            })
+            .collect()
+    }
+
+    /// The address other sockets use to reach this socket.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+}
+
+impl NonBlockingSocket<usize> for LocalSocket {
+    fn send_to(&mut self, msg: &Message, addr: &usize) {
+        if let Some(mailbox) = self.mailboxes.lock().unwrap().get_mut(*addr) {
+            mailbox.push((self.address, msg.clone()));
+        }
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(usize, Message)> {
+        std::mem::take(&mut self.mailboxes.lock().unwrap()[self.address])
+    }
+}
+
+/// Starts one [`P2PSession`](`ggrs::P2PSession`) per player, connected in memory through
+/// [`SimulatedSocket`]s wrapping [`LocalSocket`]s. Session `n` has player `n` as its local
+/// player, and reaches every other player at the address matching their handle. Each session
+/// should be inserted into its own [`App`], which are then updated in turn, advancing `clock`
+/// by one frame after every round of updates.
+///
+/// `configure` is applied to the builder of every session, and `world` provides the
+/// [`RollbackFrameRate`](`crate::RollbackFrameRate`), see [`GgrsSessionBuilder::new`]. Each
+/// socket offsets the [`seed`](`NetworkConditions::seed`) by its handle, so peers do not drop
+/// the same messages.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, start_local_p2p_sessions, NetworkConditions, SimulatedClock};
+/// # use std::time::Duration;
+/// #
+/// # type MyConfig = GgrsConfig<u8, usize>;
+/// #
+/// # fn main() -> Result<(), ggrs::GgrsError> {
+/// let conditions = NetworkConditions {
+///     latency: Duration::from_millis(50),
+///     jitter: Duration::from_millis(10),
+///     packet_loss: 0.05,
+///     seed: 7,
+/// };
+///
+/// let mut apps = [App::new(), App::new()];
+/// let clock = SimulatedClock::default();
+///
+/// let sessions =
+///     start_local_p2p_sessions::<MyConfig>(&apps[0].world, 2, conditions, &clock, |builder| {
+///         Ok(builder.with_input_delay(1))
+///     })?;
+///
+/// for (app, session) in apps.iter_mut().zip(sessions) {
+///     app.add_plugins(GgrsPlugin::<MyConfig>::default())
+///         .insert_resource(session);
+/// }
+/// # Ok(())
+/// # }
+///
+/// // called once per frame to step the simulated network
+/// fn step(apps: &mut [App], clock: &SimulatedClock) {
+///     for app in apps {
+///         app.update();
+///     }
+///
+///     clock.advance(Duration::from_secs_f64(1.0 / 60.0));
+/// }
+/// ```
+pub fn start_local_p2p_sessions<C>(
+    world: &World,
+    num_players: usize,
+    conditions: NetworkConditions,
+    clock: &SimulatedClock,
+    configure: impl Fn(GgrsSessionBuilder<C>) -> Result<GgrsSessionBuilder<C>, GgrsError>,
+) -> Result<Vec<Session<C>>, GgrsError>
+where
+    C: Config<Address = usize>,
+{
+    LocalSocket::connected(num_players)
+        .into_iter()
+        .map(|socket| {
+            let local = socket.address();
+            let mut builder = configure(GgrsSessionBuilder::new(world, num_players)?)?;
+
+            for handle in 0..num_players {
+                let player = if handle == local {
+                    PlayerType::Local
+                } else {
+                    PlayerType::Remote(handle)
+                };
+
+                builder = builder.add_player(player, handle)?;
+            }
+
+            let conditions = NetworkConditions {
+                seed: conditions.seed.wrapping_add(local as u64),
+                ..conditions
+            };
+
+            builder.start_p2p_session(SimulatedSocket::new(socket, conditions, clock.clone()))
+        })
+        .collect()
+}
@@ -0,0 +1,61 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use ggrs::PlayerHandle;
+
+/// A [`Component`] associating an [`Entity`] with the player controlling it. Combined with
+/// [`PlayerEntities`] and [`PlayerInputs::disconnected`](`crate::PlayerInputs::disconnected`),
+/// this allows gameplay to find and freeze the entities of players who have disconnected.
+///
+/// Like any other [`Component`], this must be registered for rollback if it can change
+/// during a [`Session`](`crate::Session`).
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayerHandleComponent(pub PlayerHandle);
+
+/// A [`SystemParam`] for finding the entities associated with a player through a
+/// [`PlayerHandleComponent`].
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, PlayerEntities};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// #[derive(Component)]
+/// struct Frozen;
+///
+/// fn freeze_disconnected(
+///     mut commands: Commands,
+///     inputs: Res<PlayerInputs<MyConfig>>,
+///     players: PlayerEntities,
+/// ) {
+///     for handle in inputs.disconnected() {
+///         for entity in players.for_handle(handle) {
+///             commands.entity(entity).insert(Frozen);
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_systems(GgrsSchedule, freeze_disconnected);
+/// ```
+#[derive(SystemParam)]
+pub struct PlayerEntities<'w, 's> {
+    query: Query<'w, 's, (Entity, &'static PlayerHandleComponent)>,
+}
+
+impl<'w, 's> PlayerEntities<'w, 's> {
+    /// Iterate over every [`Entity`] associated with the player with the provided `handle`.
+    ///
+    /// The order of iteration is not stable across peers. Use
+    /// [`RollbackOrdered`](`crate::RollbackOrdered`) if the order matters.
+    pub fn for_handle(&self, handle: PlayerHandle) -> impl Iterator<Item = Entity> + '_ {
+        self.query
+            .iter()
+            .filter(move |(_, player)| player.0 == handle)
+            .map(|(entity, _)| entity)
+    }
+
+    /// The player associated with the provided `entity`, if any.
+    pub fn handle_of(&self, entity: Entity) -> Option<PlayerHandle> {
+        self.query.get(entity).ok().map(|(_, player)| player.0)
+    }
+}
@@ -0,0 +1,104 @@
+use std::hash::Hash;
+
+use bevy::{prelude::*, utils::HashMap};
+use ggrs::PlayerHandle;
+
+/// A [`Resource`] associating each local [`PlayerHandle`] with the device `D` controlling it,
+/// such as a [`Gamepad`] or a keyboard scheme. Lookups work in both directions, so input systems
+/// can find the device for a handle, and device events can be routed to the right player.
+///
+/// Associations are made by the user, typically alongside starting a [`Session`](`crate::Session`)
+/// from [`LocalPlayers`](`crate::LocalPlayers`), and persist until changed or cleared.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::PlayerRegistry;
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// enum KeyboardScheme {
+///     Wasd,
+///     Arrows,
+/// }
+///
+/// let mut registry = PlayerRegistry::<KeyboardScheme>::default();
+/// registry.register(0, KeyboardScheme::Wasd);
+/// registry.register(1, KeyboardScheme::Arrows);
+///
+/// assert_eq!(registry.device_for(1), Some(&KeyboardScheme::Arrows));
+/// assert_eq!(registry.handle_for(&KeyboardScheme::Wasd), Some(0));
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct PlayerRegistry<D>
+where
+    D: Eq + Hash + Clone,
+{
+    devices: HashMap<PlayerHandle, D>,
+    handles: HashMap<D, PlayerHandle>,
+}
+
+impl<D> Default for PlayerRegistry<D>
+where
+    D: Eq + Hash + Clone,
+{
+    fn default() -> Self {
+        Self {
+            devices: default(),
+            handles: default(),
+        }
+    }
+}
+
+impl<D> PlayerRegistry<D>
+where
+    D: Eq + Hash + Clone,
+{
+    /// Associates `handle` with `device`, replacing any existing association of either.
+    pub fn register(&mut self, handle: PlayerHandle, device: D) -> &mut Self {
+        self.unregister(handle);
+
+        if let Some(previous) = self.handles.remove(&device) {
+            self.devices.remove(&previous);
+        }
+
+        self.devices.insert(handle, device.clone());
+        self.handles.insert(device, handle);
+
+        self
+    }
+
+    /// Removes the association for `handle`, returning the device it was associated with.
+    pub fn unregister(&mut self, handle: PlayerHandle) -> Option<D> {
+        let device = self.devices.remove(&handle)?;
+        self.handles.remove(&device);
+        Some(device)
+    }
+
+    /// Removes all associations.
+    pub fn clear(&mut self) {
+        self.devices.clear();
+        self.handles.clear();
+    }
+
+    /// Get the device controlling the player with the provided `handle`.
+    pub fn device_for(&self, handle: PlayerHandle) -> Option<&D> {
+        self.devices.get(&handle)
+    }
+
+    /// Get the handle of the player controlled by the provided `device`.
+    pub fn handle_for(&self, device: &D) -> Option<PlayerHandle> {
+        self.handles.get(device).copied()
+    }
+
+    /// Iterate over all associations, in order of [`PlayerHandle`].
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerHandle, &D)> + '_ {
+        let mut associations = self
+            .devices
+            .iter()
+            .map(|(&handle, device)| (handle, device))
+            .collect::<Vec<_>>();
+
+        associations.sort_by_key(|&(handle, _)| handle);
+
+        associations.into_iter()
+    }
+}
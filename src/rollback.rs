@@ -1,13 +1,25 @@
 use bevy::utils::HashMap;
 use bevy::{
-    ecs::system::{EntityCommand, EntityCommands},
+    ecs::system::{EntityCommand, EntityCommands, SystemParam},
     prelude::*,
 };
 
+use crate::{schedule_systems::report_error, InRollbackSchedule, RollbackFrameCount};
+
 /// This component flags an entity as being included in the rollback save/load schedule with GGRS.
 ///
 /// You must use the [`AddRollbackCommand`] when spawning an entity to add this component. Alternatively,
-/// you can use the `add_rollback()` extension method provided by [`AddRollbackCommandExtension`].
+/// you can use the `add_rollback()` extension method provided by [`AddRollbackCommandExtension`], or
+/// `spawn_rollback()` provided by [`SpawnRollbackCommandExtension`].
+///
+/// # Spawning during a session
+///
+/// Once a session is running, rollback entities should only be spawned from within the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`). Commands issued there are applied before the frame is
+/// saved, so the new entity is part of that frame's snapshot, and rolling back to any earlier
+/// frame removes it again. An entity given a [`Rollback`] anywhere else is not captured until
+/// the next save, and will be despawned by any rollback to a frame before it, so this is
+/// reported as an error, which panics with [`StrictErrors`](`crate::StrictErrors`).
 #[derive(Component, Hash, PartialEq, Eq, Clone, Copy, Debug)]
 pub struct Rollback(Entity);
 
@@ -25,6 +37,19 @@ impl EntityCommand for AddRollbackCommand {
     fn apply(self, id: Entity, world: &mut World) {
         let rollback = Rollback::new(id);
 
+        let spawned_mid_session = world
+            .get_resource::<RollbackFrameCount>()
+            .is_some_and(|frame| frame.0 > 0);
+
+        if spawned_mid_session && !world.contains_resource::<InRollbackSchedule>() {
+            report_error(
+                world,
+                format_args!(
+                    "{id:?} was added to rollback outside of the GgrsSchedule during a session, and will be removed by rollbacks to frames before it was spawned"
+                ),
+            );
+        }
+
         world.entity_mut(id).insert(rollback);
 
         world
@@ -36,6 +61,9 @@ impl EntityCommand for AddRollbackCommand {
 mod private {
     /// Private seal to ensure [`AddRollbackCommandExtension`](`super::AddRollbackCommandExtension`) cannot be implemented by crate consumers.
     pub trait AddRollbackCommandExtensionSeal {}
+
+    /// Private seal to ensure [`SpawnRollbackCommandExtension`](`super::SpawnRollbackCommandExtension`) cannot be implemented by crate consumers.
+    pub trait SpawnRollbackCommandExtensionSeal {}
 }
 
 /// Extension trait for [`EntityCommands`] which adds the `add_rollback()` method.
@@ -53,7 +81,32 @@ impl<'w, 's, 'a> AddRollbackCommandExtension for EntityCommands<'w, 's, 'a> {
     }
 }
 
+/// Extension trait for [`Commands`] which adds the `spawn_rollback()` method.
+pub trait SpawnRollbackCommandExtension<'w, 's>:
+    private::SpawnRollbackCommandExtensionSeal
+{
+    /// Spawns a new [`Entity`] with the provided [`Bundle`] and an automatically generated
+    /// [`Rollback`] component. See [`Rollback`] for when this should be called.
+    fn spawn_rollback<'a>(&'a mut self, bundle: impl Bundle) -> EntityCommands<'w, 's, 'a>;
+}
+
+impl<'w, 's> private::SpawnRollbackCommandExtensionSeal for Commands<'w, 's> {}
+
+impl<'w, 's> SpawnRollbackCommandExtension<'w, 's> for Commands<'w, 's> {
+    fn spawn_rollback<'a>(&'a mut self, bundle: impl Bundle) -> EntityCommands<'w, 's, 'a> {
+        let mut entity = self.spawn(bundle);
+        entity.add(AddRollbackCommand);
+        entity
+    }
+}
+
 /// A [`Resource`] which provides methods for stable ordering of [`Rollback`] flags.
+///
+/// This is itself rolled back by the [`GgrsPlugin`](`crate::GgrsPlugin`), so entities spawned
+/// in a frame which is later re-simulated receive the same order each time.
+///
+/// Checksums and other deterministic processing should use this order rather than [`Entity`]
+/// ids, which depend on how freed slots happen to be reused when spawns and despawns interleave.
 #[derive(Resource, Default, Clone)]
 pub struct RollbackOrdered {
     order: HashMap<Rollback, usize>,
@@ -62,7 +115,7 @@ pub struct RollbackOrdered {
 
 impl RollbackOrdered {
     /// Register a new [`Rollback`] for explicit ordering.
-    fn push(&mut self, rollback: Rollback) -> &mut Self {
+    pub(crate) fn push(&mut self, rollback: Rollback) -> &mut Self {
         self.sorted.push(rollback);
         self.order.insert(rollback, self.sorted.len() - 1);
 
@@ -82,6 +135,47 @@ impl RollbackOrdered {
             .expect("Rollback requested was not created using AddRollbackCommand!")
     }
 
+    /// The [`Rollback`] with the provided [`order`](`RollbackOrdered::order`), if one was registered.
+    pub fn get(&self, order: usize) -> Option<Rollback> {
+        self.sorted.get(order).copied()
+    }
+
+    /// Collects `items` into a [`Vec`] sorted by the order of the [`Rollback`] returned by
+    /// `rollback` for each item. Unlike query iteration order, this order is identical on every
+    /// peer, so systems which depend on the order entities are processed in remain deterministic.
+    /// `rollback` is called once per item.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_ggrs::{prelude::*, RollbackOrdered};
+    /// #
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn resolve_in_order(mut query: Query<(&Rollback, &mut Health)>, order: Res<RollbackOrdered>) {
+    ///     for (_, mut health) in order.sorted(query.iter_mut(), |(rollback, _)| **rollback) {
+    ///         health.0 = health.0.saturating_sub(1);
+    ///     }
+    /// }
+    /// # let mut app = App::new();
+    /// # app.add_systems(GgrsSchedule, resolve_in_order);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any [`Rollback`] was not created using [`AddRollbackCommand`].
+    pub fn sorted<T>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+        rollback: impl Fn(&T) -> Rollback,
+    ) -> Vec<T> {
+        let mut items = items.into_iter().collect::<Vec<_>>();
+        // each order is a hash lookup, so only look it up once per item
+        items.sort_by_cached_key(|item| self.order(rollback(item)));
+        items
+    }
+
     /// Get the number of registered [`Rollback`] entities.
     pub fn len(&self) -> usize {
         self.order.len()
@@ -92,3 +186,59 @@ impl RollbackOrdered {
         self.order.is_empty()
     }
 }
+
+/// A [`SystemParam`] for converting between [`Entity`] and an id which identifies the same
+/// [`Rollback`] entity on every peer, based on [`RollbackOrdered`].
+///
+/// Unlike [`Entity`], these ids can be sent to peers outside of GGRS, such as in chat messages or
+/// pings referencing an entity. Ids are derived from the order entities were given a [`Rollback`],
+/// so they only agree across peers when rollback entities are spawned deterministically, such as
+/// from the [`GgrsSchedule`](`crate::GgrsSchedule`). Ids are never reused, and survive rollbacks
+/// which respawn the entity.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::RollbackIds;
+/// #
+/// #[derive(Event)]
+/// struct PingReceived {
+///     target: u32,
+/// }
+///
+/// fn highlight_pings(mut pings: EventReader<PingReceived>, ids: RollbackIds) {
+///     for ping in pings.read() {
+///         if let Some(entity) = ids.to_entity(ping.target) {
+///             info!("Pinged {entity:?}");
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_event::<PingReceived>().add_systems(Update, highlight_pings);
+/// ```
+#[derive(SystemParam)]
+pub struct RollbackIds<'w, 's> {
+    order: Res<'w, RollbackOrdered>,
+    query: Query<'w, 's, (Entity, &'static Rollback)>,
+}
+
+impl<'w, 's> RollbackIds<'w, 's> {
+    /// The id of the provided `entity`, or [`None`] if it is not a [`Rollback`] entity.
+    pub fn to_id(&self, entity: Entity) -> Option<u32> {
+        let (_, &rollback) = self.query.get(entity).ok()?;
+        let order = self.order.order.get(&rollback)?;
+
+        u32::try_from(*order).ok()
+    }
+
+    /// The [`Entity`] currently associated with the provided `id`, or [`None`] if it has since
+    /// been despawned, or has not been spawned on this peer yet.
+    pub fn to_entity(&self, id: u32) -> Option<Entity> {
+        let rollback = self.order.get(id as usize)?;
+
+        self.query
+            .iter()
+            .find(|&(_, &candidate)| candidate == rollback)
+            .map(|(entity, _)| entity)
+    }
+}
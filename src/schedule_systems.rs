@@ -1,30 +1,33 @@
 use crate::{
-    AdvanceWorld, Checksum, ConfirmedFrameCount, FixedTimestepData, LoadWorld, LocalInputs,
-    LocalPlayers, MaxPredictionWindow, PlayerInputs, ReadInputs, RollbackFrameCount,
-    RollbackFrameRate, SaveWorld, Session,
+    apply_ai_inputs, apply_disconnect_input_policy, AdvanceWorld, AiPlayers, Checksum,
+    ChecksumDiagnostics, ComponentGroups, ConfirmedFrameCount, DesyncDump, DesyncDumpHistory,
+    ErasedSnapshots, FixedTimestepData, FrameOverstep, GgrsComponentSnapshots,
+    GgrsConfirmedSchedule, GgrsInitialized, GgrsInputQuality, GgrsPlayers, GgrsPredictionStalled,
+    GgrsRequestObserver, GgrsResimStats, GgrsSessionStateChanged, GgrsSetup,
+    GgrsSpectatorFellBehind, GgrsSpectatorStats, GgrsStateSaver, GgrsTime, InputSampling,
+    LoadWorld, LocalInputs, LocalMode, LocalPlayers, MaxFrameDelta, MaxPredictionWindow,
+    MissingSnapshotPolicy, ParallelInput, PlayerInputHistory, PlayerInputReader, PlayerInputs,
+    PredictionStalled, ReadInputs, RedundantVerification, RollbackFrameCount, RollbackFrameRate,
+    RollbackStats, RunSlowFactor, SaveWorld, Session, SessionInitialized, SnapshotKeyframeInterval,
+    SnapshotMemoryUsage, SnapshotStorages, SpectatorCatchup, SpectatorMaxLag, SpectatorStats,
+    StrictErrors, TimeDilation, DEFAULT_FPS,
+};
+use bevy::{
+    ecs::component::Tick,
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPool},
+    utils::{Duration, HashMap, Instant},
 };
-use bevy::{prelude::*, utils::Duration};
 use ggrs::{
-    Config, GgrsError, GgrsRequest, P2PSession, SessionState, SpectatorSession, SyncTestSession,
+    Config, GgrsError, GgrsRequest, InputStatus, P2PSession, PlayerHandle, SessionState,
+    SpectatorSession, SyncTestSession,
 };
+use std::{any::TypeId, collections::BTreeMap};
 
 pub(crate) fn run_ggrs_schedules<T: Config>(world: &mut World) {
-    let framerate: usize = **world.get_resource_or_insert_with::<RollbackFrameRate>(default);
-
-    let mut time_data = world
-        .remove_resource::<FixedTimestepData>()
-        .expect("failed to extract GGRS FixedTimeStepData");
-
-    let delta = world
-        .get_resource::<Time>()
-        .expect("Time resource not found, did you remove it?")
-        .delta();
-
-    let mut fps_delta = 1. / framerate as f64;
-    if time_data.run_slow {
-        fps_delta *= 1.1;
+    if let Some(mut resim_stats) = world.get_resource_mut::<GgrsResimStats>() {
+        resim_stats.start_update();
     }
-    time_data.accumulator = time_data.accumulator.saturating_add(delta);
 
     // no matter what, poll remotes and send responses
     if let Some(mut session) = world.get_resource_mut::<Session<T>>() {
@@ -39,12 +42,60 @@ pub(crate) fn run_ggrs_schedules<T: Config>(world: &mut World) {
         }
     }
 
+    pick_up_session::<T>(world);
+
+    let tick_duration = world
+        .get_resource_or_insert_with::<RollbackFrameRate>(default)
+        .tick_duration();
+
+    let input_sampling = world
+        .get_resource::<InputSampling>()
+        .copied()
+        .unwrap_or_default();
+
+    let mut time_data = world
+        .remove_resource::<FixedTimestepData>()
+        .expect("failed to extract GGRS FixedTimeStepData");
+
+    let mut delta = world
+        .get_resource::<Time>()
+        .expect("Time resource not found, did you remove it?")
+        .delta();
+
+    if let Some(max_delta) = world.get_resource::<MaxFrameDelta>() {
+        delta = delta.min(max_delta.0);
+    }
+
+    let time_dilation = world.get_resource::<TimeDilation>().copied();
+    let run_slow_factor = world
+        .get_resource::<RunSlowFactor>()
+        .copied()
+        .unwrap_or_default();
+
+    // recomputed after every step, since each P2P step updates how far ahead we are
+    let frame_duration = |time_data: &FixedTimestepData| {
+        let mut fps_delta = tick_duration.as_secs_f64();
+        if let Some(time_dilation) = time_dilation {
+            fps_delta *= time_dilation.factor(time_data.frames_ahead);
+        } else if time_data.run_slow {
+            fps_delta *= *run_slow_factor;
+        }
+        fps_delta
+    };
+    let run_locally =
+        world.contains_resource::<LocalMode>() && !world.contains_resource::<Session<T>>();
+
+    // local mode bypasses the accumulator entirely
+    if !run_locally {
+        time_data.accumulator = time_data.accumulator.saturating_add(delta);
+    }
+
     // if we accumulated enough time, do steps
-    while time_data.accumulator.as_secs_f64() > fps_delta {
+    while time_data.accumulator.as_secs_f64() > frame_duration(&time_data) {
         // decrease accumulator
         time_data.accumulator = time_data
             .accumulator
-            .saturating_sub(Duration::from_secs_f64(fps_delta));
+            .saturating_sub(Duration::from_secs_f64(frame_duration(&time_data)));
 
         // depending on the session type, doing a single update looks a bit different
         let session = world.remove_resource::<Session<T>>();
@@ -52,35 +103,375 @@ pub(crate) fn run_ggrs_schedules<T: Config>(world: &mut World) {
             Some(Session::SyncTest(s)) => run_synctest::<T>(world, s),
             Some(Session::P2P(session)) => {
                 // if we are ahead, run slow
-                time_data.run_slow = session.frames_ahead() > 0;
+                time_data.frames_ahead = session.frames_ahead();
+                time_data.run_slow = time_data.frames_ahead > 0;
 
                 run_p2p(world, session);
             }
             Some(Session::Spectator(s)) => run_spectator(world, s),
             _ => {
-                // No session has been started yet, reset time data and snapshots
-                time_data.accumulator = Duration::ZERO;
-                time_data.run_slow = false;
-                world.insert_resource(LocalPlayers::default());
-                world.insert_resource(RollbackFrameCount(0));
-                world.insert_resource(ConfirmedFrameCount(-1));
-                world.insert_resource(MaxPredictionWindow(8));
+                // No session has been started yet, reset time data and session state
+                time_data = FixedTimestepData::default();
+                reset_session_state(world);
             }
         }
     }
 
+    if run_locally {
+        run_local::<T>(world, delta);
+    }
+
+    let overstep = (time_data.accumulator.as_secs_f64() / frame_duration(&time_data)).clamp(0., 1.);
+    world.insert_resource(FrameOverstep(overstep as f32));
+
+    if input_sampling == InputSampling::PerTick {
+        // inputs sampled this tick must not leak into the next one
+        world.remove_resource::<LocalInputs<T>>();
+    }
+
     world.insert_resource(time_data);
+    world.insert_resource(SessionTick(world.read_change_tick()));
+}
+
+/// Picks up a newly inserted [`Session`], or one inserted in place of the previous one, running
+/// the [`GgrsSetup`] schedule and sending [`GgrsInitialized`] before its first frame. Also keeps
+/// [`GgrsPlayers`], [`SessionInitialized`] and [`GgrsSessionStateChanged`] up to date.
+fn pick_up_session<T: Config>(world: &mut World) {
+    let mut last_session_state = world
+        .get_resource_or_insert_with::<LastSessionState>(default)
+        .0;
+
+    // a session inserted in place of the previous one starts over, as if it was shut down first
+    if last_session_state.is_some() && session_replaced::<T>(world) {
+        debug!("session was replaced, resetting session state");
+        reset_after_session::<T>(world);
+        last_session_state = None;
+    }
+
+    let session_state = world
+        .get_resource::<Session<T>>()
+        .map(|session| match session {
+            Session::P2P(session) => session.current_state(),
+            Session::Spectator(session) => session.current_state(),
+            Session::SyncTest(_) => SessionState::Running,
+        });
+
+    // frames advanced in local mode must not carry over into the session
+    if world.contains_resource::<LocalMode>()
+        && last_session_state.is_none()
+        && session_state.is_some()
+    {
+        reset_session_state(world);
+    }
+
+    update_players::<T>(world);
+
+    let max_prediction = world
+        .get_resource::<Session<T>>()
+        .map(|session| match session {
+            Session::P2P(session) => session.max_prediction(),
+            Session::SyncTest(session) => session.max_prediction(),
+            Session::Spectator(_) => 0,
+        });
+
+    if let (None, Some(max_prediction)) = (last_session_state, max_prediction) {
+        world.insert_resource(MaxPredictionWindow(max_prediction));
+
+        {
+            let _span = bevy::utils::tracing::info_span!("schedule", name = "GgrsSetup").entered();
+            debug!("running setup schedule");
+            world.run_schedule(GgrsSetup);
+        }
+
+        world.send_event(GgrsInitialized { max_prediction });
+    }
+
+    world
+        .get_resource_or_insert_with::<SessionInitialized>(default)
+        .set_if_neq(SessionInitialized(max_prediction.is_some()));
+
+    if session_state != last_session_state {
+        world.send_event(GgrsSessionStateChanged {
+            from: last_session_state,
+            to: session_state,
+        });
+        world.insert_resource(LastSessionState(session_state));
+    }
+}
+
+/// Resets all state belonging to a [`Session`], so the next one starts from the first frame.
+fn reset_session_state(world: &mut World) {
+    world.insert_resource(LocalPlayers::default());
+    world.insert_resource(GgrsSpectatorStats::default());
+    world.insert_resource(PredictionStalled::default());
+    world.insert_resource(SessionInitialized::default());
+    world.insert_resource(RollbackFrameCount(0));
+    world.insert_resource(ConfirmedFrameCount(-1));
+    world.insert_resource(ConfirmedScheduleFrame::default());
+    world.insert_resource(Time::new_with(GgrsTime));
+
+    if let Some(mut quality) = world.get_resource_mut::<GgrsInputQuality>() {
+        *quality = GgrsInputQuality::new(quality.window());
+    }
+
+    if let Some(mut resim_stats) = world.get_resource_mut::<GgrsResimStats>() {
+        resim_stats.reset_session();
+    }
+
+    if let Some(mut memory_usage) = world.get_resource_mut::<SnapshotMemoryUsage>() {
+        memory_usage.reset_session();
+    }
+
+    if let Some(mut history) = world.get_resource_mut::<DesyncDumpHistory>() {
+        history.clear();
+    }
+    if let Some(mut diagnostics) = world.get_resource_mut::<ChecksumDiagnostics>() {
+        diagnostics.clear();
+    }
+}
+
+/// The [`SessionState`] of the [`Session`] when it was last picked up, or `None` if there was none.
+#[derive(Resource, Default, Clone, Copy)]
+struct LastSessionState(Option<SessionState>);
+
+/// The [`World::read_change_tick`] after [`run_ggrs_schedules`] last ran, so a [`Session`] inserted
+/// since, in place of the one it was stepping, can be detected.
+#[derive(Resource, Clone, Copy)]
+struct SessionTick(Tick);
+
+/// Returns `true` if the [`Session`] was inserted since the [`SessionTick`].
+fn session_replaced<T: Config>(world: &World) -> bool {
+    let Some(&SessionTick(last_run)) = world.get_resource::<SessionTick>() else {
+        return false;
+    };
+
+    world
+        .get_resource_change_ticks::<Session<T>>()
+        .is_some_and(|ticks| ticks.is_added(last_run, world.read_change_tick()))
+}
+
+pub(crate) fn shutdown_session<T: Config>(world: &mut World) -> Option<Session<T>> {
+    let session = world.remove_resource::<Session<T>>();
+
+    reset_after_session::<T>(world);
+
+    session
+}
+
+/// Resets everything the previous [`Session`] left behind, including its snapshots.
+fn reset_after_session<T: Config>(world: &mut World) {
+    // the next session must be reported as newly initialized
+    world.insert_resource(LastSessionState::default());
+    world.remove_resource::<SessionTick>();
+    world.insert_resource(FixedTimestepData::default());
+    world.insert_resource(RecordedInputs::<T>::default());
+    world.insert_resource(GgrsPlayers::default());
+    world.remove_resource::<LocalInputs<T>>();
+    reset_session_state(world);
+    SnapshotStorages::clear_all(world);
+
+    if let Some(mut history) = world.get_resource_mut::<PlayerInputHistory<T>>() {
+        *history = PlayerInputHistory::new(history.capacity());
+    }
+}
+
+/// Publishes the [`GgrsPlayers`] of the current [`Session`], if any.
+fn update_players<T: Config>(world: &mut World) {
+    let players = world
+        .get_resource::<Session<T>>()
+        .map(|session| match session {
+            Session::P2P(session) => GgrsPlayers {
+                num_players: session.num_players(),
+                local: session.local_player_handles(),
+            },
+            Session::Spectator(session) => GgrsPlayers {
+                num_players: session.num_players(),
+                local: Vec::new(),
+            },
+            Session::SyncTest(session) => GgrsPlayers {
+                num_players: session.num_players(),
+                local: (0..session.num_players()).collect(),
+            },
+        })
+        .or_else(|| {
+            world.get_resource::<LocalMode>().map(|local| GgrsPlayers {
+                num_players: local.num_players(),
+                local: (0..local.num_players()).collect(),
+            })
+        })
+        .unwrap_or_default();
+
+    if let Some(mut current) = world.get_resource_mut::<GgrsPlayers>() {
+        current.set_if_neq(players);
+    } else {
+        world.insert_resource(players);
+    }
+}
+
+/// Advances the [`Session`] exactly `frames` times, ignoring the accumulated [`Time`].
+pub(crate) fn run_n_frames<T: Config>(world: &mut World, frames: usize) -> usize {
+    pick_up_session::<T>(world);
+
+    let start = world.resource::<RollbackFrameCount>().0;
+
+    for _ in 0..frames {
+        match world.remove_resource::<Session<T>>() {
+            Some(Session::SyncTest(session)) => run_synctest(world, session),
+            Some(Session::P2P(mut session)) => {
+                session.poll_remote_clients();
+                run_p2p(world, session);
+            }
+            Some(Session::Spectator(mut session)) => {
+                session.poll_remote_clients();
+                run_spectator(world, session);
+            }
+            None => panic!("No GGRS Session found. Insert one before running frames."),
+        }
+    }
+
+    if world
+        .get_resource::<InputSampling>()
+        .copied()
+        .unwrap_or_default()
+        == InputSampling::PerTick
+    {
+        world.remove_resource::<LocalInputs<T>>();
+    }
+
+    // the session was re-inserted while stepping, which is not a replacement
+    world.insert_resource(SessionTick(world.read_change_tick()));
+
+    (world.resource::<RollbackFrameCount>().0 - start).max(0) as usize
+}
+
+/// Runs the [`ReadInputs`] schedule and collects the resulting [`LocalInputs`], respecting [`InputSampling`].
+/// Inputs are returned in order of [`PlayerHandle`], so they are always submitted in the same order.
+fn sample_local_inputs<C: Config>(world: &mut World) -> Vec<(PlayerHandle, C::Input)> {
+    let input_sampling = world
+        .get_resource::<InputSampling>()
+        .copied()
+        .unwrap_or_default();
+
+    let cached = match input_sampling {
+        InputSampling::PerTick => world
+            .get_resource::<LocalInputs<C>>()
+            .map(|local_inputs| local_inputs.0.clone()),
+        InputSampling::PerStep => None,
+    };
+
+    let mut local_inputs = cached.unwrap_or_else(|| {
+        let _span = bevy::utils::tracing::info_span!("schedule", name = "ReadInputs").entered();
+
+        if let Some(reader) = world
+            .get_resource::<PlayerInputReader<C>>()
+            .map(|reader| reader.0)
+        {
+            let local_inputs = read_player_inputs::<C>(world, reader);
+            world.insert_resource(LocalInputs::<C>(local_inputs));
+        }
+
+        world.run_schedule(ReadInputs);
+
+        let local_inputs = match input_sampling {
+            InputSampling::PerStep => world
+                .remove_resource::<LocalInputs<C>>()
+                .map(|inputs| inputs.0),
+            InputSampling::PerTick => world
+                .get_resource::<LocalInputs<C>>()
+                .map(|inputs| inputs.0.clone()),
+        };
+
+        // local AI players do not need inputs from the ReadInputs schedule
+        local_inputs
+            .or_else(|| only_local_ai_players::<C>(world).then(HashMap::default))
+            .expect(
+                "No local player inputs found. Did you insert systems into the ReadInputs schedule?",
+            )
+    });
+
+    // AI inputs are produced for every step, as they depend on the rollback state
+    if let Some(ai_players) = world.get_resource::<AiPlayers<C>>() {
+        for &handle in &world.resource::<LocalPlayers>().0 {
+            if ai_players.is_ai(handle) {
+                local_inputs.insert(handle, ai_players.input(handle, world));
+            }
+        }
+    }
+
+    let mut local_inputs = local_inputs.into_iter().collect::<Vec<_>>();
+    local_inputs.sort_unstable_by_key(|&(handle, _)| handle);
+    local_inputs
+}
+
+/// Returns `true` if every [`LocalPlayers`] handle is controlled by [`AiPlayers`].
+fn only_local_ai_players<C: Config>(world: &World) -> bool {
+    let Some(ai_players) = world.get_resource::<AiPlayers<C>>() else {
+        return false;
+    };
+
+    world
+        .resource::<LocalPlayers>()
+        .0
+        .iter()
+        .all(|&handle| ai_players.is_ai(handle))
+}
+
+/// Calls the [`PlayerInputReader`] for every [`LocalPlayers`] handle, in parallel if [`ParallelInput`] is enabled.
+fn read_player_inputs<C: Config>(
+    world: &World,
+    reader: fn(PlayerHandle, &World) -> C::Input,
+) -> HashMap<PlayerHandle, C::Input> {
+    let handles = world
+        .get_resource::<LocalPlayers>()
+        .map(|players| players.0.clone())
+        .unwrap_or_default();
+
+    let parallel = world
+        .get_resource::<ParallelInput>()
+        .is_some_and(|parallel| parallel.0);
+
+    if !parallel || handles.len() < 2 {
+        return handles
+            .into_iter()
+            .map(|handle| (handle, reader(handle, world)))
+            .collect();
+    }
+
+    ComputeTaskPool::get_or_init(TaskPool::default)
+        .scope(|scope| {
+            for &handle in &handles {
+                scope.spawn(async move { (handle, reader(handle, world)) });
+            }
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Advances a single frame covering `delta` without a [`Session`], see [`LocalMode`].
+fn run_local<C: Config>(world: &mut World, delta: Duration) {
+    let _span = bevy::utils::tracing::info_span!("ggrs", name = "LocalMode").entered();
+
+    let local_players = world.resource::<GgrsPlayers>().local.clone();
+    world.insert_resource(LocalPlayers(local_players));
+
+    let inputs = sample_local_inputs::<C>(world)
+        .into_iter()
+        .map(|(_, input)| (input, InputStatus::Confirmed))
+        .collect();
+
+    world.resource_mut::<LocalMode>().frame_delta = Some(delta);
+    handle_requests(vec![GgrsRequest::<C>::AdvanceFrame { inputs }], world);
+    world.resource_mut::<LocalMode>().frame_delta = None;
 }
 
 pub(crate) fn run_synctest<C: Config>(world: &mut World, mut sess: SyncTestSession<C>) {
+    let _span = bevy::utils::tracing::info_span!("ggrs", name = "SyncTestSession").entered();
+
     world.insert_resource(LocalPlayers((0..sess.num_players()).collect()));
 
     // read local player inputs and register them in the session
-    world.run_schedule(ReadInputs);
-    let local_inputs = world.remove_resource::<LocalInputs<C>>().expect(
-        "No local player inputs found. Did you insert systems into the ReadInputs schedule?",
-    );
-    for (handle, input) in local_inputs.0 {
+    let local_inputs = sample_local_inputs::<C>(world);
+    for (handle, input) in local_inputs {
         sess.add_local_input(handle, input)
             .expect("All handles in local_handles should be valid");
     }
@@ -91,41 +482,128 @@ pub(crate) fn run_synctest<C: Config>(world: &mut World, mut sess: SyncTestSessi
 
     match requests {
         Ok(requests) => handle_requests(requests, world),
-        Err(e) => warn!("{e}"),
+        Err(e @ GgrsError::MismatchedChecksum { .. }) => {
+            let mut message = e.to_string();
+
+            if let Some(diagnostics) = world.get_resource::<ChecksumDiagnostics>() {
+                for difference in diagnostics.resimulation_differences() {
+                    message.push_str(&format!(
+                        "\n{} on {:?} differed after resimulation",
+                        difference.type_name, difference.rollback
+                    ));
+                }
+            }
+
+            DesyncDump::write_if_enabled(world, &message);
+            report_error(world, message);
+        }
+        Err(e) => report_error(world, e),
+    }
+}
+
+/// Logs an error, such as one returned by GGRS, or panics if [`StrictErrors`] are enabled.
+pub(crate) fn report_error(world: &World, error: impl std::fmt::Display) {
+    let strict = world
+        .get_resource::<StrictErrors>()
+        .is_some_and(|strict| strict.0);
+
+    if strict {
+        panic!("GGRS error: {error}");
+    } else {
+        warn!("{error}");
     }
 }
 
 pub(crate) fn run_spectator<T: Config>(world: &mut World, mut sess: SpectatorSession<T>) {
-    // if session is ready, try to advance the frame
-    let running = sess.current_state() == SessionState::Running;
-    let requests = running.then(|| sess.advance_frame());
+    let _span = bevy::utils::tracing::info_span!("ggrs", name = "SpectatorSession").entered();
 
-    world.insert_resource(Session::Spectator(sess));
+    let max_frames = world
+        .get_resource::<SpectatorCatchup>()
+        .map_or(1, |catchup| catchup.0.max(1));
 
-    match requests {
-        Some(Ok(requests)) => handle_requests(requests, world),
-        Some(Err(GgrsError::PredictionThreshold)) => {
-            info!("P2PSpectatorSession: Waiting for input from host.")
+    for step in 1..=max_frames {
+        // if session is ready, try to advance the frame
+        let running = sess.current_state() == SessionState::Running;
+        let requests = running.then(|| sess.advance_frame());
+        let frames_behind = sess.frames_behind_host();
+
+        world.insert_resource(Session::Spectator(sess));
+
+        match requests {
+            Some(Ok(requests)) => {
+                set_prediction_stalled(world, false);
+                handle_requests(requests, world);
+            }
+            Some(Err(GgrsError::PredictionThreshold)) => {
+                info!("P2PSpectatorSession: Waiting for input from host.");
+                set_prediction_stalled(world, true);
+                return;
+            }
+            Some(Err(e)) => {
+                report_error(world, e);
+                return;
+            }
+            None => return,
+        };
+
+        // keep advancing while behind the host, up to the catchup limit
+        if frames_behind == 0 || step == max_frames {
+            if world
+                .get_resource::<SpectatorMaxLag>()
+                .is_some_and(|max_lag| frames_behind > max_lag.0)
+            {
+                world.send_event(GgrsSpectatorFellBehind { frames_behind });
+            }
+
+            return;
         }
-        Some(Err(e)) => warn!("{e}"),
-        None => {}
-    };
+
+        match world.remove_resource::<Session<T>>() {
+            Some(Session::Spectator(next)) => sess = next,
+            Some(other) => {
+                world.insert_resource(other);
+                return;
+            }
+            None => return,
+        }
+    }
 }
 
 pub(crate) fn run_p2p<C: Config>(world: &mut World, mut sess: P2PSession<C>) {
-    world.insert_resource(LocalPlayers(sess.local_player_handles()));
+    let _span = bevy::utils::tracing::info_span!("ggrs", name = "P2PSession").entered();
+
+    let local_players = sess.local_player_handles();
+    let has_local_players = !local_players.is_empty();
+    world.insert_resource(LocalPlayers(local_players));
+
+    let mut spectators = sess
+        .spectator_handles()
+        .into_iter()
+        .filter_map(|handle| {
+            let stats = sess.network_stats(handle).ok()?;
+
+            Some(SpectatorStats {
+                handle,
+                ping: stats.ping,
+                frames_behind: stats.remote_frames_behind,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    spectators.sort_by_key(|spectator| spectator.handle);
+
+    world
+        .resource_mut::<GgrsSpectatorStats>()
+        .set_if_neq(GgrsSpectatorStats { spectators });
 
     let running = sess.current_state() == SessionState::Running;
 
-    if running {
+    // observers without local players advance purely on remote inputs, like a spectator
+    if running && has_local_players {
         // get local player inputs
-        world.run_schedule(ReadInputs);
+        let local_inputs = sample_local_inputs::<C>(world);
 
-        let local_inputs = world.remove_resource::<LocalInputs<C>>().expect(
-            "No local player inputs found. Did you insert systems into the ReadInputs schedule?",
-        );
-
-        for (handle, input) in local_inputs.0 {
+        for (handle, input) in local_inputs {
             sess.add_local_input(handle, input)
                 .expect("All handles in local_inputs should be valid");
         }
@@ -136,17 +614,41 @@ pub(crate) fn run_p2p<C: Config>(world: &mut World, mut sess: P2PSession<C>) {
     world.insert_resource(Session::P2P(sess));
 
     match requests {
-        Some(Ok(requests)) => handle_requests(requests, world),
+        Some(Ok(requests)) => {
+            set_prediction_stalled(world, false);
+            handle_requests(requests, world);
+        }
         Some(Err(GgrsError::PredictionThreshold)) => {
-            info!("Skipping a frame: PredictionThreshold.")
+            info!("Skipping a frame: PredictionThreshold.");
+            set_prediction_stalled(world, true);
         }
-        Some(Err(e)) => warn!("{e}"),
+        Some(Err(e)) => report_error(world, e),
         None => {}
     }
 }
 
+/// Updates [`PredictionStalled`], sending a [`GgrsPredictionStalled`] event for each skipped frame.
+fn set_prediction_stalled(world: &mut World, stalled: bool) {
+    world
+        .get_resource_or_insert_with::<PredictionStalled>(default)
+        .set_if_neq(PredictionStalled(stalled));
+
+    if stalled {
+        let frame = world.resource::<RollbackFrameCount>().0;
+        world.send_event(GgrsPredictionStalled { frame });
+    }
+}
+
 pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &mut World) {
-    let _span = bevy::utils::tracing::info_span!("ggrs", name = "HandleRequests").entered();
+    let _span = bevy::utils::tracing::info_span!(
+        "ggrs",
+        name = "HandleRequests",
+        requests = requests.len(),
+        frame = world
+            .get_resource::<RollbackFrameCount>()
+            .map(|frame| frame.0)
+    )
+    .entered();
 
     // Extracting schedules before processing requests to avoid repeated remove/insert operations
     let mut schedules = world.resource_mut::<Schedules>();
@@ -163,8 +665,34 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
         panic!("Could not extract AdvanceWorld Schedule!");
     };
 
+    let keyframe_interval = world
+        .get_resource::<SnapshotKeyframeInterval>()
+        .map(|interval| interval.0 as i32)
+        .unwrap_or(1);
+
+    let observer = world
+        .get_resource::<GgrsRequestObserver<T>>()
+        .map(|observer| observer.0);
+
+    let state_saver = world
+        .get_resource::<GgrsStateSaver<T>>()
+        .map(|saver| saver.0);
+
+    let redundant_verification = world
+        .get_resource::<RedundantVerification>()
+        .is_some_and(|verification| verification.0);
+
+    let missing_snapshot = world
+        .get_resource::<MissingSnapshotPolicy>()
+        .copied()
+        .unwrap_or_default();
+
     // Run Schedules as Required
-    for request in requests {
+    'requests: for request in requests {
+        if let Some(observer) = observer {
+            observer(&request);
+        }
+
         let current_frame = world
             .get_resource::<RollbackFrameCount>()
             .map(|frame| frame.0)
@@ -179,11 +707,14 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
             None => None,
         };
 
+        // without any prediction, frames are never rolled back
+        let lockstep = matches!(session, Some(Session::P2P(s)) if s.max_prediction() == 0);
+
         let confirmed_frame = match session {
             Some(Session::P2P(s)) => Some(s.confirmed_frame()),
             Some(Session::SyncTest(s)) => {
                 let current_frame = current_frame - (s.check_distance() as i32);
-                (current_frame < 0).then_some(current_frame)
+                (current_frame >= 0).then_some(current_frame)
             }
             Some(Session::Spectator(_)) => Some(current_frame),
             None => None,
@@ -199,8 +730,25 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
 
         match request {
             GgrsRequest::SaveGameState { cell, frame } => {
-                let _span =
-                    bevy::utils::tracing::info_span!("schedule", name = "SaveWorld").entered();
+                let _span = bevy::utils::tracing::info_span!("schedule", name = "SaveWorld", frame)
+                    .entered();
+
+                // unless requested, we don't use the buffer provided by GGRS
+                let save_state = |world: &World| state_saver.map(|saver| saver(world));
+
+                if lockstep {
+                    debug!("skipping snapshot for frame {frame}: session is in lockstep");
+                    cell.save(frame, save_state(world), None);
+                    continue;
+                }
+
+                if frame.rem_euclid(keyframe_interval) != 0 {
+                    // this frame will be re-simulated from the previous keyframe if required
+                    debug!("skipping snapshot for frame {frame}: not a keyframe");
+                    cell.save(frame, save_state(world), None);
+                    continue;
+                }
+
                 debug!("saving snapshot for frame {frame}");
 
                 save_world_schedule.run(world);
@@ -210,39 +758,111 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
                     .get_resource::<Checksum>()
                     .map(|&Checksum(checksum)| checksum);
 
-                // we don't really use the buffer provided by GGRS
-                cell.save(frame, None, checksum);
+                cell.save(frame, save_state(world), checksum);
             }
             GgrsRequest::LoadGameState { frame, .. } => {
-                let _span =
-                    bevy::utils::tracing::info_span!("schedule", name = "LoadWorld").entered();
+                // only keyframes are stored, any frames after it must be re-simulated
+                let keyframe = frame - frame.rem_euclid(keyframe_interval);
+                let keyframe =
+                    match nearest_stored_snapshot::<T>(world, keyframe, frame, missing_snapshot) {
+                        Ok(keyframe) => keyframe,
+                        Err(error) => {
+                            // the remaining requests depend on this load
+                            report_error(world, error);
+                            break 'requests;
+                        }
+                    };
+
+                // re-simulated frames are nested within this span, so it covers the full cost of the rollback
+                let _span = bevy::utils::tracing::info_span!(
+                    "schedule",
+                    name = "LoadWorld",
+                    frame,
+                    keyframe,
+                    rollback_frames = current_frame - frame
+                )
+                .entered();
+
                 // we don't really use the buffer provided by GGRS
-                debug!("restoring snapshot for frame {frame}");
+                debug!("restoring snapshot for frame {keyframe}");
+
+                if let Some(mut stats) = world.get_resource_mut::<RollbackStats>() {
+                    stats.record_load(frame);
+                }
 
                 world
                     .get_resource_mut::<RollbackFrameCount>()
                     .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?")
-                    .0 = frame;
+                    .0 = keyframe;
 
                 load_world_schedule.run(world);
+
+                for resimulated_frame in (keyframe + 1)..=frame {
+                    let Some(inputs) = world
+                        .resource::<RecordedInputs<T>>()
+                        .0
+                        .get(&resimulated_frame)
+                        .cloned()
+                    else {
+                        report_error(
+                            world,
+                            format!("GGRS requested a rollback to frame {frame}, but no inputs are recorded to re-simulate frame {resimulated_frame} from the snapshot of frame {keyframe}"),
+                        );
+                        break 'requests;
+                    };
+
+                    advance_frame::<T>(world, &mut advance_world_schedule, inputs, true);
+                }
             }
             GgrsRequest::AdvanceFrame { inputs } => {
-                let _span =
-                    bevy::utils::tracing::info_span!("schedule", name = "AdvanceWorld").entered();
-                let mut frame_count = world
-                    .get_resource_mut::<RollbackFrameCount>()
-                    .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?");
+                if keyframe_interval > 1 || missing_snapshot == MissingSnapshotPolicy::Resimulate {
+                    let frame = world.resource::<RollbackFrameCount>().0 + 1;
+                    let confirmed_frame = world.resource::<ConfirmedFrameCount>().0;
+                    let confirmed_keyframe =
+                        confirmed_frame - confirmed_frame.rem_euclid(keyframe_interval);
 
-                frame_count.0 += 1;
-                let frame = frame_count.0;
+                    let snapshots = world.get_resource::<GgrsComponentSnapshots<Entity>>();
 
-                debug!("advancing to frame: {}", frame);
-                world.insert_resource(PlayerInputs::<T>(inputs));
+                    // any stored snapshot may be re-simulated from when its keyframe is missing
+                    let oldest_needed = match missing_snapshot {
+                        MissingSnapshotPolicy::Resimulate => snapshots
+                            .and_then(|snapshots| snapshots.frames().last())
+                            .map_or(confirmed_keyframe, |oldest| oldest.min(confirmed_keyframe)),
+                        MissingSnapshotPolicy::Panic => confirmed_keyframe,
+                    };
 
-                advance_world_schedule.run(world);
+                    // every stored snapshot, and every frame predicted since the newest of them
+                    let snapshot_depth =
+                        snapshots.map_or(DEFAULT_FPS, |snapshots| snapshots.depth());
+                    let max_recorded = snapshot_depth * keyframe_interval as usize
+                        + max_prediction.unwrap_or_default();
 
-                world.remove_resource::<PlayerInputs<T>>();
-                debug!("frame {frame} completed");
+                    let mut recorded = world.resource_mut::<RecordedInputs<T>>();
+                    recorded.0.insert(frame, inputs.clone());
+                    recorded
+                        .0
+                        .retain(|&recorded_frame, _| recorded_frame > oldest_needed);
+
+                    while recorded.0.len() > max_recorded {
+                        recorded.0.pop_first();
+                    }
+                }
+
+                let confirmed = inputs
+                    .iter()
+                    .all(|(_, status)| *status != InputStatus::Predicted);
+
+                if redundant_verification && confirmed {
+                    verify_advance_frame::<T>(
+                        world,
+                        &mut load_world_schedule,
+                        &mut save_world_schedule,
+                        &mut advance_world_schedule,
+                        inputs,
+                    );
+                } else {
+                    advance_frame::<T>(world, &mut advance_world_schedule, inputs, true);
+                }
             }
         }
     }
@@ -264,4 +884,239 @@ pub(crate) fn handle_requests<T: Config>(requests: Vec<GgrsRequest<T>>, world: &
     if old.is_some() {
         panic!("GgrsSchedule Schedule was Duplicated!");
     }
+
+    let confirmed_frame = world.resource::<ConfirmedFrameCount>().0;
+    let last_confirmed_frame = world.resource::<ConfirmedScheduleFrame>().0;
+
+    // every frame confirmed since the last run is visited, even if several were confirmed at once
+    for frame in (last_confirmed_frame + 1)..=confirmed_frame {
+        world.insert_resource(ConfirmedFrameCount(frame));
+
+        let _span =
+            bevy::utils::tracing::info_span!("schedule", name = "GgrsConfirmedSchedule", frame)
+                .entered();
+        debug!("running confirmed schedule for frame {frame}");
+        world.run_schedule(GgrsConfirmedSchedule);
+    }
+
+    world.insert_resource(ConfirmedFrameCount(confirmed_frame));
+    world.resource_mut::<ConfirmedScheduleFrame>().0 = last_confirmed_frame.max(confirmed_frame);
+}
+
+/// Advances the [`World`] by a single frame using `inputs`. Unless `record` is set, such as for
+/// the verification pass of [`verify_advance_frame`], the frame is not counted in the
+/// [`GgrsResimStats`] or [`GgrsInputQuality`], which are not rolled back.
+fn advance_frame<T: Config>(
+    world: &mut World,
+    advance_world_schedule: &mut Schedule,
+    mut inputs: Vec<(T::Input, InputStatus)>,
+    record: bool,
+) {
+    let mut frame_count = world
+        .get_resource_mut::<RollbackFrameCount>()
+        .expect("Unable to find GGRS RollbackFrameCount. Did you remove it?");
+
+    frame_count.0 += 1;
+    let frame = frame_count.0;
+
+    let resimulating = record
+        && world
+            .get_resource_mut::<GgrsResimStats>()
+            .is_some_and(|mut stats| stats.start_frame(frame));
+
+    let _span =
+        bevy::utils::tracing::info_span!("schedule", name = "AdvanceWorld", frame, resimulating)
+            .entered();
+
+    debug!("advancing to frame: {}", frame);
+
+    if let Some(players) = world.get_resource::<GgrsPlayers>() {
+        debug_assert_eq!(
+            inputs.len(),
+            players.num_players,
+            "GGRS provided inputs for an unexpected number of players"
+        );
+    }
+
+    apply_disconnect_input_policy::<T>(world, &mut inputs);
+    apply_ai_inputs::<T>(world, &mut inputs);
+
+    if let Some(mut history) = world.get_resource_mut::<PlayerInputHistory<T>>() {
+        history.push(&inputs);
+    }
+
+    if let Some(mut quality) = world
+        .get_resource_mut::<GgrsInputQuality>()
+        .filter(|_| record)
+    {
+        quality.record(frame, inputs.iter().map(|&(_, status)| status));
+    }
+
+    world.insert_resource(PlayerInputs::<T>(inputs));
+
+    let start = Instant::now();
+    advance_world_schedule.run(world);
+
+    if let Some(mut stats) = world
+        .get_resource_mut::<GgrsResimStats>()
+        .filter(|_| record)
+    {
+        stats.finish_frame(start.elapsed());
+    }
+
+    world.remove_resource::<PlayerInputs<T>>();
+    debug!("frame {frame} completed");
+}
+
+/// Returns `keyframe` if a snapshot of it is stored. Otherwise, applies the [`MissingSnapshotPolicy`],
+/// returning an earlier stored frame from which `frame` can be re-simulated, or an error if
+/// there is none.
+fn nearest_stored_snapshot<T: Config>(
+    world: &World,
+    keyframe: i32,
+    frame: i32,
+    policy: MissingSnapshotPolicy,
+) -> Result<i32, String> {
+    let Some(snapshots) = world.get_resource::<GgrsComponentSnapshots<Entity>>() else {
+        return Ok(keyframe);
+    };
+
+    if snapshots.peek(keyframe).is_some() {
+        return Ok(keyframe);
+    }
+
+    let stored = snapshots.frames().collect::<Vec<_>>();
+
+    match policy {
+        MissingSnapshotPolicy::Panic => panic!(
+            "GGRS requested a rollback to frame {frame}, but no snapshot of frame {keyframe} is stored. Stored frames: {stored:?}"
+        ),
+        MissingSnapshotPolicy::Resimulate => {
+            let recorded = &world.resource::<RecordedInputs<T>>().0;
+
+            let Some(base) = stored
+                .iter()
+                .copied()
+                .filter(|&stored_frame| stored_frame < keyframe)
+                .filter(|&stored_frame| {
+                    ((stored_frame + 1)..=frame).all(|resimulated| recorded.contains_key(&resimulated))
+                })
+                .max()
+            else {
+                return Err(format!(
+                    "GGRS requested a rollback to frame {frame}, but no snapshot of frame {keyframe} is stored, and no earlier snapshot can be re-simulated from. Stored frames: {stored:?}"
+                ));
+            };
+
+            warn!("No snapshot of frame {keyframe} is stored, re-simulating from frame {base}");
+
+            Ok(base)
+        }
+    }
+}
+
+/// Advances a frame twice from the same state, panicking if the checksums differ, or if
+/// [`ChecksumDiagnostics`] found any differing component. See [`RedundantVerification`].
+///
+/// The first pass is saved into and loaded from scratch snapshot storages, as with
+/// [`WorldSnapshot::capture`](`crate::WorldSnapshot::capture`), so the stored snapshots are
+/// untouched and only the second pass is recorded as advancing the frame.
+fn verify_advance_frame<T: Config>(
+    world: &mut World,
+    load_world_schedule: &mut Schedule,
+    save_world_schedule: &mut Schedule,
+    advance_world_schedule: &mut Schedule,
+    inputs: Vec<(T::Input, InputStatus)>,
+) {
+    let frame = world.resource::<RollbackFrameCount>().0;
+
+    let _span = bevy::utils::tracing::info_span!("schedule", name = "RedundantVerification", frame)
+        .entered();
+
+    let checksum = |world: &World| world.get_resource::<Checksum>().map(|checksum| checksum.0);
+
+    let mut expected = None;
+
+    let scratch = with_scratch_snapshots(world, default(), |world| {
+        save_world_schedule.run(world);
+
+        advance_frame::<T>(world, advance_world_schedule, inputs.clone(), false);
+        save_world_schedule.run(world);
+        expected = checksum(world);
+
+        world.resource_mut::<RollbackFrameCount>().0 = frame;
+        load_world_schedule.run(world);
+    });
+
+    advance_frame::<T>(world, advance_world_schedule, inputs, true);
+
+    let mut actual = None;
+
+    // with diagnostics, this save is compared against the first save of the frame
+    with_scratch_snapshots(world, scratch, |world| {
+        save_world_schedule.run(world);
+        actual = checksum(world);
+    });
+
+    if let Some(difference) = world
+        .get_resource::<ChecksumDiagnostics>()
+        .and_then(|diagnostics| diagnostics.resimulation_differences().first().copied())
+    {
+        panic!(
+            "Redundant verification failed: {} on {:?} differed when frame {} was advanced again from frame {frame} with identical inputs. A system in the GgrsSchedule likely depends on state which is not rolled back.",
+            difference.type_name,
+            difference.rollback,
+            frame + 1
+        );
+    }
+
+    assert_eq!(
+        expected,
+        actual,
+        "Redundant verification failed: frame {} produced a different checksum when advanced again from frame {frame} with identical inputs. A system in the GgrsSchedule likely depends on state which is not rolled back.",
+        frame + 1
+    );
+}
+
+/// Runs `f` with `scratch` in place of every stored snapshot, returning the scratch storages
+/// afterwards. The stored snapshots, and which of them component groups are reused from, are
+/// restored once `f` returns.
+fn with_scratch_snapshots(
+    world: &mut World,
+    scratch: HashMap<TypeId, ErasedSnapshots>,
+    f: impl FnOnce(&mut World),
+) -> HashMap<TypeId, ErasedSnapshots> {
+    let last_save = world
+        .get_resource::<ComponentGroups>()
+        .and_then(|groups| groups.last_save);
+
+    let stored = SnapshotStorages::swap_all(world, scratch);
+    f(world);
+    let scratch = SnapshotStorages::swap_all(world, stored);
+
+    if let Some(mut groups) = world.get_resource_mut::<ComponentGroups>() {
+        groups.last_save = last_save;
+    }
+
+    scratch
+}
+
+/// Inputs for recently advanced frames, used to re-simulate frames which were not snapshot.
+#[derive(Resource)]
+pub(crate) struct RecordedInputs<T: Config>(BTreeMap<i32, Vec<(T::Input, InputStatus)>>);
+
+impl<T: Config> Default for RecordedInputs<T> {
+    fn default() -> Self {
+        Self(default())
+    }
+}
+
+/// The last [`ConfirmedFrameCount`] the [`GgrsConfirmedSchedule`] was run for.
+#[derive(Resource)]
+pub(crate) struct ConfirmedScheduleFrame(i32);
+
+impl Default for ConfirmedScheduleFrame {
+    fn default() -> Self {
+        Self(-1)
+    }
 }
@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+use ggrs::{Config, GgrsError, NonBlockingSocket, PlayerHandle, PlayerType, SessionBuilder};
+
+use crate::{Lockstep, RollbackFrameRate, Session};
+
+/// A thin wrapper around GGRS's [`SessionBuilder`] which produces a [`Session`] ready to be
+/// inserted, configured to match the [`RollbackFrameRate`] of the [`App`].
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsSessionBuilder};
+/// #
+/// # fn start(app: &mut App) -> Result<(), ggrs::GgrsError> {
+/// let session = GgrsSessionBuilder::<GgrsConfig<u8>>::new(&app.world, 2)?
+///     .with_input_delay(2)
+///     .add_local_player(0)?
+///     .add_local_player(1)?
+///     .start_synctest_session()?;
+///
+/// app.insert_resource(session);
+/// # Ok(())
+/// # }
+/// ```
+pub struct GgrsSessionBuilder<C: Config> {
+    builder: SessionBuilder<C>,
+    frame_rate: usize,
+}
+
+impl<C: Config> GgrsSessionBuilder<C> {
+    /// Creates a builder for a session with `num_players` players, running at the
+    /// [`RollbackFrameRate`] configured in `world`. If [`Lockstep`] is enabled, the max
+    /// prediction window starts at `0`.
+    pub fn new(world: &World, num_players: usize) -> Result<Self, GgrsError> {
+        let frame_rate = world
+            .get_resource::<RollbackFrameRate>()
+            .copied()
+            .unwrap_or_default()
+            .0;
+
+        let mut builder = SessionBuilder::new()
+            .with_num_players(num_players)
+            .with_fps(frame_rate)?;
+
+        if world
+            .get_resource::<Lockstep>()
+            .is_some_and(|lockstep| lockstep.0)
+        {
+            builder = builder.with_max_prediction_window(0)?;
+        }
+
+        Ok(Self {
+            builder,
+            frame_rate,
+        })
+    }
+
+    /// Change the frame rate GGRS expects the session to run at. This should match the
+    /// [`RollbackFrameRate`] of the [`App`], so a warning is logged if it does not.
+    pub fn with_fps(mut self, fps: usize) -> Result<Self, GgrsError> {
+        if fps != self.frame_rate {
+            warn!(
+                "Session configured for {fps} FPS, but the rollback schedule runs at {} FPS. Use GgrsApp::set_rollback_schedule_fps to change it.",
+                self.frame_rate
+            );
+        }
+
+        self.builder = self.builder.with_fps(fps)?;
+
+        Ok(self)
+    }
+
+    /// Add a player local to this client with the provided `handle`.
+    pub fn add_local_player(self, handle: PlayerHandle) -> Result<Self, GgrsError> {
+        self.add_player(PlayerType::Local, handle)
+    }
+
+    /// Add a spectator at `address`, which will be sent confirmed inputs from this client.
+    /// Spectator handles must not overlap with player handles.
+    pub fn add_spectator(
+        self,
+        handle: PlayerHandle,
+        address: C::Address,
+    ) -> Result<Self, GgrsError> {
+        self.add_player(PlayerType::Spectator(address), handle)
+    }
+
+    /// Add a player of any [`PlayerType`] with the provided `handle`.
+    pub fn add_player(
+        mut self,
+        player_type: PlayerType<C::Address>,
+        handle: PlayerHandle,
+    ) -> Result<Self, GgrsError> {
+        self.builder = self.builder.add_player(player_type, handle)?;
+
+        Ok(self)
+    }
+
+    /// Set the input delay, in frames, for local players.
+    pub fn with_input_delay(mut self, delay: usize) -> Self {
+        self.builder = self.builder.with_input_delay(delay);
+        self
+    }
+
+    /// Set the maximum number of frames GGRS may predict ahead of confirmed inputs.
+    pub fn with_max_prediction_window(mut self, window: usize) -> Result<Self, GgrsError> {
+        self.builder = self.builder.with_max_prediction_window(window)?;
+
+        Ok(self)
+    }
+
+    /// Apply any other configuration directly to the underlying [`SessionBuilder`].
+    pub fn map(mut self, f: impl FnOnce(SessionBuilder<C>) -> SessionBuilder<C>) -> Self {
+        self.builder = f(self.builder);
+        self
+    }
+
+    /// Start a [`P2PSession`](`ggrs::P2PSession`) communicating over `socket`.
+    pub fn start_p2p_session(
+        self,
+        socket: impl NonBlockingSocket<C::Address> + 'static,
+    ) -> Result<Session<C>, GgrsError> {
+        Ok(Session::P2P(self.builder.start_p2p_session(socket)?))
+    }
+
+    /// Start a [`SyncTestSession`](`ggrs::SyncTestSession`), useful for testing determinism locally.
+    pub fn start_synctest_session(self) -> Result<Session<C>, GgrsError> {
+        Ok(Session::SyncTest(self.builder.start_synctest_session()?))
+    }
+}
+
+/// A function which attempts to start a [`Session`], returning [`None`] until it is ready.
+type SessionStarter<C> = Box<dyn FnMut(&mut World) -> Option<Session<C>> + Send + Sync>;
+
+/// A [`Resource`] which starts a [`Session`] once its transport is ready, such as a WebRTC socket
+/// which connects asynchronously. Until then, the provided function is polled once per update
+/// by the [`GgrsPlugin`](`crate::GgrsPlugin`), before GGRS is driven. Once it returns a
+/// [`Session`], the session is inserted and this resource is removed.
+///
+/// Polling is skipped while a [`Session`] already exists.
+///
+/// # Examples
+/// A transport which connects in the background, such as a
+/// [`bevy_matchbox`](https://docs.rs/bevy_matchbox) WebRTC socket, is handed over once connected:
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, GgrsSessionBuilder, PendingSession};
+/// # use ggrs::{Message, NonBlockingSocket};
+/// #
+/// # type MyConfig = GgrsConfig<u8, usize>;
+/// #
+/// # struct Transport;
+/// #
+/// # impl NonBlockingSocket<usize> for Transport {
+/// #     fn send_to(&mut self, _msg: &Message, _addr: &usize) {}
+/// #
+/// #     fn receive_all_messages(&mut self) -> Vec<(usize, Message)> {
+/// #         Vec::new()
+/// #     }
+/// # }
+/// #
+/// /// Holds the transport once it has connected to the other peer.
+/// #[derive(Resource, Default)]
+/// struct Connection(Option<Transport>);
+///
+/// let mut app = App::new();
+///
+/// app.add_plugins((MinimalPlugins, GgrsPlugin::<MyConfig>::default()))
+///     .init_resource::<Connection>()
+///     .insert_resource(PendingSession::<MyConfig>::new(|world: &mut World| {
+///         let socket = world.resource_mut::<Connection>().0.take()?;
+///
+///         let session = GgrsSessionBuilder::<MyConfig>::new(world, 2)
+///             .and_then(|builder| builder.add_local_player(0))
+///             .and_then(|builder| builder.add_player(PlayerType::Remote(1), 1))
+///             .and_then(|builder| builder.start_p2p_session(socket))
+///             .expect("Invalid session configuration");
+///
+///         Some(session)
+///     }));
+///
+/// app.update();
+/// assert!(!app.world.contains_resource::<Session<MyConfig>>());
+///
+/// // the session starts on the first update after the transport connects
+/// app.world.resource_mut::<Connection>().0 = Some(Transport);
+/// app.update();
+/// assert!(app.world.contains_resource::<Session<MyConfig>>());
+/// assert!(!app.world.contains_resource::<PendingSession<MyConfig>>());
+/// ```
+#[derive(Resource)]
+pub struct PendingSession<C: Config> {
+    start: SessionStarter<C>,
+}
+
+impl<C: Config> PendingSession<C> {
+    /// Creates a [`PendingSession`] which polls `start` until it returns a [`Session`].
+    pub fn new(
+        start: impl FnMut(&mut World) -> Option<Session<C>> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            start: Box::new(start),
+        }
+    }
+
+    /// An exclusive [`System`] which polls the [`PendingSession`], if any, inserting the
+    /// [`Session`] once it has started.
+    pub fn poll(world: &mut World) {
+        if world.contains_resource::<Session<C>>() {
+            return;
+        }
+
+        let Some(mut pending) = world.remove_resource::<Self>() else {
+            return;
+        };
+
+        match (pending.start)(world) {
+            Some(session) => {
+                debug!("Pending session started");
+                world.insert_resource(session);
+            }
+            None => world.insert_resource(pending),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{Rollback, RollbackFrameCount, SaveWorld, SaveWorldSet, DEFAULT_FPS};
+
+/// Checksum contributions of every tracked [`Component`] for a single frame, keyed by the
+/// [`Rollback`] entity and the full type name of the [`Component`].
+pub type ChecksumBreakdown = HashMap<(Rollback, &'static str), u64>;
+
+/// A single contribution which differs between two [`ChecksumBreakdowns`](`ChecksumBreakdown`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChecksumDifference {
+    /// The [`Rollback`] entity the [`Component`] belongs to.
+    pub rollback: Rollback,
+    /// Full type name of the [`Component`].
+    pub type_name: &'static str,
+}
+
+/// A [`Resource`] which records the checksum contribution of each tracked [`Component`]
+/// on each [`Rollback`] entity, allowing a changed [`Checksum`](`crate::Checksum`) to be
+/// traced back to the `(entity, component)` pairs responsible for it.
+///
+/// Only [`Components`](`Component`) added to the checksum through a
+/// [`ComponentChecksumPlugin`](`crate::ComponentChecksumPlugin`) are recorded.
+#[derive(Resource, Default)]
+pub struct ChecksumDiagnostics {
+    pending: ChecksumBreakdown,
+    /// Unlike [`GgrsSnapshots`](`crate::GgrsSnapshots`), frames are kept across rollbacks so
+    /// re-saved frames can be compared against their original.
+    history: BTreeMap<i32, ChecksumBreakdown>,
+    resimulation_differences: Vec<ChecksumDifference>,
+}
+
+impl ChecksumDiagnostics {
+    /// Record the checksum contribution of a [`Component`] for the frame currently being saved.
+    pub fn record(&mut self, rollback: Rollback, type_name: &'static str, checksum: u64) {
+        self.pending.insert((rollback, type_name), checksum);
+    }
+
+    /// Get the recorded contributions for a particular frame, if it is still stored.
+    pub fn breakdown(&self, frame: i32) -> Option<&ChecksumBreakdown> {
+        self.history.get(&frame)
+    }
+
+    /// Compare the contributions recorded for two frames, returning every `(entity, component)`
+    /// pair which was added, removed, or changed. Returns [`None`] if either frame is no longer stored.
+    pub fn diff(&self, from: i32, to: i32) -> Option<Vec<ChecksumDifference>> {
+        Some(diff_breakdowns(self.breakdown(from)?, self.breakdown(to)?))
+    }
+
+    /// Compare the contributions recorded for a frame against the frame before it.
+    pub fn changed(&self, frame: i32) -> Option<Vec<ChecksumDifference>> {
+        self.diff(frame.wrapping_sub(1), frame)
+    }
+
+    /// Differences found the last time a frame was saved again after a rollback.
+    ///
+    /// In a [`SyncTestSession`](`ggrs::SyncTestSession`), any difference here is a desync. In a
+    /// [`P2PSession`](`ggrs::P2PSession`), differences are expected whenever predicted inputs were wrong.
+    pub fn resimulation_differences(&self) -> &[ChecksumDifference] {
+        &self.resimulation_differences
+    }
+
+    /// Forget every recorded frame, so frames of the next [`Session`](`crate::Session`) are not
+    /// compared against this one.
+    pub(crate) fn clear(&mut self) {
+        self.pending.clear();
+        self.history.clear();
+        self.resimulation_differences.clear();
+    }
+}
+
+fn diff_breakdowns(from: &ChecksumBreakdown, to: &ChecksumBreakdown) -> Vec<ChecksumDifference> {
+    let mut differences = from
+        .iter()
+        .filter(|&(key, checksum)| to.get(key) != Some(checksum))
+        .map(|(key, _)| key)
+        .chain(to.keys().filter(|&key| !from.contains_key(key)))
+        .map(|&(rollback, type_name)| ChecksumDifference {
+            rollback,
+            type_name,
+        })
+        .collect::<Vec<_>>();
+
+    differences.sort_by_key(|difference| difference.type_name);
+
+    differences
+}
+
+/// A [`Plugin`] which enables [`ChecksumDiagnostics`]. This is intended for debugging desyncs,
+/// and adds overhead to every save.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ChecksumDiagnostics, ChecksumDiagnosticsPlugin, RollbackFrameCount};
+/// #
+/// # fn start(session: Session<GgrsConfig<u8>>) {
+/// # let mut app = App::new();
+/// #[derive(Component, Clone, Copy, Hash)]
+/// struct Health(u32);
+///
+/// app.rollback_component_with_clone::<Health>();
+/// app.checksum_component_with_hash::<Health>();
+///
+/// // Record which entities contribute what to the checksum
+/// app.add_plugins(ChecksumDiagnosticsPlugin);
+///
+/// fn print_changes(diagnostics: Res<ChecksumDiagnostics>, frame: Res<RollbackFrameCount>) {
+///     for difference in diagnostics.changed(frame.0).unwrap_or_default() {
+///         info!("{:?} changed {}", difference.rollback, difference.type_name);
+///     }
+/// }
+/// # app.add_systems(Update, print_changes);
+/// # }
+/// ```
+pub struct ChecksumDiagnosticsPlugin;
+
+impl ChecksumDiagnosticsPlugin {
+    /// A [`System`] which stores the contributions recorded during [`SaveWorldSet::Checksum`].
+    pub fn store(mut diagnostics: ResMut<ChecksumDiagnostics>, frame: Res<RollbackFrameCount>) {
+        let diagnostics = diagnostics.as_mut();
+        let pending = std::mem::take(&mut diagnostics.pending);
+
+        if let Some(previous) = diagnostics.history.get(&frame.0) {
+            let differences = diff_breakdowns(previous, &pending);
+
+            for difference in &differences {
+                debug!(
+                    "Frame {} re-saved with a different {} on {:?}",
+                    frame.0, difference.type_name, difference.rollback
+                );
+            }
+
+            diagnostics.resimulation_differences = differences;
+        }
+
+        diagnostics.history.insert(frame.0, pending);
+
+        while diagnostics.history.len() > DEFAULT_FPS {
+            diagnostics.history.pop_first();
+        }
+    }
+}
+
+impl Plugin for ChecksumDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChecksumDiagnostics>().add_systems(
+            SaveWorld,
+            Self::store
+                .after(SaveWorldSet::Checksum)
+                .before(SaveWorldSet::Snapshot),
+        );
+    }
+}
@@ -3,7 +3,8 @@ use std::hash::{Hash, Hasher};
 use bevy::prelude::*;
 
 use crate::{
-    checksum_hasher, ChecksumFlag, ChecksumPart, Rollback, RollbackOrdered, SaveWorld, SaveWorldSet,
+    checksum_hasher, ChecksumDiagnostics, ChecksumFlag, ChecksumPart, Rollback, RollbackOrdered,
+    SaveWorld, SaveWorldSet,
 };
 
 /// A [`Plugin`] which will track the [`Component`] `C` on [`Rollback Entities`](`Rollback`) and ensure a
@@ -58,6 +59,7 @@ where
 
         let update = move |mut commands: Commands,
                            rollback_ordered: Res<RollbackOrdered>,
+                           mut diagnostics: Option<ResMut<ChecksumDiagnostics>>,
                            components: Query<
             (&Rollback, &C),
             (With<Rollback>, Without<ChecksumFlag<C>>),
@@ -73,9 +75,15 @@ where
             for (&rollback, component) in components.iter() {
                 let mut hasher = hasher.clone();
 
+                let component_checksum = custom_hasher(component);
+
+                if let Some(diagnostics) = diagnostics.as_mut() {
+                    diagnostics.record(rollback, std::any::type_name::<C>(), component_checksum);
+                }
+
                 // Hashing the rollback index ensures this hash is unique and stable
                 rollback_ordered.order(rollback).hash(&mut hasher);
-                custom_hasher(component).hash(&mut hasher);
+                component_checksum.hash(&mut hasher);
 
                 // XOR chosen over addition or multiplication as it is closed on u64 and commutative
                 result ^= hasher.finish();
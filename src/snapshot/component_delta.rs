@@ -0,0 +1,210 @@
+use crate::{
+    GgrsComponentSnapshot, GgrsComponentSnapshots, LoadWorld, LoadWorldSet, Rollback,
+    RollbackFrameCount, RollbackKind, RollbackRegistry, SaveWorld, SaveWorldSet,
+    SnapshotMemoryUsage, SnapshotStorages, DEFAULT_FPS,
+};
+use bevy::{prelude::*, utils::HashMap};
+use std::sync::Arc;
+
+/// A snapshot of a single [`Component`] stored by a [`ComponentDeltaSnapshotPlugin`], as a delta
+/// against a shared baseline value.
+pub struct DeltaSnapshot<T, D> {
+    baseline: Arc<T>,
+    delta: D,
+}
+
+impl<T: Clone, D> DeltaSnapshot<T, D> {
+    /// Reconstructs the full value this snapshot was taken from using `apply`.
+    pub fn reconstruct(&self, apply: fn(&mut T, &D)) -> T {
+        let mut value = T::clone(&self.baseline);
+        apply(&mut value, &self.delta);
+        value
+    }
+}
+
+/// A [`Resource`] holding the delta functions and current baselines of a
+/// [`ComponentDeltaSnapshotPlugin`].
+#[derive(Resource)]
+pub struct ComponentDelta<T, D> {
+    diff: fn(&T, &T) -> D,
+    apply: fn(&mut T, &D),
+    baselines: HashMap<Rollback, Arc<T>>,
+    saves_since_rebase: usize,
+}
+
+/// A [`Plugin`] which manages snapshots for a [`Component`] by storing only a compact delta each
+/// frame, which suits large components that rarely change, such as a tilemap chunk.
+///
+/// `diff(baseline, value)` must produce a delta which `apply` turns `baseline` back into `value`.
+/// Deltas are taken against a baseline value of each entity, shared by every snapshot which uses
+/// it, rather than against the previous frame. This keeps every snapshot independent, so discarding
+/// any of them never invalidates another. Baselines are refreshed once per second of saves, so
+/// deltas only accumulate a bounded number of changes; an old baseline is freed once no stored
+/// snapshot refers to it.
+///
+/// Loading reconstructs the full value, and checksums are always calculated from the full
+/// [`Component`], see [`ComponentChecksumPlugin`](`crate::ComponentChecksumPlugin`).
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentDeltaSnapshotPlugin};
+/// #
+/// #[derive(Component, Clone)]
+/// struct Chunk(Vec<u8>);
+///
+/// /// Indices and new values of every changed tile
+/// struct ChunkChanges(Vec<(usize, u8)>);
+///
+/// fn diff(baseline: &Chunk, value: &Chunk) -> ChunkChanges {
+///     let changes = baseline.0.iter().zip(&value.0).enumerate()
+///         .filter(|(_, (old, new))| old != new)
+///         .map(|(index, (_, &new))| (index, new))
+///         .collect();
+///
+///     ChunkChanges(changes)
+/// }
+///
+/// fn apply(chunk: &mut Chunk, changes: &ChunkChanges) {
+///     for &(index, tile) in &changes.0 {
+///         chunk.0[index] = tile;
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(ComponentDeltaSnapshotPlugin::new(diff, apply));
+/// ```
+pub struct ComponentDeltaSnapshotPlugin<T, D> {
+    diff: fn(&T, &T) -> D,
+    apply: fn(&mut T, &D),
+}
+
+impl<T, D> ComponentDeltaSnapshotPlugin<T, D> {
+    /// Creates a plugin which stores deltas produced by `diff`, and reconstructs values using `apply`.
+    pub fn new(diff: fn(&T, &T) -> D, apply: fn(&mut T, &D)) -> Self {
+        Self { diff, apply }
+    }
+}
+
+impl<T, D> ComponentDeltaSnapshotPlugin<T, D>
+where
+    T: Component + Clone,
+    D: Send + Sync + 'static,
+{
+    pub fn save(
+        mut snapshots: ResMut<GgrsComponentSnapshots<T, DeltaSnapshot<T, D>>>,
+        mut delta: ResMut<ComponentDelta<T, D>>,
+        frame: Res<RollbackFrameCount>,
+        query: Query<(&Rollback, &T)>,
+    ) {
+        let delta = delta.as_mut();
+
+        delta.saves_since_rebase += 1;
+
+        if delta.saves_since_rebase >= DEFAULT_FPS {
+            delta.saves_since_rebase = 0;
+            delta.baselines.clear();
+        }
+
+        let components = query.iter().map(|(&rollback, component)| {
+            let baseline = delta
+                .baselines
+                .entry(rollback)
+                .or_insert_with(|| Arc::new(component.clone()))
+                .clone();
+
+            let stored = DeltaSnapshot {
+                delta: (delta.diff)(&baseline, component),
+                baseline,
+            };
+
+            (rollback, stored)
+        });
+
+        let snapshot = GgrsComponentSnapshot::new(components);
+
+        // entities which no longer have the component do not need their baseline
+        delta
+            .baselines
+            .retain(|rollback, _| snapshot.get(rollback).is_some());
+
+        trace!(
+            "Snapshot {} {} component delta(s)",
+            snapshot.iter().count(),
+            bevy::utils::get_short_name(std::any::type_name::<T>())
+        );
+
+        snapshots.push(frame.0, snapshot);
+    }
+
+    pub fn load(
+        mut commands: Commands,
+        mut snapshots: ResMut<GgrsComponentSnapshots<T, DeltaSnapshot<T, D>>>,
+        delta: Res<ComponentDelta<T, D>>,
+        frame: Res<RollbackFrameCount>,
+        mut query: Query<(Entity, &Rollback, Option<&mut T>)>,
+    ) {
+        let snapshot = snapshots.rollback(frame.0).get();
+
+        for (entity, rollback, component) in query.iter_mut() {
+            let snapshot = snapshot.get(rollback);
+
+            match (component, snapshot) {
+                (Some(mut component), Some(snapshot)) => {
+                    *component = snapshot.reconstruct(delta.apply);
+                }
+                (Some(_), None) => {
+                    commands.entity(entity).remove::<T>();
+                }
+                (None, Some(snapshot)) => {
+                    commands
+                        .entity(entity)
+                        .insert(snapshot.reconstruct(delta.apply));
+                }
+                (None, None) => {}
+            }
+        }
+
+        trace!(
+            "Rolled back {} {} component delta(s)",
+            snapshot.iter().count(),
+            bevy::utils::get_short_name(std::any::type_name::<T>())
+        );
+    }
+}
+
+impl<T, D> Plugin for ComponentDeltaSnapshotPlugin<T, D>
+where
+    T: Component + Clone,
+    D: Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<RollbackRegistry>(default)
+            .register_target::<T>(std::any::type_name::<Self>(), RollbackKind::Component);
+
+        SnapshotStorages::register::<T, GgrsComponentSnapshot<T, DeltaSnapshot<T, D>>>(
+            &mut app.world,
+        );
+
+        app.insert_resource(ComponentDelta::<T, D> {
+            diff: self.diff,
+            apply: self.apply,
+            baselines: default(),
+            saves_since_rebase: 0,
+        })
+        .init_resource::<GgrsComponentSnapshots<T, DeltaSnapshot<T, D>>>()
+        .init_resource::<SnapshotMemoryUsage>()
+        .add_systems(
+            SaveWorld,
+            (
+                GgrsComponentSnapshots::<T, DeltaSnapshot<T, D>>::discard_old_snapshots,
+                Self::save,
+                GgrsComponentSnapshots::<T, DeltaSnapshot<T, D>>::record_memory,
+            )
+                .chain()
+                .in_set(SaveWorldSet::Snapshot),
+        )
+        .add_systems(LoadWorld, Self::load.in_set(LoadWorldSet::Data));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    GgrsComponentSnapshot, GgrsSnapshots, LoadWorld, LoadWorldSet, Rollback, RollbackFrameCount,
+    RollbackKind, RollbackRegistry, SaveWorld, SaveWorldSet, SnapshotStorages,
+};
+use bevy::{
+    ecs::{component::Tick, reflect::ReflectComponent},
+    prelude::*,
+    reflect::FromType,
+};
+use std::{any::TypeId, sync::Arc};
+
+/// Typical [`Resource`] used to store snapshots captured by the [`ComponentGroupSnapshotPlugin`],
+/// with one [`GgrsComponentSnapshot`] per [`ComponentGroup`], in registration order.
+pub type GgrsComponentGroupSnapshots =
+    GgrsSnapshots<ComponentGroups, Vec<GgrsComponentSnapshot<ComponentGroups, GroupSnapshot>>>;
+
+/// The [reflected](`Reflect`) values of every [`Component`] in a [`ComponentGroup`] on a single
+/// [`Rollback`] entity. Shared between consecutive snapshots while none of the components change.
+#[derive(Clone)]
+pub struct GroupSnapshot(Arc<[Option<Box<dyn Reflect>>]>);
+
+impl GroupSnapshot {
+    /// The stored value of each component in the group, in registration order, or [`None`] if
+    /// the entity did not have it.
+    pub fn iter(&self) -> impl Iterator<Item = Option<&dyn Reflect>> + '_ {
+        self.0.iter().map(Option::as_deref)
+    }
+}
+
+/// A [`Component`] within a [`ComponentGroup`].
+struct ComponentGroupMember {
+    type_id: TypeId,
+    type_name: &'static str,
+    reflect: ReflectComponent,
+}
+
+/// A named set of [`Components`](`Component`) which are snapshot together, registered through
+/// [`GgrsApp::rollback_component_with_reflect_in_group`](`crate::GgrsApp::rollback_component_with_reflect_in_group`).
+pub struct ComponentGroup {
+    name: &'static str,
+    members: Vec<ComponentGroupMember>,
+    captured: usize,
+    reused: usize,
+}
+
+impl ComponentGroup {
+    /// The name the group was registered with.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Full names of the [`Components`](`Component`) in this group, in registration order.
+    pub fn type_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.members.iter().map(|member| member.type_name)
+    }
+
+    /// The number of entities whose components in this group were captured by the most recent
+    /// save, because at least one of them had changed.
+    pub fn captured(&self) -> usize {
+        self.captured
+    }
+
+    /// The number of entities whose snapshot of this group was reused from the previous save
+    /// by the most recent save, because none of its components had changed.
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+}
+
+/// A [`Resource`] listing every [`ComponentGroup`], and how each was handled by the most recent
+/// save. See [`ComponentGroupSnapshotPlugin`].
+#[derive(Resource, Default)]
+pub struct ComponentGroups {
+    groups: Vec<ComponentGroup>,
+    /// The change tick of the most recent save, if any.
+    pub(crate) last_save: Option<Tick>,
+}
+
+impl ComponentGroups {
+    /// Iterate over every [`ComponentGroup`], in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &ComponentGroup> + '_ {
+        self.groups.iter()
+    }
+
+    /// The [`ComponentGroup`] registered with the provided `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&ComponentGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// Adds `T` to the group called `name`, creating the group if required.
+    pub(crate) fn add<T>(&mut self, name: &'static str)
+    where
+        T: Component + Reflect + FromWorld,
+    {
+        let index = match self.groups.iter().position(|group| group.name == name) {
+            Some(index) => index,
+            None => {
+                self.groups.push(ComponentGroup {
+                    name,
+                    members: Vec::new(),
+                    captured: 0,
+                    reused: 0,
+                });
+
+                self.groups.len() - 1
+            }
+        };
+
+        self.groups[index].members.push(ComponentGroupMember {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            reflect: <ReflectComponent as FromType<T>>::from_type(),
+        });
+    }
+}
+
+/// A [`Plugin`] which snapshots [`Components`](`Component`) in named
+/// [`ComponentGroups`](`ComponentGroup`) using [reflection](`Reflect`), skipping groups which have
+/// not changed. This reduces the cost of saving entities with many components of which only a
+/// few change each frame, such as by grouping rarely changing stats apart from movement.
+///
+/// When none of the components of a group on an entity changed since the previous save, and none
+/// were added or removed, the previous snapshot of the group is shared rather than captured
+/// again. Changes are found using Bevy's change detection, so a component modified through
+/// [`bypass_change_detection`](`bevy::ecs::change_detection::DetectChangesMut::bypass_change_detection`)
+/// will not be saved. Loading restores every group, so every loaded component is considered
+/// changed by the next save.
+///
+/// This is added automatically by
+/// [`GgrsApp::rollback_component_with_reflect_in_group`](`crate::GgrsApp::rollback_component_with_reflect_in_group`).
+/// Stored snapshots are not included in the [`SnapshotMemoryUsage`](`crate::SnapshotMemoryUsage`),
+/// since their size is unknown.
+pub struct ComponentGroupSnapshotPlugin;
+
+impl ComponentGroupSnapshotPlugin {
+    pub fn save(world: &mut World) {
+        // changes made after this tick are found by the next save
+        let this_run = world.increment_change_tick();
+        let frame = world.resource::<RollbackFrameCount>().0;
+
+        world.resource_scope(|world, mut groups: Mut<ComponentGroups>| {
+            world.resource_scope(|world, mut snapshots: Mut<GgrsComponentGroupSnapshots>| {
+                let last_run = groups.last_save;
+                let previous = snapshots
+                    .frames()
+                    .next()
+                    .and_then(|newest| snapshots.peek(newest));
+
+                let entities = world
+                    .iter_entities()
+                    .filter_map(|entity| Some((*entity.get::<Rollback>()?, entity)))
+                    .collect::<Vec<_>>();
+
+                let mut snapshot = Vec::with_capacity(groups.groups.len());
+
+                for (index, group) in groups.groups.iter_mut().enumerate() {
+                    let component_ids = group
+                        .members
+                        .iter()
+                        .map(|member| world.components().get_id(member.type_id))
+                        .collect::<Vec<_>>();
+
+                    let previous = previous.and_then(|previous| previous.get(index));
+
+                    group.captured = 0;
+                    group.reused = 0;
+
+                    let mut stored = GgrsComponentSnapshot::default();
+
+                    for (rollback, entity) in &entities {
+                        let present = component_ids
+                            .iter()
+                            .map(|&id| id.is_some_and(|id| entity.contains_id(id)))
+                            .collect::<Vec<_>>();
+
+                        if !present.contains(&true) {
+                            continue;
+                        }
+
+                        let unchanged = last_run.is_some_and(|last_run| {
+                            component_ids.iter().flatten().all(|&id| {
+                                entity
+                                    .get_change_ticks_by_id(id)
+                                    .map_or(true, |ticks| !ticks.is_changed(last_run, this_run))
+                            })
+                        });
+
+                        let reusable = previous.and_then(|previous| previous.get(rollback)).filter(
+                            |previous| {
+                                unchanged
+                                    && previous
+                                        .iter()
+                                        .map(|value| value.is_some())
+                                        .eq(present.iter().copied())
+                            },
+                        );
+
+                        if let Some(previous) = reusable {
+                            stored.insert(*rollback, previous.clone());
+                            group.reused += 1;
+                            continue;
+                        }
+
+                        let values = group
+                            .members
+                            .iter()
+                            .map(|member| {
+                                member
+                                    .reflect
+                                    .reflect(*entity)
+                                    .map(|value| value.clone_value())
+                            })
+                            .collect();
+
+                        stored.insert(*rollback, GroupSnapshot(values));
+                        group.captured += 1;
+                    }
+
+                    trace!(
+                        "Snapshot component group {}, capturing {} and reusing {} entities",
+                        group.name,
+                        group.captured,
+                        group.reused
+                    );
+
+                    snapshot.push(stored);
+                }
+
+                snapshots.push(frame, snapshot);
+            });
+
+            groups.last_save = Some(this_run);
+        });
+    }
+
+    pub fn load(world: &mut World) {
+        let frame = world.resource::<RollbackFrameCount>().0;
+
+        world.resource_scope(|world, groups: Mut<ComponentGroups>| {
+            world.resource_scope(|world, mut snapshots: Mut<GgrsComponentGroupSnapshots>| {
+                let snapshot = snapshots.rollback(frame).get();
+
+                let entities = world
+                    .iter_entities()
+                    .filter_map(|entity| Some((entity.id(), *entity.get::<Rollback>()?)))
+                    .collect::<Vec<_>>();
+
+                for (entity, rollback) in entities {
+                    let mut entity = world.entity_mut(entity);
+
+                    for (group, stored) in groups.groups.iter().zip(snapshot) {
+                        let stored = stored.get(&rollback);
+
+                        for (index, member) in group.members.iter().enumerate() {
+                            match stored.and_then(|stored| stored.0[index].as_deref()) {
+                                Some(value) => member.reflect.apply_or_insert(&mut entity, value),
+                                None => member.reflect.remove(&mut entity),
+                            }
+                        }
+                    }
+                }
+
+                trace!("Rolled back {} component group(s)", groups.groups.len());
+            });
+        });
+    }
+}
+
+impl Plugin for ComponentGroupSnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        SnapshotStorages::register::<
+            ComponentGroups,
+            Vec<GgrsComponentSnapshot<ComponentGroups, GroupSnapshot>>,
+        >(&mut app.world);
+
+        app.init_resource::<ComponentGroups>()
+            .init_resource::<GgrsComponentGroupSnapshots>()
+            .add_systems(
+                SaveWorld,
+                (
+                    GgrsComponentGroupSnapshots::discard_old_snapshots,
+                    Self::save,
+                )
+                    .chain()
+                    .in_set(SaveWorldSet::Snapshot),
+            )
+            .add_systems(LoadWorld, Self::load.in_set(LoadWorldSet::Data));
+    }
+}
+
+/// Registers `T` in the [`ComponentGroup`] called `group`, adding the
+/// [`ComponentGroupSnapshotPlugin`] if required.
+pub(crate) fn add_to_component_group<T>(app: &mut App, group: &'static str)
+where
+    T: Component + Reflect + FromWorld,
+{
+    if !app.world.contains_resource::<ComponentGroups>() {
+        app.add_plugins(ComponentGroupSnapshotPlugin);
+    }
+
+    app.world
+        .get_resource_or_insert_with::<RollbackRegistry>(default)
+        .register_target::<T>(
+            std::any::type_name::<ComponentGroupSnapshotPlugin>(),
+            RollbackKind::Component,
+        );
+
+    app.world.resource_mut::<ComponentGroups>().add::<T>(group);
+}
@@ -1,14 +1,122 @@
-use std::marker::PhantomData;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    hash::BuildHasher,
+    marker::PhantomData,
+};
 
 use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
+    utils::hashbrown,
 };
 
 use crate::{LoadWorld, LoadWorldSet, RollbackEntityMap};
 
+/// Maps every [`Entity`] contained within a value, including through containers such as
+/// [`Option`], [`Vec`], and [`HashMap`](`hashbrown::HashMap`). This makes implementing [`MapEntities`] for components
+/// which reference several entities a single call per field.
+///
+/// Only the values of maps are mapped. Entities used as keys are left unchanged, since
+/// remapping them could merge entries.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, ecs::entity::{MapEntities, EntityMapper}};
+/// # use bevy_ggrs::MapContainedEntities;
+/// #
+/// #[derive(Component, Clone)]
+/// struct Squad {
+///     leader: Option<Entity>,
+///     members: Vec<Entity>,
+/// }
+///
+/// impl MapEntities for Squad {
+///     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+///         self.leader.map_contained_entities(entity_mapper);
+///         self.members.map_contained_entities(entity_mapper);
+///     }
+/// }
+/// ```
+pub trait MapContainedEntities {
+    /// Replace every contained [`Entity`] with its mapping from the `entity_mapper`.
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper);
+}
+
+impl MapContainedEntities for Entity {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        *self = entity_mapper.get_or_reserve(*self);
+    }
+}
+
+impl<T: MapContainedEntities> MapContainedEntities for Option<T> {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        if let Some(value) = self {
+            value.map_contained_entities(entity_mapper);
+        }
+    }
+}
+
+impl<T: MapContainedEntities> MapContainedEntities for [T] {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for value in self {
+            value.map_contained_entities(entity_mapper);
+        }
+    }
+}
+
+impl<T: MapContainedEntities, const N: usize> MapContainedEntities for [T; N] {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.as_mut_slice().map_contained_entities(entity_mapper);
+    }
+}
+
+impl<T: MapContainedEntities> MapContainedEntities for Vec<T> {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.as_mut_slice().map_contained_entities(entity_mapper);
+    }
+}
+
+impl<T: MapContainedEntities> MapContainedEntities for VecDeque<T> {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for value in self {
+            value.map_contained_entities(entity_mapper);
+        }
+    }
+}
+
+impl<K, V: MapContainedEntities, S: BuildHasher> MapContainedEntities
+    for hashbrown::HashMap<K, V, S>
+{
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for value in self.values_mut() {
+            value.map_contained_entities(entity_mapper);
+        }
+    }
+}
+
+impl<K, V: MapContainedEntities, S: BuildHasher> MapContainedEntities
+    for std::collections::HashMap<K, V, S>
+{
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for value in self.values_mut() {
+            value.map_contained_entities(entity_mapper);
+        }
+    }
+}
+
+impl<K, V: MapContainedEntities> MapContainedEntities for BTreeMap<K, V> {
+    fn map_contained_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        for value in self.values_mut() {
+            value.map_contained_entities(entity_mapper);
+        }
+    }
+}
+
 /// A [`Plugin`] which updates the state of a post-rollback [`Component`] `C` using [`MapEntities`].
 ///
+/// For components holding entities in containers such as [`Option`] or [`Vec`], see
+/// [`MapContainedEntities`].
+///
 /// # Examples
 /// ```rust
 /// # use bevy::{prelude::*, ecs::entity::{MapEntities, EntityMapper}};
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+
+use crate::{LoadWorld, LoadWorldSet, Rollback};
+
+/// A [`Plugin`] which applies a fixup function to a [`Component`] `C` on every
+/// [`Rollback`] entity after each load, allowing derived state to be recomputed from
+/// the restored data without a separate system.
+///
+/// The fixup runs in [`LoadWorldSet::PostLoad`], once [`Entity`] mapping has completed.
+/// It must be deterministic, and should only depend on the value it is given.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, ComponentPostLoadPlugin};
+/// #
+/// # fn start(session: Session<GgrsConfig<u8>>) {
+/// # let mut app = App::new();
+/// #[derive(Component, Clone, Copy)]
+/// struct Hitbox {
+///     size: Vec2,
+///     area: f32,
+/// }
+///
+/// fn recompute_area(hitbox: &mut Hitbox) {
+///     hitbox.area = hitbox.size.x * hitbox.size.y;
+/// }
+///
+/// // The component must be snapshot using any supported method
+/// app.rollback_component_with_copy::<Hitbox>();
+///
+/// // This will recompute the area on each rollback
+/// app.add_plugins(ComponentPostLoadPlugin::<Hitbox>(recompute_area));
+/// # }
+/// ```
+pub struct ComponentPostLoadPlugin<C: Component>(pub for<'a> fn(&'a mut C));
+
+impl<C> Plugin for ComponentPostLoadPlugin<C>
+where
+    C: Component,
+{
+    fn build(&self, app: &mut App) {
+        let fixup = self.0;
+
+        let update = move |mut components: Query<&mut C, With<Rollback>>| {
+            for mut component in components.iter_mut() {
+                fixup(component.as_mut());
+            }
+
+            trace!(
+                "Applied post-load fixup to {}",
+                bevy::utils::get_short_name(std::any::type_name::<C>())
+            );
+        };
+
+        app.add_systems(LoadWorld, update.in_set(LoadWorldSet::PostLoad));
+    }
+}
@@ -1,12 +1,17 @@
 use crate::{
     GgrsComponentSnapshot, GgrsComponentSnapshots, LoadWorld, LoadWorldSet, Rollback,
-    RollbackFrameCount, SaveWorld, SaveWorldSet, Strategy,
+    RollbackFrameCount, RollbackKind, RollbackRegistry, SaveWorld, SaveWorldSet,
+    SnapshotMemoryUsage, SnapshotStorages, Strategy,
 };
 use bevy::prelude::*;
 use std::marker::PhantomData;
 
 /// A [`Plugin`] which manages snapshots for a [`Component`] using a provided [`Strategy`].
 ///
+/// On load, the [`Component`] is inserted on or removed from each [`Rollback`] entity so that
+/// exactly the entities which had it during the snapshot frame have it again. This includes
+/// zero-sized marker components, which are best rolled back using a [`CopyStrategy`](`crate::CopyStrategy`).
+///
 /// # Examples
 /// ```rust
 /// # use bevy::prelude::*;
@@ -110,12 +115,22 @@ where
     S::Stored: Send + Sync + 'static,
 {
     fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<RollbackRegistry>(default)
+            .register::<S>(RollbackKind::Component);
+
+        SnapshotStorages::register::<S::Target, GgrsComponentSnapshot<S::Target, S::Stored>>(
+            &mut app.world,
+        );
+
         app.init_resource::<GgrsComponentSnapshots<S::Target, S::Stored>>()
+            .init_resource::<SnapshotMemoryUsage>()
             .add_systems(
                 SaveWorld,
                 (
                     GgrsComponentSnapshots::<S::Target, S::Stored>::discard_old_snapshots,
                     Self::save,
+                    GgrsComponentSnapshots::<S::Target, S::Stored>::record_memory,
                 )
                     .chain()
                     .in_set(SaveWorldSet::Snapshot),
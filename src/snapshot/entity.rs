@@ -1,13 +1,60 @@
 use crate::{
-    GgrsComponentSnapshot, GgrsComponentSnapshots, LoadWorld, LoadWorldSet, Rollback,
-    RollbackEntityMap, RollbackFrameCount, SaveWorld, SaveWorldSet,
+    GgrsComponentSnapshot, GgrsComponentSnapshots, InstantiatedEntityMap, LoadWorld, LoadWorldSet,
+    Rollback, RollbackEntityMap, RollbackFrameCount, SaveWorld, SaveWorldSet, SnapshotMemoryUsage,
+    SnapshotStorages,
 };
 use bevy::{prelude::*, utils::HashMap};
 
+/// An [`Event`] sent by the [`EntitySnapshotPlugin`] whenever a load is about to despawn
+/// [`Rollback`] entities which did not exist in the frame being loaded.
+///
+/// Despawns are deferred until [`LoadWorldSet::EntityFlush`], so systems reading this event in
+/// [`LoadWorldSet::Entity`] after [`EntitySnapshotPlugin::load`] can still access the entities,
+/// such as to capture their render state for a disappear animation. Any other state captured this
+/// way must not affect the simulation, since it is not rolled back.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, EntitySnapshotPlugin, GgrsWillDespawn, LoadWorld};
+/// #
+/// fn play_disappear_animations(
+///     mut will_despawn: EventReader<GgrsWillDespawn>,
+///     transforms: Query<&Transform>,
+/// ) {
+///     for event in will_despawn.read() {
+///         for transform in transforms.iter_many(&event.entities) {
+///             info!("Rollback removed an entity at {}", transform.translation);
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// app.add_systems(
+///     LoadWorld,
+///     play_disappear_animations
+///         .in_set(LoadWorldSet::Entity)
+///         .after(EntitySnapshotPlugin::load),
+/// );
+/// ```
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct GgrsWillDespawn {
+    /// The frame being loaded.
+    pub frame: i32,
+    /// Every [`Rollback`] entity which will be despawned. Descendants which are not [`Rollback`]
+    /// entities are despawned along with them, but are not listed.
+    pub entities: Vec<Entity>,
+}
+
 /// A [`Plugin`] which manages the rollback for [`Entities`](`Entity`). This will ensure
 /// all [`Entities`](`Entity`) match the state of the desired frame, or can be mapped using a
 /// [`RollbackEntityMap`], which this [`Plugin`] will also manage.
 ///
+/// Hierarchies are restored like any other [`Rollback`] entities, so a hierarchy removed using
+/// `despawn_recursive` is restored in full by rolling back to a frame before the despawn, as long
+/// as every entity in it is a [`Rollback`] entity. Descendants which are not [`Rollback`] entities
+/// cannot be restored, and are despawned along with their [`Rollback`] ancestor when a rollback
+/// removes it.
+///
 /// # Examples
 /// ```rust
 /// # use bevy::prelude::*;
@@ -48,9 +95,21 @@ impl EntitySnapshotPlugin {
         mut map: ResMut<RollbackEntityMap>,
         frame: Res<RollbackFrameCount>,
         query: Query<(&Rollback, Entity)>,
+        children: Query<&Children>,
+        rollbacks: Query<(), With<Rollback>>,
+        instantiated: Option<Res<InstantiatedEntityMap>>,
+        mut will_despawn: EventWriter<GgrsWillDespawn>,
     ) {
+        // entities were just spawned to match the snapshot, only their references need mapping
+        if let Some(instantiated) = instantiated {
+            commands.remove_resource::<InstantiatedEntityMap>();
+            *map = RollbackEntityMap::new(instantiated.0.clone());
+            return;
+        }
+
         let mut entity_map = HashMap::default();
         let mut rollback_mapping = HashMap::new();
+        let mut despawned = Vec::new();
 
         let snapshot = snapshots.rollback(frame.0).get();
 
@@ -68,7 +127,13 @@ impl EntitySnapshotPlugin {
                     entity_map.insert(current_entity, old_entity);
                 }
                 (Some(current_entity), None) => {
-                    commands.entity(current_entity).despawn();
+                    despawned.push(current_entity);
+                    despawn_with_non_rollback_descendants(
+                        &mut commands,
+                        current_entity,
+                        &children,
+                        &rollbacks,
+                    );
                 }
                 (None, Some(old_entity)) => {
                     let current_entity = commands.spawn(rollback).id();
@@ -82,19 +147,51 @@ impl EntitySnapshotPlugin {
 
         trace!("Rolled back {} entity(s)", snapshot.iter().count());
 
+        if !despawned.is_empty() {
+            will_despawn.send(GgrsWillDespawn {
+                frame: frame.0,
+                entities: despawned,
+            });
+        }
+
         *map = RollbackEntityMap::new(entity_map);
     }
 }
 
+/// Despawns `entity` along with any descendants which are not [`Rollback`] entities, since those
+/// could never be restored or despawned by a later rollback. Descendants which are [`Rollback`]
+/// entities are left to the snapshot, which either despawns them too or restores their [`Parent`].
+fn despawn_with_non_rollback_descendants(
+    commands: &mut Commands,
+    entity: Entity,
+    children: &Query<&Children>,
+    rollbacks: &Query<(), With<Rollback>>,
+) {
+    if let Ok(entity_children) = children.get(entity) {
+        for &child in entity_children {
+            if !rollbacks.contains(child) {
+                despawn_with_non_rollback_descendants(commands, child, children, rollbacks);
+            }
+        }
+    }
+
+    commands.entity(entity).despawn();
+}
+
 impl Plugin for EntitySnapshotPlugin {
     fn build(&self, app: &mut App) {
+        SnapshotStorages::register::<Entity, GgrsComponentSnapshot<Entity>>(&mut app.world);
+
         app.init_resource::<GgrsComponentSnapshots<Entity>>()
+            .init_resource::<SnapshotMemoryUsage>()
             .init_resource::<RollbackEntityMap>()
+            .add_event::<GgrsWillDespawn>()
             .add_systems(
                 SaveWorld,
                 (
                     GgrsComponentSnapshots::<Entity>::discard_old_snapshots,
                     Self::save,
+                    GgrsComponentSnapshots::<Entity>::record_memory,
                 )
                     .chain()
                     .in_set(SaveWorldSet::Snapshot),
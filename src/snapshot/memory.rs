@@ -0,0 +1,145 @@
+use std::mem::size_of;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    GgrsComponentSnapshot, GgrsSnapshots, MaxPredictionWindow, Rollback, SaveWorld, SaveWorldSet,
+    SnapshotKeyframeInterval,
+};
+
+/// Estimates the memory held by a single stored snapshot.
+///
+/// Estimates are shallow: memory owned indirectly by a stored value, such as the contents of a
+/// [`Vec`] or a boxed [`Reflect`] value, is not included.
+pub trait SnapshotMemory {
+    /// The estimated number of bytes held by this snapshot, including its own size.
+    fn memory_bytes(&self) -> usize;
+}
+
+impl<T> SnapshotMemory for Option<T> {
+    fn memory_bytes(&self) -> usize {
+        size_of::<Self>()
+    }
+}
+
+impl<For, As> SnapshotMemory for GgrsComponentSnapshot<For, As> {
+    fn memory_bytes(&self) -> usize {
+        size_of::<Self>() + self.snapshot.capacity() * size_of::<(Rollback, As)>()
+    }
+}
+
+impl<For, As: SnapshotMemory> GgrsSnapshots<For, As> {
+    /// The estimated number of bytes held by all snapshots in this storage, see [`SnapshotMemory`].
+    pub fn memory_bytes(&self) -> usize {
+        let snapshots = self
+            .snapshots
+            .iter()
+            .map(SnapshotMemory::memory_bytes)
+            .sum::<usize>();
+
+        snapshots + self.frames.capacity() * size_of::<i32>()
+    }
+
+    /// A system which records the [`memory_bytes`](`GgrsSnapshots::memory_bytes`) of this
+    /// storage in the [`SnapshotMemoryUsage`].
+    pub fn record_memory(snapshots: Res<Self>, mut usage: ResMut<SnapshotMemoryUsage>)
+    where
+        For: Send + Sync + 'static,
+        As: Send + Sync + 'static,
+    {
+        usage
+            .by_type
+            .insert(std::any::type_name::<Self>(), snapshots.memory_bytes());
+
+        usage.depth = usage.depth.max(snapshots.depth());
+    }
+}
+
+/// A [`Resource`] estimating the memory held by every snapshot storage, updated after each save.
+/// See [`SnapshotMemory`] for how estimates are made.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SnapshotMemoryUsage {
+    by_type: HashMap<&'static str, usize>,
+    /// Largest depth of any storage during the last save.
+    depth: usize,
+    depth_limit: Option<usize>,
+}
+
+impl SnapshotMemoryUsage {
+    /// The estimated number of bytes held by all snapshots.
+    pub fn total_bytes(&self) -> usize {
+        self.by_type.values().sum()
+    }
+
+    /// The estimated number of bytes held by each snapshot storage, keyed by its type name.
+    pub fn by_type(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.by_type.iter().map(|(&name, &bytes)| (name, bytes))
+    }
+
+    /// The depth every snapshot storage has been limited to by the [`SnapshotMemoryLimit`], if any.
+    pub fn depth_limit(&self) -> Option<usize> {
+        self.depth_limit
+    }
+
+    /// Lifts the depth limit once a [`Session`](`crate::Session`) ends, so the next one starts
+    /// with the full depth.
+    pub(crate) fn reset_session(&mut self) {
+        self.depth_limit = None;
+    }
+
+    /// A system which reduces the depth of every snapshot storage while the [`SnapshotMemoryLimit`]
+    /// is exceeded. Depth is never reduced below what GGRS requires to roll back, so the limit may
+    /// still be exceeded if it is too small.
+    pub fn enforce_limit(
+        mut usage: ResMut<Self>,
+        limit: Option<Res<SnapshotMemoryLimit>>,
+        max_prediction: Option<Res<MaxPredictionWindow>>,
+        keyframe_interval: Option<Res<SnapshotKeyframeInterval>>,
+    ) {
+        let depth = std::mem::take(&mut usage.depth);
+
+        let Some(limit) = limit else {
+            return;
+        };
+
+        let total = usage.total_bytes();
+
+        if total <= limit.0 || depth == 0 {
+            return;
+        }
+
+        // Rolling back the whole prediction window may require loading the keyframe before it
+        let min_depth = max_prediction.map_or(0, |window| window.0)
+            + keyframe_interval.map_or(1, |interval| interval.0);
+        let scaled = (depth as u128 * limit.0 as u128 / total as u128) as usize;
+        let depth_limit = scaled.clamp(min_depth.min(depth), depth);
+
+        if depth_limit < depth {
+            warn!(
+                "Snapshots hold an estimated {total} bytes, exceeding the limit of {} bytes. Reducing snapshot depth from {depth} to {depth_limit}.",
+                limit.0
+            );
+
+            usage.depth_limit = Some(depth_limit);
+        }
+    }
+}
+
+/// A [`Resource`] limiting the estimated memory held by snapshots, in bytes. While exceeded, the
+/// depth of every snapshot storage is reduced, but never below what GGRS requires to roll back.
+/// Reduced depth is kept until the [`Session`](`crate::Session`) ends.
+/// See [`GgrsApp::set_snapshot_memory_limit`](`crate::GgrsApp::set_snapshot_memory_limit`).
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnapshotMemoryLimit(pub usize);
+
+/// A [`Plugin`] which maintains the [`SnapshotMemoryUsage`] and enforces any [`SnapshotMemoryLimit`].
+pub struct SnapshotMemoryPlugin;
+
+impl Plugin for SnapshotMemoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SnapshotMemoryUsage>().add_systems(
+            SaveWorld,
+            SnapshotMemoryUsage::enforce_limit.after(SaveWorldSet::Snapshot),
+        );
+    }
+}
@@ -1,35 +1,65 @@
-use crate::{ConfirmedFrameCount, Rollback, DEFAULT_FPS};
+use crate::{
+    ConfirmedFrameCount, Rollback, RollbackFrameCount, SnapshotKeyframeInterval,
+    SnapshotMemoryUsage, SnapshotRetention, DEFAULT_FPS,
+};
 use bevy::{
     prelude::*,
     utils::{AHasher, FixedState, HashMap},
 };
-use std::{collections::VecDeque, hash::BuildHasher, marker::PhantomData};
+use std::{
+    any::{Any, TypeId},
+    collections::VecDeque,
+    hash::BuildHasher,
+    marker::PhantomData,
+};
 
 mod checksum;
+mod checksum_diagnostics;
 mod component_checksum;
+mod component_delta;
+mod component_group;
 mod component_map;
+mod component_post_load;
 mod component_snapshot;
 mod entity;
 mod entity_checksum;
+mod memory;
+mod previous_frame;
+mod registry;
+mod relationship;
 mod resource_checksum;
 mod resource_map;
 mod resource_snapshot;
 mod rollback_entity_map;
 mod set;
 mod strategy;
+mod structural_checksum;
+mod transform;
+mod world_state;
 
 pub use checksum::*;
+pub use checksum_diagnostics::*;
 pub use component_checksum::*;
+pub use component_delta::*;
+pub use component_group::*;
 pub use component_map::*;
+pub use component_post_load::*;
 pub use component_snapshot::*;
 pub use entity::*;
 pub use entity_checksum::*;
+pub use memory::*;
+pub use previous_frame::*;
+pub use registry::*;
+pub use relationship::*;
 pub use resource_checksum::*;
 pub use resource_map::*;
 pub use resource_snapshot::*;
 pub use rollback_entity_map::*;
 pub use set::*;
 pub use strategy::*;
+pub use structural_checksum::*;
+pub use transform::*;
+pub use world_state::*;
 
 pub mod prelude {
     pub use super::{Checksum, LoadWorldSet, SaveWorldSet};
@@ -54,6 +84,8 @@ pub struct GgrsSnapshots<For, As = For> {
     frames: VecDeque<i32>,
     /// Maximum amount of snapshots to store at any one time
     depth: usize,
+    /// The depth before it was reduced to fit a [`SnapshotMemoryLimit`], if it was.
+    unlimited_depth: Option<usize>,
     _phantom: PhantomData<For>,
 }
 
@@ -63,6 +95,7 @@ impl<For, As> Default for GgrsSnapshots<For, As> {
             snapshots: VecDeque::with_capacity(DEFAULT_FPS),
             frames: VecDeque::with_capacity(DEFAULT_FPS),
             depth: DEFAULT_FPS, // TODO: Make sensible choice here
+            unlimited_depth: None,
             _phantom: default(),
         }
     }
@@ -70,8 +103,10 @@ impl<For, As> Default for GgrsSnapshots<For, As> {
 
 impl<For, As> GgrsSnapshots<For, As> {
     /// Updates the capacity of this storage to the provided depth.
+    /// At least one snapshot is always kept, so a `depth` of `0` is treated as `1`.
     pub fn set_depth(&mut self, depth: usize) -> &mut Self {
-        self.depth = depth;
+        self.depth = depth.max(1);
+        self.unlimited_depth = None;
 
         // Greedy allocation to avoid allocating at a more sensitive time.
         if self.snapshots.capacity() < self.depth {
@@ -92,6 +127,25 @@ impl<For, As> GgrsSnapshots<For, As> {
         self.depth
     }
 
+    /// Reduces the depth to at most `depth_limit`, or restores the depth from before it was
+    /// reduced if there is no limit.
+    fn apply_depth_limit(&mut self, depth_limit: Option<usize>) {
+        match depth_limit {
+            Some(depth_limit) if self.depth > depth_limit => {
+                let unlimited_depth = self.unlimited_depth.unwrap_or(self.depth);
+
+                self.set_depth(depth_limit);
+                self.unlimited_depth = Some(unlimited_depth);
+            }
+            Some(_) => {}
+            None => {
+                if let Some(unlimited_depth) = self.unlimited_depth {
+                    self.set_depth(unlimited_depth);
+                }
+            }
+        }
+    }
+
     /// Push a new snapshot for the provided frame. If the frame is earlier than any
     /// currently stored snapshots, those snapshots will be discarded.
     pub fn push(&mut self, frame: i32, snapshot: As) -> &mut Self {
@@ -151,6 +205,33 @@ impl<For, As> GgrsSnapshots<For, As> {
         self
     }
 
+    /// Like [`confirm`](`GgrsSnapshots::confirm`), but snapshots from before `confirmed_frame`
+    /// are only discarded if `retain` returns `false` for their frame.
+    pub fn confirm_retaining(
+        &mut self,
+        confirmed_frame: i32,
+        mut retain: impl FnMut(i32) -> bool,
+    ) -> &mut Self {
+        debug_assert_eq!(
+            self.snapshots.len(),
+            self.frames.len(),
+            "Snapshot and Frame queues must always be in sync"
+        );
+
+        let mut index = 0;
+
+        while let Some(&frame) = self.frames.get(index) {
+            if frame < confirmed_frame && !retain(frame) {
+                self.snapshots.remove(index).unwrap();
+                self.frames.remove(index).unwrap();
+            } else {
+                index += 1;
+            }
+        }
+
+        self
+    }
+
     /// Rolls back to the provided frame, discarding snapshots taken after the rollback point.
     pub fn rollback(&mut self, frame: i32) -> &mut Self {
         loop {
@@ -170,11 +251,24 @@ impl<For, As> GgrsSnapshots<For, As> {
         self
     }
 
+    /// Discards every stored snapshot, keeping the current depth.
+    pub fn clear(&mut self) -> &mut Self {
+        self.snapshots.clear();
+        self.frames.clear();
+
+        self
+    }
+
     /// Get the current snapshot. Use `rollback(frame)` to first select a frame to rollback to.
     pub fn get(&self) -> &As {
         self.snapshots.front().unwrap()
     }
 
+    /// Iterate over the frames which currently have a snapshot stored, newest first.
+    pub fn frames(&self) -> impl Iterator<Item = i32> + '_ {
+        self.frames.iter().copied()
+    }
+
     /// Get a particular snapshot if it exists.
     pub fn peek(&self, frame: i32) -> Option<&As> {
         let (index, _) = self
@@ -186,18 +280,138 @@ impl<For, As> GgrsSnapshots<For, As> {
     }
 
     /// A system which automatically confirms the [`ConfirmedFrameCount`], discarding older snapshots.
+    /// The keyframe preceding the confirmed frame is always kept, see [`SnapshotKeyframeInterval`],
+    /// as is any snapshot kept by the [`SnapshotRetention`] policy. The depth is reduced if required
+    /// by a [`SnapshotMemoryLimit`], and restored once a new [`Session`](`crate::Session`) starts.
     pub fn discard_old_snapshots(
         mut snapshots: ResMut<Self>,
         confirmed_frame: Option<Res<ConfirmedFrameCount>>,
+        keyframe_interval: Option<Res<SnapshotKeyframeInterval>>,
+        retention: Option<Res<SnapshotRetention>>,
+        current_frame: Option<Res<RollbackFrameCount>>,
+        memory_usage: Option<Res<SnapshotMemoryUsage>>,
     ) where
         For: Send + Sync + 'static,
         As: Send + Sync + 'static,
     {
+        snapshots.apply_depth_limit(memory_usage.and_then(|usage| usage.depth_limit()));
+
         let Some(confirmed_frame) = confirmed_frame else {
             return;
         };
 
-        snapshots.confirm(confirmed_frame.0);
+        let keyframe_interval = keyframe_interval.map_or(1, |interval| interval.0 as i32);
+        let confirmed_keyframe =
+            confirmed_frame.0 - confirmed_frame.0.rem_euclid(keyframe_interval);
+
+        match retention {
+            Some(retention) => {
+                let current_frame = current_frame.map_or(0, |frame| frame.0);
+
+                snapshots.confirm_retaining(confirmed_keyframe, |frame| {
+                    (retention.0)(frame, current_frame)
+                });
+            }
+            None => {
+                snapshots.confirm(confirmed_keyframe);
+            }
+        }
+    }
+}
+
+/// A type erased [`GgrsSnapshots`] storage, removed from the [`World`] by [`SnapshotStorages`].
+pub(crate) type ErasedSnapshots = Box<dyn Any + Send + Sync>;
+
+/// Functions managing a single registered [`GgrsSnapshots`] storage.
+#[derive(Clone, Copy)]
+struct SnapshotStorage {
+    /// Discards every snapshot in the storage.
+    clear: fn(&mut World),
+    /// Replaces the storage with the provided one, or an empty storage of the same depth,
+    /// returning the storage it replaced.
+    swap: fn(&mut World, Option<ErasedSnapshots>) -> Option<ErasedSnapshots>,
+}
+
+/// Every snapshot storage added by a snapshot [`Plugin`], so all of them can be cleared once a
+/// [`Session`](`crate::Session`) ends, or swapped out to capture a
+/// [`WorldSnapshot`](`crate::WorldSnapshot`).
+#[derive(Resource, Default)]
+pub(crate) struct SnapshotStorages(HashMap<TypeId, SnapshotStorage>);
+
+impl SnapshotStorages {
+    /// Records that `world` contains a [`GgrsSnapshots<For, As>`] storage.
+    pub(crate) fn register<For, As>(world: &mut World)
+    where
+        For: Send + Sync + 'static,
+        As: Send + Sync + 'static,
+    {
+        world.get_resource_or_insert_with::<Self>(default).0.insert(
+            TypeId::of::<GgrsSnapshots<For, As>>(),
+            SnapshotStorage {
+                clear: |world| {
+                    if let Some(mut snapshots) = world.get_resource_mut::<GgrsSnapshots<For, As>>()
+                    {
+                        snapshots.clear();
+                    }
+                },
+                swap: |world, replacement| {
+                    let previous = world.remove_resource::<GgrsSnapshots<For, As>>();
+
+                    let replacement = replacement
+                        .and_then(|replacement| {
+                            replacement.downcast::<GgrsSnapshots<For, As>>().ok()
+                        })
+                        .map(|replacement| *replacement)
+                        .unwrap_or_else(|| {
+                            let mut empty = GgrsSnapshots::<For, As>::default();
+
+                            if let Some(previous) = &previous {
+                                empty.set_depth(previous.depth());
+                            }
+
+                            empty
+                        });
+
+                    world.insert_resource(replacement);
+
+                    previous.map(|previous| Box::new(previous) as ErasedSnapshots)
+                },
+            },
+        );
+    }
+
+    /// Discards every snapshot in every registered storage.
+    pub(crate) fn clear_all(world: &mut World) {
+        for storage in Self::storages(world) {
+            (storage.clear)(world);
+        }
+    }
+
+    /// Replaces every registered storage with the matching storage in `replacements`, or an
+    /// empty one if there is none, returning the storages they replaced.
+    pub(crate) fn swap_all(
+        world: &mut World,
+        mut replacements: HashMap<TypeId, ErasedSnapshots>,
+    ) -> HashMap<TypeId, ErasedSnapshots> {
+        let storages = world
+            .get_resource::<Self>()
+            .map(|storages| storages.0.clone())
+            .unwrap_or_default();
+
+        storages
+            .into_iter()
+            .filter_map(|(type_id, storage)| {
+                let previous = (storage.swap)(world, replacements.remove(&type_id))?;
+                Some((type_id, previous))
+            })
+            .collect()
+    }
+
+    fn storages(world: &World) -> Vec<SnapshotStorage> {
+        world
+            .get_resource::<Self>()
+            .map(|storages| storages.0.values().copied().collect())
+            .unwrap_or_default()
     }
 }
 
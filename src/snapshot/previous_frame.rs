@@ -0,0 +1,66 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{GgrsComponentSnapshots, Rollback, RollbackFrameCount};
+
+/// A [`SystemParam`] for reading the value a [`Component`] had on each [`Rollback`] entity at
+/// the end of the previous frame, such as to compute a velocity from a position.
+///
+/// Inside the [`GgrsSchedule`](`crate::GgrsSchedule`), values are read from the snapshot saved
+/// before the current frame was advanced, which is also what a rollback re-simulates from, so
+/// this is deterministic across re-simulation. Only components snapshot as themselves, such as
+/// with [`rollback_component_with_copy`](`crate::GgrsApp::rollback_component_with_copy`) or
+/// [`rollback_component_with_clone`](`crate::GgrsApp::rollback_component_with_clone`), can be
+/// read. Values are unavailable if the previous frame was not snapshot, such as with a
+/// [`SnapshotKeyframeInterval`](`crate::SnapshotKeyframeInterval`) above `1`.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, PrevFrame};
+/// #
+/// #[derive(Component, Clone, Copy)]
+/// struct Position(i32);
+///
+/// #[derive(Component)]
+/// struct Velocity(i32);
+///
+/// fn update_velocity(
+///     mut query: Query<(&Rollback, &Position, &mut Velocity)>,
+///     previous: PrevFrame<Position>,
+/// ) {
+///     for (rollback, position, mut velocity) in &mut query {
+///         if let Some(previous) = previous.get(rollback) {
+///             velocity.0 = position.0 - previous.0;
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// # app.add_systems(GgrsSchedule, update_velocity);
+/// ```
+#[derive(SystemParam)]
+pub struct PrevFrame<'w, T: Component> {
+    snapshots: Option<Res<'w, GgrsComponentSnapshots<T>>>,
+    frame: Res<'w, RollbackFrameCount>,
+}
+
+impl<'w, T: Component> PrevFrame<'w, T> {
+    /// The frame values are read from.
+    pub fn frame(&self) -> i32 {
+        self.frame.0 - 1
+    }
+
+    /// The value of `T` on the provided [`Rollback`] entity at the end of the previous frame.
+    /// Returns [`None`] if the entity did not have `T`, or the previous frame was not snapshot.
+    pub fn get(&self, rollback: &Rollback) -> Option<&T> {
+        self.snapshots.as_ref()?.peek(self.frame())?.get(rollback)
+    }
+
+    /// Iterate over every [`Rollback`] entity which had `T` at the end of the previous frame.
+    pub fn iter(&self) -> impl Iterator<Item = (&Rollback, &T)> + '_ {
+        self.snapshots
+            .as_ref()
+            .and_then(|snapshots| snapshots.peek(self.frame()))
+            .into_iter()
+            .flat_map(|snapshot| snapshot.iter())
+    }
+}
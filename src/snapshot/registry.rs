@@ -0,0 +1,317 @@
+use std::any::TypeId;
+
+use bevy::{
+    prelude::*,
+    reflect::{TypeInfo, TypeRegistry, VariantInfo},
+    utils::{HashMap, HashSet},
+};
+
+use crate::{FromReflectStrategy, ReflectStrategy, Strategy};
+
+/// Well known types which only affect presentation, or are derived from other rollback state,
+/// and so should not be rolled back. Matched by type name prefix, so this works regardless of
+/// which Bevy features are enabled.
+const RENDER_ONLY_TYPES: &[(&str, &str)] = &[
+    (
+        "bevy_render::view::visibility::",
+        "visibility only affects rendering",
+    ),
+    ("bevy_render::camera::", "cameras only affect rendering"),
+    (
+        "bevy_render::primitives::Aabb",
+        "bounding boxes are recomputed for rendering",
+    ),
+    (
+        "bevy_asset::handle::Handle<",
+        "asset handles refer to local, non-deterministic asset storage",
+    ),
+    (
+        "bevy_transform::components::global_transform::GlobalTransform",
+        "it is recomputed from Transform, which should be rolled back instead",
+    ),
+];
+
+/// Why the type called `type_name` should not be rolled back, if it only affects rendering.
+fn render_only_reason(type_name: &str) -> Option<&'static str> {
+    RENDER_ONLY_TYPES
+        .iter()
+        .find(|&&(prefix, _)| type_name.starts_with(prefix))
+        .map(|&(_, reason)| reason)
+}
+
+/// Describes how a type participates in rollback.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RollbackKind {
+    /// The type is snapshot as a [`Component`] on [`Rollback`](`crate::Rollback`) entities.
+    Component,
+    /// The type is snapshot as a [`Resource`].
+    Resource,
+}
+
+/// Information about a single type registered for rollback.
+#[derive(Clone, Copy, Debug)]
+pub struct RollbackRegistration {
+    /// Full name of the registered type.
+    pub type_name: &'static str,
+    /// Full name of the [`Strategy`] used to snapshot the type, or of the [`Plugin`] for types
+    /// which are not snapshot using a [`Strategy`].
+    pub strategy_name: &'static str,
+}
+
+/// A type reachable through the fields of a type snapshot using [`ReflectStrategy`] or
+/// [`FromReflectStrategy`], which is not registered in the [`AppTypeRegistry`].
+/// See [`RollbackRegistry::unregistered_reflect_types`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnregisteredReflectType {
+    /// Full name of the type registered for rollback.
+    pub rollback_type: &'static str,
+    /// Path of the unregistered type. For the items of a list, array or map, this is the path
+    /// of the collection.
+    pub type_path: &'static str,
+}
+
+/// A [`Resource`] which tracks every type registered for rollback, allowing duplicate or
+/// conflicting registrations to be caught while the [`App`] is being built.
+///
+/// This can also be read at runtime to check exactly which types will be snapshot,
+/// catching a forgotten registration in a test or at startup.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct RollbackRegistry {
+    components: HashMap<TypeId, RollbackRegistration>,
+    resources: HashMap<TypeId, RollbackRegistration>,
+}
+
+impl RollbackRegistry {
+    /// Records that the [`Target`](`Strategy::Target`) of `S` will be rolled back as `kind`.
+    ///
+    /// In debug builds, a warning is logged for well known types which only affect rendering,
+    /// such as `Visibility` or asset handles, since these should not be rolled back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type has already been registered as the same `kind`, since the type
+    /// would otherwise be saved and loaded twice per frame.
+    pub fn register<S>(&mut self, kind: RollbackKind) -> &mut Self
+    where
+        S: Strategy + 'static,
+        S::Target: 'static,
+    {
+        self.register_target::<S::Target>(std::any::type_name::<S>(), kind)
+    }
+
+    /// Records that `T` will be rolled back as `kind` by the snapshot method named `strategy_name`,
+    /// for rollback which is not described by a [`Strategy`]. See [`RollbackRegistry::register`].
+    pub(crate) fn register_target<T: 'static>(
+        &mut self,
+        strategy_name: &'static str,
+        kind: RollbackKind,
+    ) -> &mut Self {
+        let id = TypeId::of::<T>();
+
+        let registration = RollbackRegistration {
+            type_name: std::any::type_name::<T>(),
+            strategy_name,
+        };
+
+        let (same, other) = match kind {
+            RollbackKind::Component => (&mut self.components, &self.resources),
+            RollbackKind::Resource => (&mut self.resources, &self.components),
+        };
+
+        if let Some(existing) = same.get(&id) {
+            panic!(
+                "{} has already been registered for {:?} rollback using {}; registering it again using {} would snapshot it twice. Remove one of the registrations.",
+                registration.type_name, kind, existing.strategy_name, registration.strategy_name
+            );
+        }
+
+        #[cfg(debug_assertions)]
+        if let Some(reason) = render_only_reason(registration.type_name) {
+            warn!(
+                "{} has been registered for rollback, but {}. Rolling it back adds snapshot cost and can cause false desyncs.",
+                registration.type_name, reason
+            );
+        }
+
+        if other.contains_key(&id) {
+            warn!(
+                "{} has been registered for both Component and Resource rollback. This is allowed, but is usually a mistake.",
+                registration.type_name
+            );
+        }
+
+        same.insert(id, registration);
+
+        self
+    }
+
+    /// Returns `true` if `T` has been registered for rollback as `kind`.
+    pub fn is_registered<T: 'static>(&self, kind: RollbackKind) -> bool {
+        self.contains(TypeId::of::<T>(), kind)
+    }
+
+    /// Returns `true` if the type with the provided [`TypeId`] has been registered for rollback as `kind`.
+    pub(crate) fn contains(&self, id: TypeId, kind: RollbackKind) -> bool {
+        match kind {
+            RollbackKind::Component => self.components.contains_key(&id),
+            RollbackKind::Resource => self.resources.contains_key(&id),
+        }
+    }
+
+    /// Finds every type nested within the fields of a type snapshot using [`ReflectStrategy`] or
+    /// [`FromReflectStrategy`] which is not registered in `type_registry`, sorted by rollback type.
+    /// Such types cannot be fully reflected, such as by `DynamicScene`-based persistence, so they
+    /// should be registered too.
+    ///
+    /// Only rollback types which are themselves registered in `type_registry` are checked. Bevy's own
+    /// types are skipped, since their plugins register what they depend on.
+    ///
+    /// In debug builds, the [`GgrsPlugin`](`crate::GgrsPlugin`) logs a warning for these once the
+    /// [`App`] has been built.
+    pub fn unregistered_reflect_types(
+        &self,
+        type_registry: &TypeRegistry,
+    ) -> Vec<UnregisteredReflectType> {
+        let strategies = [
+            std::any::type_name::<ReflectStrategy<()>>(),
+            std::any::type_name::<FromReflectStrategy<()>>(),
+        ]
+        .map(|name| &name[..name.find('<').unwrap_or(0)]);
+
+        let mut unregistered = Vec::new();
+
+        for (&id, registration) in self.components.iter().chain(&self.resources) {
+            let checked = strategies
+                .iter()
+                .any(|strategy| registration.strategy_name.starts_with(strategy))
+                && !registration.type_name.starts_with("bevy_")
+                && type_registry.contains(id);
+
+            if !checked {
+                continue;
+            }
+
+            let mut missing = Vec::new();
+            find_unregistered(
+                id,
+                registration.type_name,
+                type_registry,
+                &mut HashSet::new(),
+                &mut missing,
+            );
+
+            unregistered.extend(
+                missing
+                    .into_iter()
+                    .map(|type_path| UnregisteredReflectType {
+                        rollback_type: registration.type_name,
+                        type_path,
+                    }),
+            );
+        }
+
+        unregistered.sort();
+        unregistered.dedup();
+
+        unregistered
+    }
+
+    /// All registered types which only affect rendering, such as `Visibility` or asset handles,
+    /// sorted by type name, along with why they should not be rolled back.
+    ///
+    /// In debug builds, a warning is logged for each of these as they are registered.
+    pub fn render_only_types(&self) -> Vec<(RollbackRegistration, &'static str)> {
+        let mut render_only = self
+            .components
+            .values()
+            .chain(self.resources.values())
+            .filter_map(|&registration| {
+                Some((registration, render_only_reason(registration.type_name)?))
+            })
+            .collect::<Vec<_>>();
+
+        render_only.sort_by_key(|(registration, _)| registration.type_name);
+        render_only
+    }
+
+    /// All types registered for [`Component`] rollback, sorted by type name.
+    pub fn components(&self) -> Vec<RollbackRegistration> {
+        sorted(&self.components)
+    }
+
+    /// All types registered for [`Resource`] rollback, sorted by type name.
+    pub fn resources(&self) -> Vec<RollbackRegistration> {
+        sorted(&self.resources)
+    }
+
+    /// The [`TypeId`] of every type registered for [`Resource`] rollback, sorted by type name.
+    pub(crate) fn resource_type_ids(&self) -> Vec<TypeId> {
+        let mut resources = self.resources.iter().collect::<Vec<_>>();
+        resources.sort_by_key(|(_, registration)| registration.type_name);
+        resources.into_iter().map(|(&id, _)| id).collect()
+    }
+}
+
+/// Collects the path of every type reachable from `id` which has no [`TypeInfo`] in `registry`.
+fn find_unregistered(
+    id: TypeId,
+    type_path: &'static str,
+    registry: &TypeRegistry,
+    visited: &mut HashSet<TypeId>,
+    missing: &mut Vec<&'static str>,
+) {
+    if !visited.insert(id) {
+        return;
+    }
+
+    let Some(info) = registry.get_type_info(id) else {
+        missing.push(type_path);
+        return;
+    };
+
+    let fields: Vec<(TypeId, &'static str)> = match info {
+        TypeInfo::Struct(info) => info
+            .iter()
+            .map(|field| (field.type_id(), field.type_path()))
+            .collect(),
+        TypeInfo::TupleStruct(info) => info
+            .iter()
+            .map(|field| (field.type_id(), field.type_path()))
+            .collect(),
+        TypeInfo::Tuple(info) => info
+            .iter()
+            .map(|field| (field.type_id(), field.type_path()))
+            .collect(),
+        TypeInfo::Enum(info) => info
+            .iter()
+            .flat_map(|variant| match variant {
+                VariantInfo::Struct(variant) => variant
+                    .iter()
+                    .map(|field| (field.type_id(), field.type_path()))
+                    .collect(),
+                VariantInfo::Tuple(variant) => variant
+                    .iter()
+                    .map(|field| (field.type_id(), field.type_path()))
+                    .collect(),
+                VariantInfo::Unit(_) => Vec::new(),
+            })
+            .collect(),
+        TypeInfo::List(info) => vec![(info.item_type_id(), info.type_path())],
+        TypeInfo::Array(info) => vec![(info.item_type_id(), info.type_path())],
+        TypeInfo::Map(info) => vec![
+            (info.key_type_id(), info.type_path()),
+            (info.value_type_id(), info.type_path()),
+        ],
+        TypeInfo::Value(_) => Vec::new(),
+    };
+
+    for (id, type_path) in fields {
+        find_unregistered(id, type_path, registry, visited, missing);
+    }
+}
+
+fn sorted(registrations: &HashMap<TypeId, RollbackRegistration>) -> Vec<RollbackRegistration> {
+    let mut registrations = registrations.values().copied().collect::<Vec<_>>();
+    registrations.sort_by_key(|registration| registration.type_name);
+    registrations
+}
@@ -0,0 +1,228 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::{ComponentMapEntitiesPlugin, LoadWorld, LoadWorldSet};
+
+/// A [`Plugin`] which keeps a relationship between entities coherent after a rollback, where
+/// [`Component`] `A` on one entity refers to another entity, which refers back using `B`, such as
+/// a `Follows` and `FollowedBy` pair. `A` and `B` may be the same type for symmetric
+/// relationships, such as `Partner`.
+///
+/// Both components are updated using [`MapEntities`], the same as with
+/// [`ComponentMapEntitiesPlugin`], so that references to respawned entities are updated together.
+/// Afterwards, any `A` or `B` still referring to at least one entity which does not exist is
+/// removed, rather than left dangling. This happens when one endpoint was despawned without the other
+/// side of the relationship being updated in the same frame, or when an endpoint is not a
+/// [`Rollback`](`crate::Rollback`) entity. Both components must still be snapshot, which
+/// [`GgrsApp::rollback_relationship`](`crate::GgrsApp::rollback_relationship`) also does.
+///
+/// A side referring to many entities, such as `FollowedBy`, can instead be pruned with
+/// [`GgrsApp::prune_relationship_with`](`crate::GgrsApp::prune_relationship_with`), so that its
+/// references to the remaining entities are kept.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::{prelude::*, ecs::entity::{MapEntities, EntityMapper}};
+/// # use bevy_ggrs::{prelude::*, RollbackRelationshipPlugin};
+/// #
+/// #[derive(Component, Clone)]
+/// struct Follows(Entity);
+///
+/// #[derive(Component, Clone)]
+/// struct FollowedBy(Vec<Entity>);
+///
+/// impl MapEntities for Follows {
+///     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+///         self.0 = entity_mapper.get_or_reserve(self.0);
+///     }
+/// }
+///
+/// impl MapEntities for FollowedBy {
+///     fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+///         for entity in &mut self.0 {
+///             *entity = entity_mapper.get_or_reserve(*entity);
+///         }
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.rollback_component_with_clone::<Follows>()
+///     .rollback_component_with_clone::<FollowedBy>()
+///     .add_plugins(RollbackRelationshipPlugin::<Follows, FollowedBy>::default())
+///     // keep following the leader while any follower remains
+///     .prune_relationship_with::<FollowedBy>(|followed_by| {
+///         followed_by.0.retain(|&entity| entity != Entity::PLACEHOLDER);
+///         !followed_by.0.is_empty()
+///     });
+/// ```
+pub struct RollbackRelationshipPlugin<A, B = A>
+where
+    A: Component + Clone + MapEntities,
+    B: Component + Clone + MapEntities,
+{
+    _phantom: PhantomData<(A, B)>,
+}
+
+impl<A, B> Default for RollbackRelationshipPlugin<A, B>
+where
+    A: Component + Clone + MapEntities,
+    B: Component + Clone + MapEntities,
+{
+    fn default() -> Self {
+        Self {
+            _phantom: default(),
+        }
+    }
+}
+
+impl<A, B> RollbackRelationshipPlugin<A, B>
+where
+    A: Component + Clone + MapEntities,
+    B: Component + Clone + MapEntities,
+{
+    /// Exclusive system which prunes or removes every `A` and `B` referring to an entity which
+    /// does not exist.
+    pub fn update(world: &mut World) {
+        remove_dangling::<A>(world);
+
+        if !Self::is_symmetric() {
+            remove_dangling::<B>(world);
+        }
+    }
+
+    fn is_symmetric() -> bool {
+        TypeId::of::<A>() == TypeId::of::<B>()
+    }
+}
+
+/// A [`Resource`] holding the function which removes the [`Entity::PLACEHOLDER`] references left
+/// in a `C` by a [`RollbackRelationshipPlugin`], see
+/// [`GgrsApp::prune_relationship_with`](`crate::GgrsApp::prune_relationship_with`).
+#[derive(Resource)]
+pub(crate) struct RelationshipPrune<C: Component>(pub(crate) fn(&mut C) -> bool);
+
+/// Prunes every `C` which refers to an entity which does not exist, removing it if any such
+/// reference remains.
+fn remove_dangling<C: Component + Clone + MapEntities>(world: &mut World) {
+    let holders = world
+        .query_filtered::<Entity, With<C>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    if holders.is_empty() {
+        return;
+    }
+
+    let prune = world
+        .get_resource::<RelationshipPrune<C>>()
+        .map(|prune| prune.0);
+
+    // mapping every living entity to itself, any entity added to the map by a value is dangling
+    let mut entity_map = world
+        .iter_entities()
+        .map(|entity| (entity.id(), entity.id()))
+        .collect::<HashMap<_, _>>();
+
+    let updates = EntityMapper::world_scope(&mut entity_map, world, |world, entity_mapper| {
+        let mut updates = Vec::new();
+
+        for &holder in &holders {
+            let original = world.get::<C>(holder).unwrap().clone();
+            let known = entity_mapper.get_map().len();
+
+            original.clone().map_entities(entity_mapper);
+
+            if entity_mapper.get_map().len() == known {
+                continue;
+            }
+
+            // the dangling entities are replaced by the placeholder for the prune function
+            let dangling = entity_mapper
+                .get_map()
+                .keys()
+                .copied()
+                .filter(|&entity| world.get_entity(entity).is_none())
+                .collect::<Vec<_>>();
+
+            entity_mapper
+                .get_map_mut()
+                .extend(dangling.iter().map(|&entity| (entity, Entity::PLACEHOLDER)));
+
+            let mut value = original.clone();
+            value.map_entities(entity_mapper);
+
+            // forget the dangling entities, so other values referring to them are found too
+            entity_mapper
+                .get_map_mut()
+                .retain(|&entity, _| world.get_entity(entity).is_some());
+
+            let kept = prune.is_some_and(|prune| prune(&mut value)) && {
+                let known = entity_mapper.get_map().len();
+                value.clone().map_entities(entity_mapper);
+
+                let pruned = entity_mapper.get_map().len() == known;
+
+                entity_mapper
+                    .get_map_mut()
+                    .retain(|&entity, _| world.get_entity(entity).is_some());
+
+                pruned
+            };
+
+            updates.push((holder, kept.then_some(value)));
+        }
+
+        updates
+    });
+
+    let pruned = updates.iter().filter(|(_, value)| value.is_some()).count();
+    let removed = updates.len() - pruned;
+
+    for (holder, value) in updates {
+        match value {
+            Some(value) => *world.get_mut::<C>(holder).unwrap() = value,
+            None => {
+                world.entity_mut(holder).remove::<C>();
+            }
+        }
+    }
+
+    if pruned + removed > 0 {
+        debug!(
+            "Pruned {} and removed {} {} referring to entities which no longer exist",
+            pruned,
+            removed,
+            bevy::utils::get_short_name(std::any::type_name::<C>())
+        );
+    }
+}
+
+impl<A, B> Plugin for RollbackRelationshipPlugin<A, B>
+where
+    A: Component + Clone + MapEntities,
+    B: Component + Clone + MapEntities,
+{
+    fn build(&self, app: &mut App) {
+        // either may already be mapped, such as through `update_component_with_map_entities`
+        if !app.is_plugin_added::<ComponentMapEntitiesPlugin<A>>() {
+            app.add_plugins(ComponentMapEntitiesPlugin::<A>::default());
+        }
+
+        if !app.is_plugin_added::<ComponentMapEntitiesPlugin<B>>() {
+            app.add_plugins(ComponentMapEntitiesPlugin::<B>::default());
+        }
+
+        app.add_systems(
+            LoadWorld,
+            Self::update
+                .in_set(LoadWorldSet::Mapping)
+                .after(ComponentMapEntitiesPlugin::<A>::update)
+                .after(ComponentMapEntitiesPlugin::<B>::update),
+        );
+    }
+}
@@ -1,12 +1,16 @@
 use crate::{
-    GgrsResourceSnapshots, LoadWorld, LoadWorldSet, RollbackFrameCount, SaveWorld, SaveWorldSet,
-    Strategy,
+    GgrsResourceSnapshots, LoadWorld, LoadWorldSet, RollbackFrameCount, RollbackKind,
+    RollbackRegistry, SaveWorld, SaveWorldSet, SnapshotMemoryUsage, SnapshotStorages, Strategy,
 };
 use bevy::prelude::*;
 use std::marker::PhantomData;
 
 /// A [`Plugin`] which manages snapshots for a [`Resource`] using a provided [`Strategy`].
 ///
+/// The absence of the [`Resource`] is also rolled back: loading a frame saved without it removes
+/// it from the [`World`]. Since this is rarely intended, a warning is logged the first time it is
+/// absent when saving.
+///
 /// # Examples
 /// ```rust
 /// # use bevy::prelude::*;
@@ -59,7 +63,18 @@ where
         mut snapshots: ResMut<GgrsResourceSnapshots<S::Target, S::Stored>>,
         frame: Res<RollbackFrameCount>,
         resource: Option<Res<S::Target>>,
+        mut warned_absent: Local<bool>,
     ) {
+        if resource.is_none() && !*warned_absent {
+            *warned_absent = true;
+
+            warn!(
+                "{} is registered for rollback, but was not present when frame {} was saved, so it will be removed when loading that frame. Insert it before the session starts if it should always exist.",
+                std::any::type_name::<S::Target>(),
+                frame.0
+            );
+        }
+
         snapshots.push(frame.0, resource.map(|res| S::store(res.as_ref())));
 
         trace!(
@@ -97,12 +112,20 @@ where
     S::Stored: Send + Sync + 'static,
 {
     fn build(&self, app: &mut App) {
+        app.world
+            .get_resource_or_insert_with::<RollbackRegistry>(default)
+            .register::<S>(RollbackKind::Resource);
+
+        SnapshotStorages::register::<S::Target, Option<S::Stored>>(&mut app.world);
+
         app.init_resource::<GgrsResourceSnapshots<S::Target, S::Stored>>()
+            .init_resource::<SnapshotMemoryUsage>()
             .add_systems(
                 SaveWorld,
                 (
                     GgrsResourceSnapshots::<S::Target, S::Stored>::discard_old_snapshots,
                     Self::save,
+                    GgrsResourceSnapshots::<S::Target, S::Stored>::record_memory,
                 )
                     .chain()
                     .in_set(SaveWorldSet::Snapshot),
@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{AdvanceWorld, GgrsSchedule, LoadWorld, SaveWorld};
+use crate::{AdvanceWorld, InRollbackSchedule, LoadWorld, RollbackSchedules, SaveWorld};
 
 /// Set for ordering systems during the [`LoadWorld`] schedule.
 /// The most common option is [`LoadWorldSet::Data`], which is where [`Component`]
 /// and [`Resource`] snapshots are loaded and applied to the [`World`].
+/// To run logic after every load, use [`LoadWorldSet::PostLoad`].
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub enum LoadWorldSet {
     /// Recreate the [`Entity`] graph as it was during the frame to be rolled back to.
@@ -29,10 +30,24 @@ pub enum LoadWorldSet {
     /// which had to be recreated could not use the same ID, so any data referring to that ID is now invalid.
     /// Once this set completes, all data should now be coherent with the [`World`].
     Mapping,
+    /// Flush any deferred operations
+    MappingFlush,
+    /// Runs once after every load, with the [`World`] fully restored and coherent.
+    /// This is the place to rebuild any derived state (caches, acceleration structures)
+    /// from the restored rollback data. Systems here must be deterministic.
+    PostLoad,
 }
 
+/// Set for ordering systems during the [`SaveWorld`] schedule.
+/// To run logic before every save, use [`SaveWorldSet::Prepare`].
 #[derive(SystemSet, Hash, Debug, PartialEq, Eq, Clone)]
 pub enum SaveWorldSet {
+    /// Runs once before every save, prior to any checksums or snapshots being taken.
+    /// This is the place to flush any pending writes into rollback data, such as lazily
+    /// maintained state which must be committed to the [`World`] before it is captured.
+    Prepare,
+    /// Flush any deferred operations
+    PrepareFlush,
     /// Generate checksums for any tracked data.
     ///
     /// Within this set, it is expected that all data which will participate in the
@@ -67,12 +82,20 @@ impl Plugin for SnapshotSetPlugin {
                 LoadWorldSet::Data,
                 LoadWorldSet::DataFlush,
                 LoadWorldSet::Mapping,
+                LoadWorldSet::MappingFlush,
+                LoadWorldSet::PostLoad,
             )
                 .chain(),
         )
         .configure_sets(
             SaveWorld,
-            (SaveWorldSet::Checksum, SaveWorldSet::Snapshot).chain(),
+            (
+                SaveWorldSet::Prepare,
+                SaveWorldSet::PrepareFlush,
+                SaveWorldSet::Checksum,
+                SaveWorldSet::Snapshot,
+            )
+                .chain(),
         )
         .configure_sets(
             AdvanceWorld,
@@ -85,6 +108,8 @@ impl Plugin for SnapshotSetPlugin {
         )
         .add_systems(LoadWorld, apply_deferred.in_set(LoadWorldSet::EntityFlush))
         .add_systems(LoadWorld, apply_deferred.in_set(LoadWorldSet::DataFlush))
+        .add_systems(LoadWorld, apply_deferred.in_set(LoadWorldSet::MappingFlush))
+        .add_systems(SaveWorld, apply_deferred.in_set(SaveWorldSet::PrepareFlush))
         .add_systems(
             AdvanceWorld,
             apply_deferred
@@ -99,7 +124,19 @@ impl Plugin for SnapshotSetPlugin {
         )
         .add_systems(
             AdvanceWorld,
-            (|world: &mut World| world.run_schedule(GgrsSchedule)).in_set(AdvanceWorldSet::Main),
+            (|world: &mut World| {
+                let schedules = world
+                    .get_resource::<RollbackSchedules>()
+                    .cloned()
+                    .unwrap_or_default();
+
+                world.insert_resource(InRollbackSchedule);
+                for schedule in schedules.iter() {
+                    world.run_schedule(schedule);
+                }
+                world.remove_resource::<InRollbackSchedule>();
+            })
+            .in_set(AdvanceWorldSet::Main),
         );
     }
 }
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use bevy::{
     prelude::{FromWorld, World},
-    reflect::Reflect,
+    reflect::{FromReflect, Reflect, ReflectRef},
 };
 
 /// Describes how to efficiently transform a [`Target`](`Strategy::Target`) into a
@@ -92,3 +92,103 @@ impl<T: Reflect + FromWorld> Strategy for ReflectStrategy<T> {
         target
     }
 }
+
+/// A [`Strategy`] based on [`FromReflect`]
+///
+/// Unlike [`ReflectStrategy`], a fresh value is constructed with [`FromReflect`] whenever
+/// [applying](`Reflect::apply`) the snapshot in place would be incorrect: when an enum anywhere
+/// within the value, including within a list, array or map, is in a different variant than it
+/// was when stored, or when a list or map has a different length. Loading never requires
+/// [`FromWorld`].
+pub struct FromReflectStrategy<T: FromReflect>(PhantomData<T>);
+
+impl<T: FromReflect> Strategy for FromReflectStrategy<T> {
+    type Target = T;
+
+    type Stored = Box<dyn Reflect>;
+
+    #[inline(always)]
+    fn store(target: &Self::Target) -> Self::Stored {
+        target.as_reflect().clone_value()
+    }
+
+    #[inline(always)]
+    fn update(target: &mut Self::Target, stored: &Self::Stored) {
+        if variants_match(target.as_reflect(), stored.as_ref()) {
+            target.apply(stored.as_ref());
+        } else {
+            *target = Self::load(stored);
+        }
+    }
+
+    #[inline(always)]
+    fn load(stored: &Self::Stored) -> Self::Target {
+        T::from_reflect(stored.as_ref()).unwrap_or_else(|| {
+            panic!(
+                "Unable to load a snapshot of {} using FromReflect",
+                std::any::type_name::<T>()
+            )
+        })
+    }
+}
+
+/// Whether every enum within `target` is in the same variant as its counterpart in `stored`, and
+/// every list and map within it has the same length, so `stored` can be applied in place.
+fn variants_match(target: &dyn Reflect, stored: &dyn Reflect) -> bool {
+    match (target.reflect_ref(), stored.reflect_ref()) {
+        (ReflectRef::Enum(target), ReflectRef::Enum(stored)) => {
+            target.variant_name() == stored.variant_name()
+                && target.field_len() == stored.field_len()
+                && (0..target.field_len()).all(|index| {
+                    match (target.field_at(index), stored.field_at(index)) {
+                        (Some(target), Some(stored)) => variants_match(target, stored),
+                        _ => false,
+                    }
+                })
+        }
+        (ReflectRef::Struct(target), ReflectRef::Struct(stored)) => {
+            (0..target.field_len()).all(|index| {
+                match (target.field_at(index), target.name_at(index)) {
+                    (Some(field), Some(name)) => stored
+                        .field(name)
+                        .map_or(true, |stored| variants_match(field, stored)),
+                    _ => true,
+                }
+            })
+        }
+        (ReflectRef::TupleStruct(target), ReflectRef::TupleStruct(stored)) => {
+            (0..target.field_len()).all(|index| match (target.field(index), stored.field(index)) {
+                (Some(target), Some(stored)) => variants_match(target, stored),
+                _ => true,
+            })
+        }
+        (ReflectRef::Tuple(target), ReflectRef::Tuple(stored)) => {
+            (0..target.field_len()).all(|index| match (target.field(index), stored.field(index)) {
+                (Some(target), Some(stored)) => variants_match(target, stored),
+                _ => true,
+            })
+        }
+        // applying a shorter list leaves the extra elements of the target in place
+        (ReflectRef::List(target), ReflectRef::List(stored)) => {
+            target.len() == stored.len()
+                && target
+                    .iter()
+                    .zip(stored.iter())
+                    .all(|(target, stored)| variants_match(target, stored))
+        }
+        (ReflectRef::Array(target), ReflectRef::Array(stored)) => target
+            .iter()
+            .zip(stored.iter())
+            .all(|(target, stored)| variants_match(target, stored)),
+        // likewise, applying a map never removes entries missing from the stored map
+        (ReflectRef::Map(target), ReflectRef::Map(stored)) => {
+            target.len() == stored.len()
+                && stored.iter().all(|(key, stored)| {
+                    target
+                        .get(key)
+                        .is_some_and(|target| variants_match(target, stored))
+                })
+        }
+        _ => true,
+    }
+}
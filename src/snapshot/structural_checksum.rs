@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+
+use crate::{Checksum, ChecksumPlugin, Rollback, RollbackRegistry, SaveWorld, SaveWorldSet};
+
+/// A cheap summary of the structure of the [`World`] for the frame most recently saved: how many
+/// [`Rollback`] entities exist, and which [`Resources`](`Resource`) registered for rollback are
+/// present. Peers whose structural checksums differ have diverged grossly, such as by spawning a
+/// different number of entities, which is usually a spawn or despawn determinism bug.
+///
+/// With the [`StructuralChecksumPlugin`] added, this is stored in the upper 64 bits of every
+/// [`Checksum`], so it is exchanged alongside the full checksum. Use
+/// [`from_checksum`](`Self::from_checksum`) on the checksums of a
+/// [`GgrsEvent::DesyncDetected`](`ggrs::GgrsEvent::DesyncDetected`) to tell a structural
+/// divergence apart from a divergence in component values.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, StructuralChecksum, StructuralChecksumPlugin};
+/// #
+/// # type MyConfig = GgrsConfig<u8>;
+/// #
+/// fn report_desyncs(mut session: ResMut<Session<MyConfig>>) {
+///     let Session::P2P(session) = session.as_mut() else {
+///         return;
+///     };
+///
+///     for event in session.events() {
+///         let GgrsEvent::DesyncDetected {
+///             frame,
+///             local_checksum,
+///             remote_checksum,
+///             ..
+///         } = event
+///         else {
+///             continue;
+///         };
+///
+///         let local = StructuralChecksum::from_checksum(local_checksum);
+///         let remote = StructuralChecksum::from_checksum(remote_checksum);
+///
+///         if local != remote {
+///             error!("Frame {frame} diverged structurally: {local:?} locally, {remote:?} remotely");
+///         }
+///     }
+/// }
+/// # let mut app = App::new();
+/// app.add_plugins(StructuralChecksumPlugin)
+///     .add_systems(Update, report_desyncs);
+/// ```
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StructuralChecksum {
+    /// The number of [`Rollback`] entities.
+    pub rollback_entities: u32,
+    /// One bit per [`Resource`] registered for rollback, in order of type name, set if the
+    /// [`Resource`] is present. With more than 32 registered resources, bits are shared.
+    pub resources_present: u32,
+}
+
+impl StructuralChecksum {
+    /// Reads the structural checksum stored in a [`Checksum`] by the [`StructuralChecksumPlugin`].
+    pub fn from_checksum(checksum: u128) -> Self {
+        let bits = (checksum >> 64) as u64;
+
+        Self {
+            rollback_entities: (bits >> 32) as u32,
+            resources_present: bits as u32,
+        }
+    }
+
+    /// The upper 64 bits this structural checksum occupies within a [`Checksum`].
+    pub fn to_checksum_bits(&self) -> u128 {
+        let bits = (u64::from(self.rollback_entities) << 32) | u64::from(self.resources_present);
+
+        u128::from(bits) << 64
+    }
+}
+
+/// A [`Plugin`] which computes a [`StructuralChecksum`] every time the [`World`] is saved, and
+/// stores it in the upper 64 bits of the [`Checksum`].
+///
+/// The checksums of [`ComponentChecksumPlugin`](`crate::ComponentChecksumPlugin`) and other
+/// built in plugins only occupy the lower 64 bits, so they are unaffected. Custom
+/// [`ChecksumParts`](`crate::ChecksumPart`) using all 128 bits still contribute to the
+/// [`Checksum`], but make [`StructuralChecksum::from_checksum`] unreliable.
+pub struct StructuralChecksumPlugin;
+
+impl StructuralChecksumPlugin {
+    /// A [`System`] which computes the [`StructuralChecksum`] and adds it to the [`Checksum`].
+    pub fn update(world: &mut World) {
+        let rollback_entities = world
+            .query_filtered::<(), With<Rollback>>()
+            .iter(world)
+            .count() as u32;
+
+        let resources_present = world
+            .get_resource::<RollbackRegistry>()
+            .map(|registry| {
+                registry
+                    .resource_type_ids()
+                    .into_iter()
+                    .enumerate()
+                    .filter(|&(_, type_id)| {
+                        world
+                            .components()
+                            .get_resource_id(type_id)
+                            .is_some_and(|id| world.get_resource_by_id(id).is_some())
+                    })
+                    .fold(0, |bits, (index, _)| bits | (1 << (index % 32)))
+            })
+            .unwrap_or_default();
+
+        let structural = StructuralChecksum {
+            rollback_entities,
+            resources_present,
+        };
+
+        trace!("Frame has structural checksum {structural:?}");
+
+        if let Some(mut checksum) = world.get_resource_mut::<Checksum>() {
+            checksum.0 ^= structural.to_checksum_bits();
+        }
+
+        world.insert_resource(structural);
+    }
+}
+
+impl Plugin for StructuralChecksumPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StructuralChecksum>().add_systems(
+            SaveWorld,
+            Self::update
+                .after(ChecksumPlugin::update)
+                .before(SaveWorldSet::Snapshot),
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::hash::{Hash, Hasher};
+
+use bevy::{
+    prelude::*,
+    transform::systems::{propagate_transforms, sync_simple_transforms},
+};
+
+use crate::{checksum_hasher, LoadWorld, LoadWorldSet, Rollback};
+
+/// Hashes every field of a [`Transform`] bit-for-bit using [`checksum_hasher`], suitable for
+/// use with [`ComponentChecksumPlugin`](`crate::ComponentChecksumPlugin`).
+///
+/// Every NaN is hashed as [`f32::NAN`], since its payload bits may differ between platforms even
+/// when the simulation is otherwise identical.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::transform_hasher;
+/// let nan = Transform::from_xyz(f32::NAN, 0.0, 0.0);
+/// let negative_nan = Transform::from_xyz(-f32::NAN, 0.0, 0.0);
+///
+/// assert_eq!(transform_hasher(&nan), transform_hasher(&negative_nan));
+/// ```
+pub fn transform_hasher(transform: &Transform) -> u64 {
+    let mut hasher = checksum_hasher();
+
+    for value in transform
+        .translation
+        .to_array()
+        .into_iter()
+        .chain(transform.rotation.to_array())
+        .chain(transform.scale.to_array())
+    {
+        let value = if value.is_nan() { f32::NAN } else { value };
+        value.to_bits().hash(&mut hasher);
+    }
+
+    hasher.finish()
+}
+
+/// A [`Plugin`] which recomputes [`GlobalTransform`] from [`Transform`] at the end of every
+/// [`LoadWorld`], after [`LoadWorldSet::PostLoad`].
+///
+/// [`GlobalTransform`] should not be rolled back, since Bevy derives it from [`Transform`]. However,
+/// Bevy only propagates transforms in [`PostUpdate`], so after a rollback, systems in the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`) would otherwise read the [`GlobalTransform`] of the
+/// frame before it. Rollback entities which were respawned without a [`GlobalTransform`] receive one.
+///
+/// Propagation is only performed once per load, not once per re-simulated frame, so systems which
+/// move entities and then read their [`GlobalTransform`] within the same frame must still propagate
+/// it themselves. See [`GgrsApp::propagate_transforms_after_load`](`crate::GgrsApp::propagate_transforms_after_load`).
+pub struct TransformPropagationPlugin;
+
+impl TransformPropagationPlugin {
+    /// A system which adds a [`GlobalTransform`] to any [`Rollback`] entity with a [`Transform`] but
+    /// without a [`GlobalTransform`], such as one respawned during the load.
+    pub fn insert_missing(
+        mut commands: Commands,
+        query: Query<Entity, (With<Rollback>, With<Transform>, Without<GlobalTransform>)>,
+    ) {
+        for entity in &query {
+            commands.entity(entity).insert(GlobalTransform::default());
+        }
+    }
+}
+
+impl Plugin for TransformPropagationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            LoadWorld,
+            (
+                Self::insert_missing,
+                apply_deferred,
+                (sync_simple_transforms, propagate_transforms),
+            )
+                .chain()
+                .after(LoadWorldSet::PostLoad),
+        );
+    }
+}
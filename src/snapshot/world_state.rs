@@ -0,0 +1,134 @@
+use crate::{
+    GgrsSnapshots, LoadWorld, LoadWorldSet, RollbackFrameCount, SaveWorld, SaveWorldSet,
+    SnapshotStorages,
+};
+use bevy::prelude::*;
+use std::sync::Arc;
+
+/// Typical [`Resource`] used to store snapshots of state captured by a [`WorldStateSnapshotPlugin`].
+pub type GgrsWorldStateSnapshots<S> = GgrsSnapshots<WorldStateRollback<S>, S>;
+
+type SaveWorldState<S> = Arc<dyn Fn(&World) -> S + Send + Sync>;
+type LoadWorldState<S> = Arc<dyn Fn(&mut World, &S) + Send + Sync>;
+
+/// A [`Resource`] holding the functions used by a [`WorldStateSnapshotPlugin`] to capture and
+/// restore its state.
+#[derive(Resource)]
+pub struct WorldStateRollback<S> {
+    save: SaveWorldState<S>,
+    load: LoadWorldState<S>,
+}
+
+/// A [`Plugin`] which rolls back arbitrary state using a pair of functions, for state which is
+/// not modeled as a [`Component`] or [`Resource`], such as the internals of a third party plugin.
+///
+/// `save` captures the state of the [`World`] each frame, and is stored alongside every other
+/// snapshot. `load` is passed the [`World`] and the state captured for the frame being loaded,
+/// and must restore it. `load` runs in [`LoadWorldSet::Data`], while this plugin's storage is
+/// temporarily removed from the [`World`].
+///
+/// Each type `S` may only be registered once. Captured state is not included in the
+/// [`SnapshotMemoryUsage`](`crate::SnapshotMemoryUsage`), since its size is unknown.
+///
+/// # Examples
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_ggrs::{prelude::*, WorldStateSnapshotPlugin};
+/// #
+/// /// A physics engine which keeps its own state, and cannot be cloned
+/// #[derive(Resource)]
+/// struct PhysicsWorld {
+///     steps: u64,
+///     // ...
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugins(WorldStateSnapshotPlugin::new(
+///     |world: &World| world.resource::<PhysicsWorld>().steps,
+///     |world: &mut World, steps: &u64| world.resource_mut::<PhysicsWorld>().steps = *steps,
+/// ));
+/// ```
+pub struct WorldStateSnapshotPlugin<S> {
+    save: SaveWorldState<S>,
+    load: LoadWorldState<S>,
+}
+
+impl<S> WorldStateSnapshotPlugin<S> {
+    /// Creates a plugin which captures state using `save`, and restores it using `load`.
+    pub fn new(
+        save: impl Fn(&World) -> S + Send + Sync + 'static,
+        load: impl Fn(&mut World, &S) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            save: Arc::new(save),
+            load: Arc::new(load),
+        }
+    }
+}
+
+impl<S> WorldStateSnapshotPlugin<S>
+where
+    S: Send + Sync + 'static,
+{
+    pub fn save(world: &mut World) {
+        let frame = world.resource::<RollbackFrameCount>().0;
+        let save = world.resource::<WorldStateRollback<S>>().save.clone();
+
+        let state = save(world);
+
+        world
+            .resource_mut::<GgrsWorldStateSnapshots<S>>()
+            .push(frame, state);
+
+        trace!(
+            "Snapshot {}",
+            bevy::utils::get_short_name(std::any::type_name::<S>())
+        );
+    }
+
+    pub fn load(world: &mut World) {
+        let frame = world.resource::<RollbackFrameCount>().0;
+        let load = world.resource::<WorldStateRollback<S>>().load.clone();
+
+        world.resource_scope(|world, mut snapshots: Mut<GgrsWorldStateSnapshots<S>>| {
+            load(world, snapshots.rollback(frame).get());
+        });
+
+        trace!(
+            "Rolled back {}",
+            bevy::utils::get_short_name(std::any::type_name::<S>())
+        );
+    }
+}
+
+impl<S> Plugin for WorldStateSnapshotPlugin<S>
+where
+    S: Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        if app.world.contains_resource::<WorldStateRollback<S>>() {
+            panic!(
+                "{} has already been registered for rollback using save and load functions; registering it again would snapshot it twice. Remove one of the registrations.",
+                std::any::type_name::<S>()
+            );
+        }
+
+        SnapshotStorages::register::<WorldStateRollback<S>, S>(&mut app.world);
+
+        app.insert_resource(WorldStateRollback::<S> {
+            save: self.save.clone(),
+            load: self.load.clone(),
+        })
+        .init_resource::<GgrsWorldStateSnapshots<S>>()
+        .add_systems(
+            SaveWorld,
+            (
+                GgrsWorldStateSnapshots::<S>::discard_old_snapshots,
+                Self::save,
+            )
+                .chain()
+                .in_set(SaveWorldSet::Snapshot),
+        )
+        .add_systems(LoadWorld, Self::load.in_set(LoadWorldSet::Data));
+    }
+}
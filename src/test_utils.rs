@@ -0,0 +1,71 @@
+//! Helpers for writing determinism tests for games built with `bevy_ggrs`.
+
+use bevy::prelude::*;
+use ggrs::{Config, GameStateCell, GgrsRequest, InputStatus};
+
+use crate::{schedule_systems::handle_requests, Checksum, GgrsPlayers, RollbackFrameCount};
+
+/// Advances two [`Apps`](`App`) through `frames` frames with identical inputs, asserting that
+/// their [`Checksums`](`Checksum`) match after every frame. `inputs` is called with each frame
+/// number and must return the input of every player for that frame, in handle order.
+///
+/// No [`Session`](`crate::Session`) is required: requests are issued directly, as a GGRS session
+/// would without any rollbacks, with [`GgrsPlayers`] listing every player as local. Both apps are
+/// updated once beforehand to run their startup systems.
+/// Only data added to the checksum (such as with
+/// [`checksum_component_with_hash`](`crate::GgrsApp::checksum_component_with_hash`)) is compared.
+///
+/// # Panics
+///
+/// Panics on the first frame where the two checksums differ.
+pub fn assert_in_sync<C: Config>(
+    app_a: &mut App,
+    app_b: &mut App,
+    frames: usize,
+    mut inputs: impl FnMut(i32) -> Vec<C::Input>,
+) {
+    app_a.update();
+    app_b.update();
+
+    for _ in 0..frames {
+        let frame = app_a.world.resource::<RollbackFrameCount>().0;
+        let frame_inputs = inputs(frame + 1);
+
+        let checksum_a = advance::<C>(&mut app_a.world, frame, &frame_inputs);
+        let checksum_b = advance::<C>(&mut app_b.world, frame, &frame_inputs);
+
+        assert_eq!(
+            checksum_a, checksum_b,
+            "Checksums diverged when saving frame {frame}"
+        );
+    }
+}
+
+/// Saves the current frame and advances once, returning the checksum of the saved frame.
+fn advance<C: Config>(world: &mut World, frame: i32, inputs: &[C::Input]) -> u128 {
+    let inputs = inputs
+        .iter()
+        .map(|&input| (input, InputStatus::Confirmed))
+        .collect();
+
+    // as published by the plugin for a session where every player is local
+    world.insert_resource(GgrsPlayers {
+        num_players: inputs.len(),
+        local: (0..inputs.len()).collect(),
+    });
+
+    let requests = vec![
+        GgrsRequest::<C>::SaveGameState {
+            cell: GameStateCell::default(),
+            frame,
+        },
+        GgrsRequest::AdvanceFrame { inputs },
+    ];
+
+    handle_requests(requests, world);
+
+    world
+        .get_resource::<Checksum>()
+        .map(|checksum| checksum.0)
+        .unwrap_or_default()
+}
@@ -3,12 +3,19 @@ use std::time::Duration;
 use bevy::prelude::*;
 
 use crate::{
-    AdvanceWorld, AdvanceWorldSet, CloneStrategy, ResourceSnapshotPlugin, RollbackFrameCount,
-    DEFAULT_FPS,
+    AdvanceWorld, AdvanceWorldSet, CloneStrategy, LocalMode, ResourceSnapshotPlugin,
+    RollbackFrameCount, DEFAULT_FPS,
 };
 
 /// [`Resource`] describing the rate at which the [`AdvanceWorld`] will run.
-#[derive(Resource, Clone, Copy, Debug, Hash, Deref)]
+///
+/// This is read on every update, so it does not need to be known when the [`App`] is built. Peers
+/// who agree on a rate when the session starts, such as a lower rate for cross-region play, can
+/// insert it before building their session with
+/// [`GgrsSessionBuilder`](`crate::GgrsSessionBuilder`), which matches the session to it. The rate
+/// must only change between sessions, and must be the same on every peer. Since it is included in
+/// the [`Checksum`](`crate::Checksum`), peers running at different rates are reported as desynced.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Hash, Deref)]
 pub struct RollbackFrameRate(pub(crate) usize);
 
 impl Default for RollbackFrameRate {
@@ -17,11 +24,135 @@ impl Default for RollbackFrameRate {
     }
 }
 
+impl RollbackFrameRate {
+    /// A rate of `fps` rollback frames per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is `0`.
+    pub fn new(fps: usize) -> Self {
+        assert!(fps > 0, "Rollback schedule FPS must be at least 1");
+
+        Self(fps)
+    }
+
+    /// The duration of a single rollback frame. Useful for aligning other fixed timestep
+    /// logic with the [`GgrsSchedule`](`crate::GgrsSchedule`).
+    pub fn tick_duration(&self) -> Duration {
+        Duration::from_secs_f64(1. / self.0 as f64)
+    }
+}
+
+/// [`Resource`] limiting how much real time a single app update can add towards advancing
+/// rollback frames, preventing a storm of catch-up frames after a long stall.
+///
+/// Browsers throttle or suspend background tabs, so a wasm build may see a single very large
+/// [`delta`](`Time::delta`) when its tab regains focus. Bevy already limits this through the
+/// `max_delta` of [`Time<Virtual>`], but its default still allows many rollback frames in a single
+/// update. On `wasm32`, the [`GgrsPlugin`](`crate::GgrsPlugin`) inserts the default of 100
+/// milliseconds. Elsewhere, this is only applied once inserted.
+/// See [`GgrsApp::set_max_frame_delta`](`crate::GgrsApp::set_max_frame_delta`).
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Hash, Deref)]
+pub struct MaxFrameDelta(pub Duration);
+
+impl Default for MaxFrameDelta {
+    fn default() -> Self {
+        Self(Duration::from_millis(100))
+    }
+}
+
+/// [`Resource`] setting how much longer each rollback frame lasts while a
+/// [`P2PSession`](`ggrs::P2PSession`) is ahead of its remote peers, letting them catch up.
+/// Defaults to `1.1`, running 10% slower. Ignored if a [`TimeDilation`] is inserted.
+///
+/// Since each frame lasts longer rather than being skipped, a client which stays ahead keeps
+/// advancing at the reduced rate instead of stalling.
+/// See [`GgrsApp::set_run_slow_factor`](`crate::GgrsApp::set_run_slow_factor`).
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Deref)]
+pub struct RunSlowFactor(f64);
+
+impl Default for RunSlowFactor {
+    fn default() -> Self {
+        Self(1.1)
+    }
+}
+
+impl RunSlowFactor {
+    /// Each rollback frame lasts `factor` times as long while running slow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `factor` is less than `1`, or not finite, since frames would otherwise last no
+    /// time at all.
+    pub fn new(factor: f64) -> Self {
+        assert!(
+            factor.is_finite() && factor >= 1.,
+            "Run slow factor must be finite and at least 1, got {factor}"
+        );
+
+        Self(factor)
+    }
+}
+
+/// [`Resource`] which enables continuous time dilation for [`P2PSessions`](`ggrs::P2PSession`).
+/// Rather than only running slow while ahead of remote peers, the duration of each rollback
+/// frame is adjusted proportionally to how many frames ahead (slower) or behind (faster) this
+/// client is, reducing the input latency caused by drifting clocks.
+///
+/// Without this resource, a client which is ahead simply runs 10% slower until it is not, see
+/// [`RunSlowFactor`].
+/// See [`GgrsApp::set_time_dilation`](`crate::GgrsApp::set_time_dilation`).
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct TimeDilation {
+    /// Fractional change in frame duration per frame ahead or behind, outside the deadband.
+    pub gain: f64,
+    /// Number of frames ahead or behind which are tolerated without any adjustment.
+    pub deadband: u32,
+    /// Maximum fractional change in frame duration, in either direction. Must be less than `1`,
+    /// so frames never last zero or negative time.
+    pub max_adjustment: f64,
+}
+
+impl Default for TimeDilation {
+    fn default() -> Self {
+        Self {
+            gain: 0.02,
+            deadband: 0,
+            max_adjustment: 0.1,
+        }
+    }
+}
+
+impl TimeDilation {
+    /// The factor to apply to the duration of a rollback frame when `frames_ahead` of remote peers.
+    pub fn factor(&self, frames_ahead: i32) -> f64 {
+        let deadband = self.deadband as i32;
+
+        let error = if frames_ahead > deadband {
+            frames_ahead - deadband
+        } else if frames_ahead < -deadband {
+            frames_ahead + deadband
+        } else {
+            0
+        };
+
+        let adjustment =
+            (self.gain * error as f64).clamp(-self.max_adjustment, self.max_adjustment);
+
+        1. + adjustment
+    }
+}
+
 /// A [`Time`] type for use with GGRS. This time is guaranteed to be in-sync with
 /// all peers, and reflect that exactly [`RollbackFrameCount`] frames have passed at
 /// the [`RollbackFrameRate`] rate. Note that in the [`GgrsSchedule`](`crate::GgrsSchedule`),
 /// this is the [default time](`Time<()>`).
 ///
+/// Both [`delta`](`Time::delta`) and [`elapsed`](`Time::elapsed`) are derived purely from
+/// [`RollbackFrameCount`] and [`RollbackFrameRate`], so they are identical on every peer and
+/// rewind along with the rest of the world when a snapshot is loaded. Systems in the
+/// [`GgrsSchedule`](`crate::GgrsSchedule`) should prefer this over [`Time<Real>`] or [`Time<Virtual>`].
+///
 /// # Examples
 /// ```rust
 /// # use bevy::prelude::*;
@@ -56,7 +187,14 @@ impl GgrsTimePlugin {
         mut time: ResMut<Time<GgrsTime>>,
         framerate: Res<RollbackFrameRate>,
         frame: Res<RollbackFrameCount>,
+        local_mode: Option<Res<LocalMode>>,
     ) {
+        // local frames follow the real time elapsed, rather than the frame count
+        if let Some(delta) = local_mode.and_then(|local| local.frame_delta()) {
+            time.advance_by(delta);
+            return;
+        }
+
         let this_frame = frame.0 as u64;
         let framerate = framerate.0 as u64;
 
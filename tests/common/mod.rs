@@ -0,0 +1,86 @@
+//! The fixture shared by the integration tests: a rolled back [`Counter`], increased by the input
+//! of the first player every frame of a [`SyncTestSession`](`ggrs::SyncTestSession`), in an
+//! [`App`] under [`MinimalPlugins`]. Not every test uses every part of it.
+#![allow(dead_code)]
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_ggrs::{prelude::*, LocalInputs, LocalPlayers};
+use ggrs::{Config, PlayerType, SessionBuilder};
+
+pub type TestConfig = GgrsConfig<u8>;
+
+#[derive(Component, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub struct Counter(pub u32);
+
+/// Every local player presses their button, with an input of `1`.
+pub fn read_local_inputs(mut commands: Commands, local_players: Res<LocalPlayers>) {
+    let local_inputs = local_players.0.iter().map(|&handle| (handle, 1)).collect();
+
+    commands.insert_resource(LocalInputs::<TestConfig>(local_inputs));
+}
+
+pub fn spawn_counter(mut commands: Commands) {
+    commands.spawn(Counter::default()).add_rollback();
+}
+
+pub fn increase_counter(mut counters: Query<&mut Counter>, inputs: Res<PlayerInputs<TestConfig>>) {
+    for mut counter in &mut counters {
+        counter.0 += inputs[0].0 as u32;
+    }
+}
+
+/// A SyncTest session with `num_players` local players, which checks the last 2 frames.
+pub fn synctest_session(num_players: usize) -> Session<TestConfig> {
+    synctest_session_with_check_distance(num_players, 2)
+}
+
+pub fn synctest_session_with_check_distance(
+    num_players: usize,
+    check_distance: usize,
+) -> Session<TestConfig> {
+    let mut builder = SessionBuilder::<TestConfig>::new()
+        .with_num_players(num_players)
+        .with_check_distance(check_distance);
+
+    for handle in 0..num_players {
+        builder = builder.add_player(PlayerType::Local, handle).unwrap();
+    }
+
+    Session::SyncTest(builder.start_synctest_session().unwrap())
+}
+
+/// Creates an [`App`] with `plugin` under [`MinimalPlugins`], where each update lasts a single
+/// frame at the default rollback frame rate. Neither inputs nor a session are added.
+pub fn minimal_app<C: Config>(plugin: GgrsPlugin<C>) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(plugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )));
+
+    app
+}
+
+/// Extends [`minimal_app`] so every local player presses their button. No session is started.
+pub fn create_app(plugin: GgrsPlugin<TestConfig>) -> App {
+    let mut app = minimal_app(plugin);
+
+    app.add_systems(ReadInputs, read_local_inputs);
+
+    app
+}
+
+/// Extends [`create_app`] with a rolled back [`Counter`], increased by the input of the first
+/// player every frame, and a SyncTest session with `num_players` local players.
+pub fn create_counter_app(plugin: GgrsPlugin<TestConfig>, num_players: usize) -> App {
+    let mut app = create_app(plugin);
+
+    app.rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, spawn_counter)
+        .add_systems(GgrsSchedule, increase_counter)
+        .insert_resource(synctest_session(num_players));
+
+    app
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "debug")]
+
+use bevy::prelude::*;
+use bevy_ggrs::{available_frames, debug_load, debug_step_back, prelude::*, RollbackFrameCount};
+
+mod common;
+use common::*;
+
+/// Extends [`create_counter_app`] by running it until snapshots of several frames are stored.
+fn create_running_app() -> App {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    app
+}
+
+#[test]
+fn it_loads_historical_snapshots() {
+    let mut app = create_running_app();
+
+    let frames = available_frames(&app.world);
+    let &oldest = frames.last().expect("No snapshots were stored");
+
+    assert!(!debug_load(&mut app.world, i32::MAX));
+    assert!(debug_load(&mut app.world, oldest));
+
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, oldest);
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, oldest as u32);
+}
+
+#[test]
+fn it_steps_back_one_frame_at_a_time() {
+    let mut app = create_running_app();
+
+    let &oldest = available_frames(&app.world)
+        .last()
+        .expect("No snapshots were stored");
+    let mut frame = app.world.resource::<RollbackFrameCount>().0;
+
+    while debug_step_back(&mut app.world) {
+        frame -= 1;
+
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, frame);
+        let counter = app.world.query::<&Counter>().single(&app.world).0;
+        assert_eq!(counter, frame as u32);
+    }
+
+    assert_eq!(frame, oldest, "Stopped before reaching the oldest snapshot");
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, oldest);
+}
@@ -1,4 +1,8 @@
 use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        system::RunSystemOnce,
+    },
     prelude::*,
     utils::{Duration, HashMap},
 };
@@ -146,3 +150,331 @@ fn entity_mapping() {
         "Parent doesn't exist"
     );
 }
+
+#[derive(Component, Clone, Copy)]
+struct Target;
+
+#[derive(Component, Clone)]
+struct Leader(Option<Entity>);
+
+#[derive(Component, Clone)]
+struct Squad(Vec<Entity>);
+
+#[derive(Component, Clone)]
+struct Roles(HashMap<u8, Entity>);
+
+impl MapEntities for Leader {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0.map_contained_entities(entity_mapper);
+    }
+}
+
+impl MapEntities for Squad {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0.map_contained_entities(entity_mapper);
+    }
+}
+
+impl MapEntities for Roles {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0.map_contained_entities(entity_mapper);
+    }
+}
+
+/// Spawns a rollback [`Target`] referenced by `holder`, despawns it after taking a snapshot,
+/// then loads that snapshot and returns the respawned [`Target`].
+fn respawn_referenced_target<C>(holder: fn(Entity) -> C) -> (App, Entity)
+where
+    C: Component + Clone + MapEntities,
+{
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<GgrsConfig>::default())
+        .rollback_component_with_copy::<Target>()
+        .rollback_component_with_clone::<C>()
+        .update_component_with_map_entities::<C>()
+        .add_systems(Startup, move |mut commands: Commands| {
+            let target = commands.spawn(Target).add_rollback().id();
+            commands.spawn(holder(target)).add_rollback();
+        });
+
+    app.update();
+
+    let mut targets = app.world.query_filtered::<Entity, With<Target>>();
+    let original = targets.single(&app.world);
+
+    let frame = capture_snapshot(&mut app.world);
+
+    app.world.despawn(original);
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    let respawned = targets.single(&app.world);
+    assert_ne!(respawned, original, "Target was not respawned");
+
+    (app, respawned)
+}
+
+#[test]
+fn it_maps_entities_in_options() {
+    let (mut app, respawned) = respawn_referenced_target(|target| Leader(Some(target)));
+
+    let leader = app.world.query::<&Leader>().single(&app.world);
+    assert_eq!(leader.0, Some(respawned));
+}
+
+#[test]
+fn it_maps_entities_in_vecs() {
+    let (mut app, respawned) = respawn_referenced_target(|target| Squad(vec![target, target]));
+
+    let squad = app.world.query::<&Squad>().single(&app.world);
+    assert_eq!(squad.0, [respawned, respawned]);
+}
+
+#[test]
+fn it_maps_entities_in_hash_maps() {
+    let (mut app, respawned) =
+        respawn_referenced_target(|target| Roles(HashMap::from_iter([(0, target), (1, target)])));
+
+    let roles = app.world.query::<&Roles>().single(&app.world);
+    assert_eq!(roles.0.get(&0), Some(&respawned));
+    assert_eq!(roles.0.get(&1), Some(&respawned));
+}
+
+#[derive(Component, Clone, Copy)]
+struct Limb;
+
+#[derive(Component)]
+struct Decoration;
+
+#[test]
+fn it_restores_recursively_despawned_hierarchies() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<GgrsConfig>::default())
+        .rollback_component_with_copy::<Target>()
+        .rollback_component_with_copy::<Limb>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands
+                .spawn(Target)
+                .add_rollback()
+                .with_children(|parent| {
+                    parent.spawn(Limb).add_rollback().with_children(|limb| {
+                        limb.spawn(Limb).add_rollback();
+                    });
+                });
+        });
+
+    app.update();
+
+    let frame = capture_snapshot(&mut app.world);
+
+    let mut targets = app.world.query_filtered::<Entity, With<Target>>();
+    let original = targets.single(&app.world);
+    app.world.entity_mut(original).despawn_recursive();
+
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    let target = targets.single(&app.world);
+    assert_ne!(target, original, "Hierarchy was not respawned");
+
+    let upper = app
+        .world
+        .get::<Children>(target)
+        .expect("Children were not restored")[0];
+    assert_eq!(
+        app.world.get::<Parent>(upper).map(Parent::get),
+        Some(target)
+    );
+    assert!(app.world.get::<Limb>(upper).is_some());
+
+    let lower = app
+        .world
+        .get::<Children>(upper)
+        .expect("Children were not restored")[0];
+    assert_eq!(app.world.get::<Parent>(lower).map(Parent::get), Some(upper));
+    assert!(app.world.get::<Limb>(lower).is_some());
+}
+
+#[test]
+fn it_despawns_non_rollback_descendants_with_their_ancestor() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<GgrsConfig>::default())
+        .rollback_component_with_copy::<Target>();
+
+    app.update();
+
+    let frame = capture_snapshot(&mut app.world);
+
+    // spawned after the snapshot, so loading it must remove the whole hierarchy
+    app.world.run_system_once(|mut commands: Commands| {
+        commands
+            .spawn(Target)
+            .add_rollback()
+            .with_children(|parent| {
+                parent.spawn(Decoration);
+            });
+    });
+
+    let mut decorations = app.world.query::<&Decoration>();
+    assert_eq!(decorations.iter(&app.world).count(), 1);
+
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    assert_eq!(decorations.iter(&app.world).count(), 0);
+}
+
+#[derive(Component, Clone)]
+struct Follows(Entity);
+
+#[derive(Component, Clone)]
+struct FollowedBy(Vec<Entity>);
+
+impl MapEntities for Follows {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0.map_contained_entities(entity_mapper);
+    }
+}
+
+impl MapEntities for FollowedBy {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0.map_contained_entities(entity_mapper);
+    }
+}
+
+/// Forgets followers which no longer exist, removing [`FollowedBy`] once none remain.
+fn prune_followers(followed_by: &mut FollowedBy) -> bool {
+    followed_by
+        .0
+        .retain(|&entity| entity != Entity::PLACEHOLDER);
+    !followed_by.0.is_empty()
+}
+
+/// Spawns a leader followed by a follower, returning both.
+fn create_relationship_app() -> (App, Entity, Entity) {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<GgrsConfig>::default())
+        .rollback_relationship::<Follows, FollowedBy>()
+        .prune_relationship_with::<FollowedBy>(prune_followers)
+        .add_systems(Startup, |mut commands: Commands| {
+            let leader = commands.spawn_empty().add_rollback().id();
+            let follower = commands.spawn(Follows(leader)).add_rollback().id();
+            commands.entity(leader).insert(FollowedBy(vec![follower]));
+        });
+
+    app.update();
+
+    let (follower, follows) = app.world.query::<(Entity, &Follows)>().single(&app.world);
+    let leader = follows.0;
+
+    (app, leader, follower)
+}
+
+#[test]
+fn it_maps_both_sides_of_relationships() {
+    let (mut app, leader, follower) = create_relationship_app();
+
+    let frame = capture_snapshot(&mut app.world);
+
+    app.world.despawn(leader);
+    app.world.despawn(follower);
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    let (follower, follows) = app.world.query::<(Entity, &Follows)>().single(&app.world);
+    let leader = follows.0;
+
+    assert_eq!(
+        app.world
+            .get::<FollowedBy>(leader)
+            .map(|followed| followed.0.clone()),
+        Some(vec![follower]),
+        "The leader does not refer back to the respawned follower"
+    );
+}
+
+#[test]
+fn it_removes_relationships_to_despawned_endpoints() {
+    let (mut app, leader, follower) = create_relationship_app();
+
+    // the leader is despawned without updating the follower, so the snapshot dangles
+    app.world.despawn(leader);
+    let frame = capture_snapshot(&mut app.world);
+
+    app.world.entity_mut(follower).insert(Follows(follower));
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    assert!(app.world.get_entity(follower).is_some());
+    assert!(
+        app.world.get::<Follows>(follower).is_none(),
+        "The follower still follows a despawned leader"
+    );
+}
+
+#[test]
+fn it_removes_relationships_from_despawned_endpoints() {
+    let (mut app, leader, follower) = create_relationship_app();
+
+    // the follower is despawned without updating the leader
+    app.world.despawn(follower);
+    let frame = capture_snapshot(&mut app.world);
+
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    assert!(app.world.get_entity(leader).is_some());
+    assert!(
+        app.world.get::<FollowedBy>(leader).is_none(),
+        "The leader is still followed by a despawned follower"
+    );
+}
+
+#[test]
+fn it_prunes_only_the_despawned_endpoints_of_relationships() {
+    let (mut app, leader, follower) = create_relationship_app();
+
+    let other = app.world.spawn(Follows(leader)).id();
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.entity(other).add_rollback();
+    });
+    app.world
+        .get_mut::<FollowedBy>(leader)
+        .unwrap()
+        .0
+        .push(other);
+
+    // one of the followers is despawned without updating the leader
+    app.world.despawn(follower);
+    let frame = capture_snapshot(&mut app.world);
+
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    assert_eq!(
+        app.world
+            .get::<FollowedBy>(leader)
+            .map(|followed| followed.0.clone()),
+        Some(vec![other]),
+        "The leader is not followed by exactly the remaining follower"
+    );
+    assert_eq!(
+        app.world.get::<Follows>(other).map(|follows| follows.0),
+        Some(leader)
+    );
+}
+
+#[test]
+fn it_maps_relationships_updated_with_map_entities_once() {
+    let mut app = App::new();
+
+    // the relationship already maps both sides, so neither must be added again
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<GgrsConfig>::default())
+        .rollback_relationship::<Follows, FollowedBy>()
+        .update_component_with_map_entities::<Follows>()
+        .update_component_with_map_entities::<FollowedBy>();
+
+    app.update();
+}
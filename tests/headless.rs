@@ -0,0 +1,1528 @@
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_ggrs::{
+    prelude::*, ChecksumDiagnostics, ChecksumDiagnosticsPlugin, ConfirmedFrameCount,
+    GgrsComponentSnapshots, GgrsConfirmedSchedule, GgrsPlayers, GgrsResimStats, GgrsSessionBuilder,
+    GgrsSessionSeed, InputSampling, LocalInputs, LocalPlayers, PendingSession, PlayerEntities,
+    PlayerHandleComponent, PlayerInputHistory, PlayerInputSchema, RollbackFrameCount,
+    RollbackFrameRate, RollbackRegistry, RollbackStats, RollbackView, SaveWorld,
+    SessionInitialized, StructuralChecksum, StructuralChecksumPlugin, TaggedInput,
+};
+use ggrs::{PlayerHandle, SessionState};
+
+mod common;
+use common::*;
+
+/// Ensures the rollback stage runs under [`MinimalPlugins`], without any window or render resources.
+#[test]
+fn it_runs_with_minimal_plugins() {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(
+        counter, frame as u32,
+        "Counter diverged from the frame count"
+    );
+}
+
+#[test]
+fn it_reports_session_state_changes() {
+    let mut app = create_app(GgrsPlugin::default());
+    app.update();
+
+    app.insert_resource(synctest_session(1));
+    app.update();
+
+    let events = app.world.resource::<Events<GgrsSessionStateChanged>>();
+    let changes = events
+        .iter_current_update_events()
+        .copied()
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        changes,
+        vec![GgrsSessionStateChanged {
+            from: None,
+            to: Some(SessionState::Running),
+        }]
+    );
+}
+
+#[test]
+fn it_runs_when_scheduled_manually() {
+    let mut app = create_counter_app(GgrsPlugin::without_driver(), 1);
+
+    app.update();
+    assert_eq!(
+        app.world.resource::<RollbackFrameCount>().0,
+        0,
+        "GGRS should not run without a driver"
+    );
+
+    app.add_systems(Update, GgrsPlugin::<TestConfig>::run);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, frame as u32);
+}
+
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+struct PhysicsSchedule;
+
+#[derive(ScheduleLabel, Debug, Hash, PartialEq, Eq, Clone)]
+struct GameplaySchedule;
+
+#[derive(Component, Clone, Copy, Default)]
+struct Doubled(u32);
+
+fn double_counter(mut query: Query<(&Counter, &mut Doubled)>) {
+    for (counter, mut doubled) in &mut query {
+        doubled.0 = counter.0 * 2;
+    }
+}
+
+#[test]
+fn it_runs_rollback_schedules_in_order() {
+    let mut app = create_app(
+        GgrsPlugin::default()
+            .with_rollback_schedule(PhysicsSchedule)
+            .with_rollback_schedule(GameplaySchedule),
+    );
+
+    app.rollback_component_with_copy::<Counter>()
+        .rollback_component_with_copy::<Doubled>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands
+                .spawn((Counter::default(), Doubled::default()))
+                .add_rollback();
+        })
+        // runs last, so only sees the counter once both earlier schedules have run
+        .add_systems(GameplaySchedule, double_counter)
+        .add_systems(PhysicsSchedule, increase_counter)
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let (counter, doubled) = app.world.query::<(&Counter, &Doubled)>().single(&app.world);
+    assert_eq!(counter.0, frame as u32);
+    assert_eq!(doubled.0, frame as u32 * 2);
+}
+
+#[test]
+#[should_panic(expected = "is already run")]
+fn it_panics_on_duplicate_rollback_schedules() {
+    GgrsPlugin::<TestConfig>::default()
+        .with_rollback_schedule(PhysicsSchedule)
+        .with_rollback_schedule(PhysicsSchedule);
+}
+
+#[derive(Resource, Default)]
+struct Difficulty(u8);
+
+/// Reads a resource which is not rolled back, and may differ between peers
+fn read_difficulty(_handle: PlayerHandle, world: &RollbackView) -> u8 {
+    world.resource::<Difficulty>().0
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "is not registered for Resource rollback")]
+fn it_panics_when_ai_players_read_state_which_is_not_rolled_back() {
+    let mut app = create_counter_app(
+        GgrsPlugin::default().with_ai_players([0], read_difficulty),
+        1,
+    );
+
+    app.init_resource::<Difficulty>();
+
+    for _ in 0..5 {
+        app.update();
+    }
+}
+
+#[derive(Resource, Default)]
+struct FirstSave(Option<(i32, usize)>);
+
+fn spawn_players(mut commands: Commands, players: Res<GgrsPlayers>) {
+    for _ in 0..players.num_players {
+        commands.spawn(Counter::default()).add_rollback();
+    }
+}
+
+fn record_first_save(
+    mut first_save: ResMut<FirstSave>,
+    frame: Res<RollbackFrameCount>,
+    rollbacks: Query<(), With<Rollback>>,
+) {
+    first_save
+        .0
+        .get_or_insert((frame.0, rollbacks.iter().count()));
+}
+
+#[test]
+fn it_captures_setup_in_the_first_snapshot() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.init_resource::<FirstSave>()
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(GgrsSetup, spawn_players)
+        .add_systems(GgrsSchedule, increase_counter)
+        .add_systems(SaveWorld, record_first_save)
+        .insert_resource(synctest_session(2));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<FirstSave>().0, Some((0, 2)));
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counters = app
+        .world
+        .query::<&Counter>()
+        .iter(&app.world)
+        .map(|counter| counter.0)
+        .collect::<Vec<_>>();
+    assert_eq!(counters, vec![frame as u32; 2]);
+}
+
+#[derive(Resource, Default)]
+struct StageLog(Vec<&'static str>);
+
+#[test]
+fn it_orders_systems_around_the_stage_set() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.init_resource::<StageLog>()
+        .add_systems(
+            PreUpdate,
+            (|mut log: ResMut<StageLog>| log.0.push("before")).before(GgrsStageSet),
+        )
+        .add_systems(
+            PreUpdate,
+            (|mut log: ResMut<StageLog>| log.0.push("after")).after(GgrsStageSet),
+        )
+        .add_systems(ReadInputs, |mut log: ResMut<StageLog>| log.0.push("read"))
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let log = &app.world.resource::<StageLog>().0;
+    assert!(log.contains(&"read"), "Inputs were never read");
+
+    let mut previous = "after";
+    for &entry in log {
+        match entry {
+            "before" => assert_eq!(previous, "after"),
+            "read" => assert_ne!(previous, "after", "Inputs read outside the stage set"),
+            _ => assert_ne!(previous, "after"),
+        }
+
+        previous = entry;
+    }
+}
+
+#[test]
+fn it_publishes_players() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.update();
+    assert_eq!(*app.world.resource::<GgrsPlayers>(), GgrsPlayers::default());
+
+    app.insert_resource(synctest_session(2));
+    app.update();
+
+    let players = app.world.resource::<GgrsPlayers>();
+    assert_eq!(players.num_players, 2);
+    assert_eq!(players.local, vec![0, 1]);
+    assert!(players.is_local(1));
+}
+
+#[derive(Resource, Default)]
+struct ConfirmedFrames(Vec<i32>);
+
+#[test]
+fn it_runs_confirmed_schedule_once_per_confirmed_frame() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.init_resource::<ConfirmedFrames>()
+        .add_systems(
+            GgrsConfirmedSchedule,
+            |mut frames: ResMut<ConfirmedFrames>, confirmed: Res<ConfirmedFrameCount>| {
+                frames.0.push((*confirmed).into());
+            },
+        )
+        .insert_resource(synctest_session(1))
+        // several frames are advanced and confirmed in each update
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            3.5 / 60.0,
+        )));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frames = &app.world.resource::<ConfirmedFrames>().0;
+    assert_eq!(
+        frames.first(),
+        Some(&0),
+        "Confirmed schedule skipped frame 0"
+    );
+    assert!(
+        frames.len() > 60,
+        "Confirmed schedule only ran for {} frame(s)",
+        frames.len()
+    );
+    assert!(
+        frames.windows(2).all(|pair| pair[1] == pair[0] + 1),
+        "Confirmed schedule did not run exactly once for each frame: {frames:?}"
+    );
+}
+
+#[derive(Resource, Default)]
+struct SampledFrames(Vec<i32>);
+
+fn record_sampled_frame(mut frames: ResMut<SampledFrames>, frame: Res<RollbackFrameCount>) {
+    frames.0.push(frame.0);
+}
+
+#[test]
+fn it_reads_inputs_once_per_frame() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.init_resource::<SampledFrames>()
+        .add_systems(ReadInputs, record_sampled_frame)
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let sampled = &app.world.resource::<SampledFrames>().0;
+
+    // Inputs for each frame are read once, before advancing to it, and never during re-simulation
+    assert_eq!(*sampled, (0..frame).collect::<Vec<_>>());
+}
+
+/// Runs 10 updates which each advance several frames, sampling inputs as provided.
+fn run_with_input_sampling(input_sampling: InputSampling) -> App {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    app.set_input_sampling(input_sampling)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            3.5 / 60.0,
+        )))
+        .init_resource::<SampledFrames>()
+        .add_systems(ReadInputs, record_sampled_frame);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    app
+}
+
+#[test]
+fn it_reads_inputs_for_every_step_or_once_per_tick() {
+    let mut per_step = run_with_input_sampling(InputSampling::PerStep);
+
+    let frame = per_step.world.resource::<RollbackFrameCount>().0;
+    let sampled = &per_step.world.resource::<SampledFrames>().0;
+    assert!(frame > 20, "Only advanced {frame} frames");
+    assert_eq!(*sampled, (0..frame).collect::<Vec<_>>());
+
+    let mut per_tick = run_with_input_sampling(InputSampling::PerTick);
+
+    let frame = per_tick.world.resource::<RollbackFrameCount>().0;
+    let sampled = &per_tick.world.resource::<SampledFrames>().0;
+    assert!(frame > 20, "Only advanced {frame} frames");
+    assert!(sampled.len() <= 10, "Sampled {} times", sampled.len());
+    assert!(sampled.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // the inputs sampled in each update are still used for every frame advanced in it
+    let counter = per_tick.world.query::<&Counter>().single(&per_tick.world).0;
+    assert_eq!(counter, frame as u32);
+    assert!(per_tick
+        .world
+        .get_resource::<LocalInputs<TestConfig>>()
+        .is_none());
+}
+
+#[derive(Resource)]
+struct InputScale(u8);
+
+struct InputScalePlugin;
+
+impl Plugin for InputScalePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputScale(2));
+    }
+}
+
+fn read_scaled_inputs(
+    mut commands: Commands,
+    local_players: Res<LocalPlayers>,
+    scale: Res<InputScale>,
+) {
+    let local_inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| (handle, scale.0))
+        .collect();
+
+    commands.insert_resource(LocalInputs::<TestConfig>(local_inputs));
+}
+
+#[test]
+fn it_reads_inputs_from_resources_added_later() {
+    let mut app = App::new();
+
+    // InputScalePlugin is deliberately added after the GgrsPlugin and the input system
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_systems(ReadInputs, read_scaled_inputs)
+        .add_plugins(InputScalePlugin)
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, spawn_counter)
+        .add_systems(GgrsSchedule, increase_counter)
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, 2 * frame as u32);
+}
+
+fn read_triple_inputs(mut commands: Commands, local_players: Res<LocalPlayers>) {
+    let local_inputs = local_players.0.iter().map(|&handle| (handle, 3)).collect();
+
+    commands.insert_resource(LocalInputs::<TestConfig>(local_inputs));
+}
+
+#[test]
+fn it_replaces_input_systems_at_runtime() {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    for _ in 0..15 {
+        app.update();
+    }
+
+    let swapped_at = app.world.resource::<RollbackFrameCount>().0;
+    ReadInputs::replace_systems(&mut app.world, read_triple_inputs);
+
+    for _ in 0..15 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > swapped_at, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, (swapped_at + 3 * (frame - swapped_at)) as u32);
+}
+
+#[test]
+fn it_reports_initialization() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.update();
+    assert!(app.world.resource::<Events<GgrsInitialized>>().is_empty());
+    assert!(!app.world.resource::<SessionInitialized>().0);
+
+    let session = synctest_session(1);
+    let max_prediction = session.as_synctest().unwrap().max_prediction();
+
+    app.insert_resource(session);
+    app.update();
+
+    let initialized = app
+        .world
+        .resource::<Events<GgrsInitialized>>()
+        .iter_current_update_events()
+        .copied()
+        .collect::<Vec<_>>();
+
+    assert_eq!(initialized, vec![GgrsInitialized { max_prediction }]);
+    assert!(app.world.resource::<SessionInitialized>().0);
+
+    app.update();
+    assert!(app
+        .world
+        .resource::<Events<GgrsInitialized>>()
+        .iter_current_update_events()
+        .next()
+        .is_none());
+    assert!(app.world.resource::<SessionInitialized>().0);
+
+    app.world.remove_resource::<Session<TestConfig>>();
+    app.update();
+    assert!(!app.world.resource::<SessionInitialized>().0);
+}
+
+#[derive(Resource, Default)]
+struct SetupRuns(usize);
+
+#[test]
+fn it_sets_up_sessions_replaced_in_place() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.init_resource::<SetupRuns>()
+        .add_systems(GgrsSetup, |mut runs: ResMut<SetupRuns>| runs.0 += 1)
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<SetupRuns>().0, 1);
+    assert!(app.world.resource::<RollbackFrameCount>().0 > 5);
+
+    // inserted directly over the running one, without shutting it down first
+    app.insert_resource(synctest_session(2));
+    app.update();
+
+    let initialized = app
+        .world
+        .resource::<Events<GgrsInitialized>>()
+        .iter_current_update_events()
+        .count();
+
+    assert_eq!(initialized, 1);
+    assert_eq!(app.world.resource::<SetupRuns>().0, 2);
+    assert_eq!(app.world.resource::<GgrsPlayers>().num_players, 2);
+    assert!(app.world.resource::<RollbackFrameCount>().0 <= 1);
+
+    // stepping the session is not mistaken for replacing it
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<SetupRuns>().0, 2);
+}
+
+#[test]
+fn it_builds_sessions_matching_the_frame_rate() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .set_rollback_schedule_fps(30)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 30.0,
+        )))
+        .add_systems(ReadInputs, read_local_inputs)
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, spawn_counter)
+        .add_systems(GgrsSchedule, increase_counter);
+
+    let session = GgrsSessionBuilder::<TestConfig>::new(&app.world, 1)
+        .unwrap()
+        .add_local_player(0)
+        .unwrap()
+        .start_synctest_session()
+        .unwrap();
+
+    app.insert_resource(session);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, frame as u32);
+}
+
+#[test]
+fn it_runs_an_exact_number_of_frames() {
+    // no time passes, so only run_n_frames advances the session
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+    app.update();
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 0);
+
+    let advanced = GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 100);
+
+    assert_eq!(advanced, 100);
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 100);
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, 100);
+}
+
+#[test]
+fn it_sets_up_sessions_run_for_an_exact_number_of_frames() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.init_resource::<SetupRuns>()
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(GgrsSetup, |mut runs: ResMut<SetupRuns>| runs.0 += 1)
+        .add_systems(GgrsSetup, spawn_players)
+        .add_systems(GgrsSchedule, increase_counter)
+        .insert_resource(synctest_session(2));
+
+    // the session is never picked up by an update
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    assert_eq!(app.world.resource::<SetupRuns>().0, 1);
+    assert!(app.world.resource::<SessionInitialized>().0);
+    assert_eq!(app.world.resource::<GgrsPlayers>().num_players, 2);
+
+    let initialized = app
+        .world
+        .resource::<Events<GgrsInitialized>>()
+        .iter_current_update_events()
+        .count();
+    assert_eq!(initialized, 1);
+
+    let counters = app
+        .world
+        .query::<&Counter>()
+        .iter(&app.world)
+        .map(|counter| counter.0)
+        .collect::<Vec<_>>();
+    assert_eq!(counters, vec![10; 2]);
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+    assert_eq!(app.world.resource::<SetupRuns>().0, 1);
+}
+
+#[derive(Resource, Clone, Default)]
+struct Score(u32);
+
+#[derive(Resource, Default)]
+struct Unregistered(u32);
+
+fn read_real_time(_time: Res<Time<bevy::time::Real>>) {}
+
+fn write_unregistered(mut unregistered: ResMut<Unregistered>) {
+    unregistered.0 += 1;
+}
+
+fn write_score(mut score: ResMut<Score>) {
+    score.0 += 1;
+}
+
+#[test]
+fn it_finds_nondeterministic_systems() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .init_resource::<Score>()
+        .init_resource::<Unregistered>()
+        .rollback_resource_with_clone::<Score>()
+        .add_systems(
+            GgrsSchedule,
+            (read_real_time, write_unregistered, write_score).chain(),
+        );
+
+    let warnings = bevy_ggrs::check_determinism(&mut app.world);
+
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+    assert!(warnings[0].system.ends_with("read_real_time"));
+    assert!(warnings[1].system.ends_with("write_unregistered"));
+}
+
+#[derive(Resource, Default)]
+struct InputLog(Vec<Vec<u8>>);
+
+fn log_input_bytes(mut log: ResMut<InputLog>, inputs: Res<PlayerInputs<TestConfig>>) {
+    assert_eq!(inputs.input_bytes(0), &[1]);
+    log.0.push(inputs.to_bytes());
+}
+
+#[test]
+fn it_exposes_raw_input_bytes() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .init_resource::<InputLog>()
+        .add_systems(GgrsSchedule, log_input_bytes)
+        .insert_resource(synctest_session(1));
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    let log = &app.world.resource::<InputLog>().0;
+    assert!(log.len() >= 10);
+    assert!(log.iter().all(|bytes| bytes == &[1]));
+}
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+enum GameState {
+    #[default]
+    Menu,
+    InGame,
+}
+
+#[test]
+fn it_only_runs_in_state() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_state::<GameState>()
+        .add_plugins(GgrsPlugin::<TestConfig>::default().run_in_state(GameState::InGame))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_systems(ReadInputs, read_local_inputs)
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 0);
+
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::InGame);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert!(app.world.resource::<RollbackFrameCount>().0 > 10);
+}
+
+#[test]
+fn it_reports_the_tick_duration() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .set_rollback_schedule_fps(30);
+
+    let tick_duration = app.world.resource::<RollbackFrameRate>().tick_duration();
+    assert_eq!(tick_duration, Duration::from_secs_f64(1.0 / 30.0));
+}
+
+/// Simulates a browser tab regaining focus after being suspended for a long time.
+#[test]
+fn it_clamps_large_frame_deltas() {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    app.set_max_frame_delta(Duration::from_millis(50))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(10)));
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    // Each update may add at most 50ms, or 3 frames at 60 FPS
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 0, "Rollback schedule did not advance");
+    assert!(frame <= 9, "Advanced {frame} frames in 3 updates");
+}
+
+#[test]
+#[should_panic(expected = "FPS must be at least 1")]
+fn it_rejects_a_zero_frame_rate() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .set_rollback_schedule_fps(0);
+}
+
+#[test]
+fn it_provides_the_session_seed() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default().with_session_seed(1234))
+        .add_systems(
+            Startup,
+            |seed: Res<GgrsSessionSeed>, mut commands: Commands| {
+                commands.insert_resource(Score(seed.0 as u32));
+            },
+        );
+
+    app.update();
+
+    assert_eq!(app.world.resource::<Score>().0, 1234);
+}
+
+/// Runs a session with the provided seed, returning the checksum of its sixth save.
+fn checksum_with_seed(seed: u64) -> Option<u128> {
+    let mut app = create_counter_app(GgrsPlugin::default().with_session_seed(seed), 1);
+
+    app.init_resource::<SavedChecksums>()
+        .add_systems(SaveWorld, record_checksum.after(SaveWorldSet::Snapshot));
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    app.world.resource::<SavedChecksums>().0.get(5).copied()
+}
+
+#[test]
+fn it_checksums_the_session_seed() {
+    let checksum = checksum_with_seed(1);
+
+    assert!(checksum.is_some());
+    assert_eq!(checksum, checksum_with_seed(1));
+    assert_ne!(
+        checksum,
+        checksum_with_seed(2),
+        "The seed was not checksummed"
+    );
+}
+
+#[derive(Resource, Default)]
+struct PlayerEntityCounts {
+    player_one: usize,
+    player_two: usize,
+    disconnected: usize,
+}
+
+fn count_player_entities(
+    players: PlayerEntities,
+    inputs: Res<PlayerInputs<TestConfig>>,
+    mut counts: ResMut<PlayerEntityCounts>,
+) {
+    counts.player_one = players.for_handle(0).count();
+    counts.player_two = players.for_handle(1).count();
+    counts.disconnected = inputs.disconnected().count();
+}
+
+#[test]
+fn it_finds_entities_by_player_handle() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .init_resource::<PlayerEntityCounts>()
+        .add_systems(GgrsSchedule, count_player_entities)
+        .insert_resource(synctest_session(2));
+
+    let first = app.world.spawn(PlayerHandleComponent(0)).id();
+    app.world.spawn(PlayerHandleComponent(0));
+    app.world.spawn(PlayerHandleComponent(1));
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+
+    let counts = app.world.resource::<PlayerEntityCounts>();
+    assert_eq!(counts.player_one, 2);
+    assert_eq!(counts.player_two, 1);
+    assert_eq!(counts.disconnected, 0);
+
+    let mut state = bevy::ecs::system::SystemState::<PlayerEntities>::new(&mut app.world);
+    assert_eq!(state.get(&app.world).handle_of(first), Some(0));
+}
+
+enum Role {
+    Attacker,
+    Defender,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoleInput {
+    Attacker { fire: bool },
+    Defender { shield: bool },
+}
+
+impl TaggedInput<u8> for RoleInput {
+    type Schema = Role;
+
+    fn encode(&self) -> u8 {
+        match *self {
+            RoleInput::Attacker { fire } => fire as u8,
+            RoleInput::Defender { shield } => shield as u8,
+        }
+    }
+
+    fn decode(role: &Role, input: &u8) -> Self {
+        match role {
+            Role::Attacker => RoleInput::Attacker {
+                fire: *input & 1 != 0,
+            },
+            Role::Defender => RoleInput::Defender {
+                shield: *input & 1 != 0,
+            },
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct DecodedInputs(Vec<(usize, RoleInput)>);
+
+fn decode_role_inputs(
+    inputs: Res<PlayerInputs<TestConfig>>,
+    schema: Res<PlayerInputSchema<Role>>,
+    mut decoded: ResMut<DecodedInputs>,
+) {
+    decoded.0 = schema.decode_all::<TestConfig, RoleInput>(&inputs);
+}
+
+#[test]
+fn it_decodes_inputs_by_player_schema() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .insert_resource(PlayerInputSchema::new([
+            (0, Role::Attacker),
+            (1, Role::Defender),
+        ]))
+        .init_resource::<DecodedInputs>()
+        .add_systems(GgrsSchedule, decode_role_inputs)
+        .insert_resource(synctest_session(2));
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 3);
+
+    // Both players send the same raw input, which means something different for each role
+    assert_eq!(
+        app.world.resource::<DecodedInputs>().0,
+        [
+            (0, RoleInput::Attacker { fire: true }),
+            (1, RoleInput::Defender { shield: true }),
+        ]
+    );
+    assert_eq!(RoleInput::Defender { shield: true }.encode(), 1);
+}
+
+#[test]
+fn it_exposes_the_underlying_session() {
+    let mut session = synctest_session(1);
+
+    assert!(session.as_p2p().is_none());
+    assert!(session.as_spectator_mut().is_none());
+
+    let synctest = session
+        .as_synctest_mut()
+        .expect("Session should be a SyncTest");
+    assert_eq!(synctest.num_players(), 1);
+}
+
+#[derive(Component, Clone, Copy, Default, Hash)]
+struct Score(u32);
+
+fn spawn_score(mut commands: Commands) {
+    commands.spawn(Score::default()).add_rollback();
+}
+
+fn increase_score(mut scores: Query<&mut Score>) {
+    for mut score in &mut scores {
+        score.0 += 1;
+    }
+}
+
+/// Hidden state in a [`Local`] is not rolled back, so re-simulating a frame gives a different result.
+fn increase_score_by_calls(mut calls: Local<u32>, mut scores: Query<&mut Score>) {
+    *calls += 1;
+
+    for mut score in &mut scores {
+        score.0 += *calls;
+    }
+}
+
+fn create_verified_app<M>(system: impl IntoSystemConfigs<M>) -> App {
+    let mut app = create_app(GgrsPlugin::default().with_redundant_verification(true));
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .rollback_component_with_copy::<Score>()
+        .checksum_component_with_hash::<Score>()
+        .add_systems(Startup, spawn_score)
+        .add_systems(GgrsSchedule, system)
+        .insert_resource(synctest_session(1));
+
+    app
+}
+
+#[test]
+fn it_verifies_deterministic_frames() {
+    let mut app = create_verified_app(increase_score);
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let score = app.world.query::<&Score>().single(&app.world).0;
+    assert_eq!(score, frame as u32);
+}
+
+#[test]
+fn it_does_not_record_the_verification_pass() {
+    let run = |verified: bool| {
+        let mut app = create_app(GgrsPlugin::default().with_redundant_verification(verified));
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+            .rollback_component_with_copy::<Score>()
+            .checksum_component_with_hash::<Score>()
+            .set_snapshot_keyframe_interval(2)
+            .add_systems(Startup, spawn_score)
+            .add_systems(GgrsSchedule, increase_score)
+            .insert_resource(synctest_session(1));
+
+        app.update();
+        GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+        let frames = app
+            .world
+            .resource::<GgrsComponentSnapshots<Score>>()
+            .frames()
+            .collect::<Vec<_>>();
+
+        (*app.world.resource::<GgrsResimStats>(), frames)
+    };
+
+    let (verified_stats, verified_frames) = run(true);
+    let (stats, frames) = run(false);
+
+    assert_eq!(
+        verified_stats.total_resimulated_frames(),
+        stats.total_resimulated_frames(),
+        "The verification pass was counted as a re-simulation"
+    );
+    assert_eq!(
+        verified_frames, frames,
+        "The verification pass was snapshot"
+    );
+    assert!(frames.iter().all(|frame| frame % 2 == 0));
+}
+
+#[test]
+#[should_panic(expected = "Redundant verification failed")]
+fn it_detects_hidden_state_with_redundant_verification() {
+    let mut app = create_verified_app(increase_score_by_calls);
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+}
+
+#[test]
+#[should_panic(expected = "Redundant verification failed: headless::Score on")]
+fn it_reports_the_mismatching_component_with_diagnostics() {
+    let mut app = create_verified_app(increase_score_by_calls);
+    app.add_plugins(ChecksumDiagnosticsPlugin);
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+}
+
+fn read_handle_input(handle: usize, _world: &World) -> u8 {
+    handle as u8 + 1
+}
+
+#[derive(Resource, Default)]
+struct SubmittedInputs(Vec<u8>);
+
+fn record_submitted_inputs(
+    inputs: Res<PlayerInputs<TestConfig>>,
+    mut submitted: ResMut<SubmittedInputs>,
+) {
+    submitted.0 = inputs.iter().map(|(input, _)| *input).collect();
+}
+
+#[test]
+fn it_reads_local_players_in_parallel() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default().with_parallel_input(true))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .set_player_input_reader::<TestConfig>(read_handle_input)
+        .init_resource::<SubmittedInputs>()
+        .add_systems(GgrsSchedule, record_submitted_inputs)
+        .insert_resource(synctest_session(4));
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+
+    assert_eq!(app.world.resource::<SubmittedInputs>().0, [1, 2, 3, 4]);
+}
+
+#[test]
+fn it_starts_pending_sessions_once_ready() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_systems(ReadInputs, read_local_inputs)
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, spawn_counter)
+        .add_systems(GgrsSchedule, increase_counter);
+
+    let mut polls = 0;
+
+    app.insert_resource(PendingSession::<TestConfig>::new(
+        move |world: &mut World| {
+            polls += 1;
+
+            // Simulates a socket which takes a few updates to connect
+            if polls < 3 {
+                return None;
+            }
+
+            let session = GgrsSessionBuilder::<TestConfig>::new(world, 1)
+                .unwrap()
+                .add_local_player(0)
+                .unwrap()
+                .start_synctest_session()
+                .unwrap();
+
+            Some(session)
+        },
+    ));
+
+    app.update();
+    app.update();
+
+    assert!(app.world.get_resource::<Session<TestConfig>>().is_none());
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert!(app
+        .world
+        .get_resource::<PendingSession<TestConfig>>()
+        .is_none());
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, frame as u32);
+}
+
+#[test]
+fn it_adds_systems_to_the_ggrs_schedule() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, spawn_counter)
+        .add_ggrs_systems(increase_counter)
+        .insert_resource(synctest_session(1));
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, 5);
+}
+
+#[derive(Resource, Default, Clone, Copy)]
+struct Tally(u32);
+
+fn add_one(mut tally: ResMut<Tally>) {
+    tally.0 += 1;
+}
+
+fn add_two(mut tally: ResMut<Tally>) {
+    tally.0 += 2;
+}
+
+fn double(mut tally: ResMut<Tally>) {
+    tally.0 *= 2;
+}
+
+fn create_tally_app() -> App {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .init_resource::<Tally>()
+        .rollback_resource_with_copy::<Tally>()
+        .add_order_independent_ggrs_systems("tally", (add_one, add_two))
+        .insert_resource(synctest_session(1));
+
+    app
+}
+
+#[test]
+fn it_allows_ambiguities_within_order_independent_groups() {
+    let mut app = create_tally_app();
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+
+    assert_eq!(app.world.resource::<Tally>().0, 15);
+}
+
+#[test]
+#[should_panic(expected = "conflicting access")]
+fn it_still_reports_ambiguities_outside_order_independent_groups() {
+    let mut app = create_tally_app();
+    app.add_ggrs_systems(double);
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+}
+
+fn read_frame_inputs(
+    mut commands: Commands,
+    local_players: Res<LocalPlayers>,
+    mut frame: Local<u8>,
+) {
+    *frame += 1;
+
+    let local_inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| (handle, *frame))
+        .collect();
+
+    commands.insert_resource(LocalInputs::<TestConfig>(local_inputs));
+}
+
+#[test]
+fn it_rolls_back_input_history() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default().with_input_history(3))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .add_systems(ReadInputs, read_frame_inputs)
+        .insert_resource(synctest_session(1));
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+
+    let history = app.world.resource::<PlayerInputHistory<TestConfig>>();
+    let inputs = history
+        .player(0)
+        .map(|&(input, _)| input)
+        .collect::<Vec<_>>();
+
+    // re-simulated frames replace their entries, rather than being appended again
+    assert_eq!(inputs, [5, 4, 3]);
+    assert_eq!(history.get(0, 1).map(|&(input, _)| input), Some(4));
+}
+
+#[test]
+fn it_tracks_the_last_load() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .insert_resource(synctest_session(1));
+
+    app.update();
+
+    assert_eq!(
+        *app.world.resource::<RollbackStats>(),
+        RollbackStats::default()
+    );
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let stats = *app.world.resource::<RollbackStats>();
+
+    // a SyncTest session rolls back by its check distance on every frame once possible
+    assert!(stats.load_count() >= 5, "Only {} loads", stats.load_count());
+
+    // the check distance is rolled back before the latest frame was advanced
+    let last_load_frame = stats.last_load_frame().expect("No loads were recorded");
+    assert!(
+        (frame - 3..frame).contains(&last_load_frame),
+        "Last load of {last_load_frame} is too far from frame {frame}"
+    );
+}
+
+#[test]
+fn it_shuts_sessions_down() {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    let session = GgrsPlugin::<TestConfig>::shutdown(&mut app.world);
+    assert!(session.is_some());
+    drop(session);
+
+    assert!(app.world.get_resource::<Session<TestConfig>>().is_none());
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 0);
+    assert_eq!(i32::from(*app.world.resource::<ConfirmedFrameCount>()), -1);
+
+    let snapshots = app.world.resource::<GgrsComponentSnapshots<Counter>>();
+    assert_eq!(snapshots.frames().count(), 0);
+
+    // the next session starts from the first frame
+    app.world
+        .query::<&mut Counter>()
+        .single_mut(&mut app.world)
+        .0 = 0;
+    app.insert_resource(synctest_session(1));
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 5);
+
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 5);
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, 5);
+}
+
+#[test]
+fn it_forgets_checksum_diagnostics_of_previous_sessions() {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    app.add_plugins(ChecksumDiagnosticsPlugin)
+        .checksum_component::<Counter>(|counter| counter.0 as u64)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    let diagnostics = app.world.resource::<ChecksumDiagnostics>();
+    assert!(diagnostics.breakdown(5).is_some());
+
+    GgrsPlugin::<TestConfig>::shutdown(&mut app.world);
+
+    // frames of the next session must not be compared against this one
+    let diagnostics = app.world.resource::<ChecksumDiagnostics>();
+    assert!(diagnostics.breakdown(5).is_none());
+    assert!(diagnostics.resimulation_differences().is_empty());
+}
+
+#[derive(Resource, Default)]
+struct SavedChecksums(Vec<u128>);
+
+fn record_checksum(checksum: Res<Checksum>, mut saved: ResMut<SavedChecksums>) {
+    saved.0.push(checksum.0);
+}
+
+/// Runs 30 updates of 1/30th of a second at the provided rate, agreed after the [`App`] was built.
+fn run_at_agreed_rate(fps: usize) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 30.0,
+        )))
+        .add_systems(ReadInputs, read_local_inputs)
+        .init_resource::<SavedChecksums>()
+        .add_systems(SaveWorld, record_checksum.after(SaveWorldSet::Snapshot));
+
+    app.update();
+
+    // e.g. negotiated during matchmaking
+    app.insert_resource(RollbackFrameRate::new(fps));
+
+    let session = GgrsSessionBuilder::<TestConfig>::new(&app.world, 1)
+        .unwrap()
+        .add_local_player(0)
+        .unwrap()
+        .start_synctest_session()
+        .unwrap();
+
+    app.insert_resource(session);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    app
+}
+
+#[test]
+fn it_runs_at_a_rate_agreed_at_session_start() {
+    let mut slow = run_at_agreed_rate(30);
+    let mut fast = run_at_agreed_rate(60);
+
+    let slow_frames = slow.world.resource::<RollbackFrameCount>().0;
+    let fast_frames = fast.world.resource::<RollbackFrameCount>().0;
+
+    assert!((28..=30).contains(&slow_frames), "Ran {slow_frames} frames");
+    assert!((58..=60).contains(&fast_frames), "Ran {fast_frames} frames");
+
+    // identical worlds at different rates must not produce matching checksums
+    let slow_checksum = slow.world.resource::<SavedChecksums>().0.last().copied();
+    let fast_checksum = fast.world.resource::<SavedChecksums>().0.last().copied();
+    assert!(slow_checksum.is_some());
+    assert_ne!(slow_checksum, fast_checksum);
+}
+
+#[derive(Resource, Clone, Copy)]
+struct NeverInserted;
+
+#[test]
+fn it_stores_a_structural_checksum_in_the_checksum() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.add_plugins(StructuralChecksumPlugin)
+        .rollback_component_with_copy::<Counter>()
+        .rollback_resource_with_copy::<NeverInserted>()
+        .init_resource::<SavedChecksums>()
+        .add_systems(Startup, |mut commands: Commands| {
+            for _ in 0..3 {
+                commands.spawn(Counter::default()).add_rollback();
+            }
+        })
+        .add_systems(GgrsSchedule, increase_counter)
+        .add_systems(SaveWorld, record_checksum.after(SaveWorldSet::Snapshot))
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let structural = *app.world.resource::<StructuralChecksum>();
+    assert_eq!(structural.rollback_entities, 3);
+
+    let registered = app.world.resource::<RollbackRegistry>().resources().len();
+    assert!(
+        (structural.resources_present.count_ones() as usize) < registered,
+        "An absent resource was reported as present"
+    );
+
+    let checksum = app.world.resource::<SavedChecksums>().0.last().copied();
+    assert_eq!(
+        checksum.map(StructuralChecksum::from_checksum),
+        Some(structural)
+    );
+}
+
+#[derive(Component, Reflect, Clone, Copy, Default, Hash)]
+#[reflect(Component)]
+struct Health(u32);
+
+/// Changes [`Health`] by a growing, non-rolled back offset, guaranteeing a desync.
+fn desync_health(mut healths: Query<&mut Health>, mut offset: Local<u32>) {
+    *offset += 1;
+
+    for mut health in &mut healths {
+        health.0 += *offset;
+    }
+}
+
+#[test]
+fn it_dumps_the_world_on_desync() {
+    let path = std::env::temp_dir().join("bevy_ggrs_it_dumps_the_world_on_desync.txt");
+    let _ = std::fs::remove_file(&path);
+
+    let mut app = create_app(
+        GgrsPlugin::default()
+            .with_input_history(2)
+            .with_desync_dump(&path),
+    );
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+        .register_type::<Health>()
+        .rollback_component_with_copy::<Health>()
+        .checksum_component_with_hash::<Health>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(Health::default()).add_rollback();
+        })
+        .add_systems(GgrsSchedule, desync_health)
+        .insert_resource(synctest_session(1));
+
+    app.update();
+
+    GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+    let dump = std::fs::read_to_string(&path).expect("No desync dump was written");
+    let _ = std::fs::remove_file(&path);
+
+    assert!(dump.contains("Reason: "));
+    assert!(dump
+        .lines()
+        .any(|line| line.contains("Health: ") && line.contains("Health(")));
+    assert!(dump.contains("0 frame(s) ago: 0: [01]"));
+
+    let (_, stored) = dump
+        .split_once("\nStored values\n")
+        .expect("No stored values were written");
+    let stored = stored.split("\n\n").next().unwrap();
+
+    let first = stored
+        .lines()
+        .position(|line| line.ends_with(", first saved"))
+        .expect("The mismatched frame was not written");
+    let last = stored
+        .lines()
+        .rposition(|line| line.ends_with(", last saved"))
+        .expect("The mismatched frame was not written as saved again");
+    assert!(first < last);
+    assert!(first > 0, "The frame before the mismatch was not written");
+
+    let healths = stored
+        .lines()
+        .filter(|line| line.contains("Health: "))
+        .collect::<Vec<_>>();
+    assert_eq!(healths.len(), 3);
+    assert_ne!(
+        healths[1], healths[2],
+        "The mismatched frame was saved with the same values"
+    );
+}
+
+#[test]
+fn it_runs_once_per_update_in_local_mode() {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default().with_local_mode(1))
+        // much shorter than a rollback frame, which would never advance with a fixed timestep
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(5)))
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(ReadInputs, read_local_inputs)
+        .add_systems(Startup, spawn_counter)
+        .add_systems(GgrsSchedule, increase_counter);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 10);
+    assert_eq!(app.world.query::<&Counter>().single(&app.world).0, 10);
+    assert_eq!(app.world.resource::<GgrsPlayers>().local, [0]);
+    assert_eq!(
+        app.world.resource::<Time<GgrsTime>>().elapsed(),
+        app.world.resource::<Time<Virtual>>().elapsed()
+    );
+
+    app.insert_resource(synctest_session(1));
+    app.update();
+
+    // the session starts over from the first frame, at a fixed timestep
+    assert_eq!(app.world.resource::<RollbackFrameCount>().0, 0);
+}
@@ -1,19 +1,21 @@
 use bevy::{
     input::{keyboard::KeyboardInput, ButtonState, Input, InputPlugin},
     prelude::*,
-    time::TimeUpdateStrategy,
-    utils::{Duration, HashMap},
-    MinimalPlugins,
+    utils::HashMap,
 };
 use bevy_ggrs::{
-    AddRollbackCommandExtension, GgrsConfig, GgrsPlugin, GgrsSchedule, LocalInputs, LocalPlayers,
-    PlayerInputs, ReadInputs, Rollback, Session,
+    AddRollbackCommandExtension, GgrsConfig, GgrsPlugin, GgrsPredictionStalled, GgrsSchedule,
+    GgrsSpectatorFellBehind, LocalInputs, LocalPlayers, PlayerInputs, PredictionStalled,
+    ReadInputs, Rollback, RollbackFrameCount, Session,
 };
 use bytemuck::{Pod, Zeroable};
 use ggrs::{Config, P2PSession, PlayerHandle, PlayerType, SessionBuilder, UdpNonBlockingSocket};
 use serial_test::serial;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+mod common;
+use common::minimal_app;
+
 #[test]
 #[serial]
 fn it_runs_advance_frame_schedule_systems() -> Result<(), Box<dyn std::error::Error>> {
@@ -69,15 +71,170 @@ fn it_syncs_rollback_components() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn it_reports_prediction_stalls() -> Result<(), Box<dyn std::error::Error>> {
+    let (player1, player2) = create_players();
+    let session1 = start_session(&player1, &player2)?;
+    let mut app1 = create_app::<TestConfig>(session1);
+    let session2 = start_session(&player2, &player1)?;
+    let mut app2 = create_app::<TestConfig>(session2);
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    assert!(!app1.world.resource::<PredictionStalled>().0);
+
+    // without remote inputs, app1 eventually exceeds its prediction window
+    for _ in 0..30 {
+        app1.update();
+    }
+
+    assert!(app1.world.resource::<PredictionStalled>().0);
+    assert!(!app1
+        .world
+        .resource::<Events<GgrsPredictionStalled>>()
+        .is_empty());
+
+    for _ in 0..50 {
+        app1.update();
+        app2.update();
+    }
+
+    assert!(!app1.world.resource::<PredictionStalled>().0);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_catches_up_spectators() -> Result<(), Box<dyn std::error::Error>> {
+    const HOST_PORT: u16 = 8083;
+    const SPECTATOR_PORT: u16 = 8084;
+    const MAX_CATCHUP: i32 = 8;
+
+    let localhost = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+    let host_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .add_player(PlayerType::Local, 0)?
+        .add_player(
+            PlayerType::Spectator(SocketAddr::new(localhost, SPECTATOR_PORT)),
+            1,
+        )?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(HOST_PORT)?)?;
+    let mut host = create_app::<TestConfig>(host_session);
+
+    let spectator_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .start_spectator_session(
+            SocketAddr::new(localhost, HOST_PORT),
+            UdpNonBlockingSocket::bind_to_port(SPECTATOR_PORT)?,
+        );
+    let mut spectator = create_app_with(
+        Session::Spectator(spectator_session),
+        GgrsPlugin::<TestConfig>::default().with_spectator_catchup(MAX_CATCHUP as usize),
+    );
+
+    for _ in 0..50 {
+        host.update();
+        spectator.update();
+    }
+
+    // the host keeps running while the spectator falls behind
+    for _ in 0..30 {
+        host.update();
+    }
+
+    // without catching up, two updates would advance at most two frames
+    let before = spectator.world.resource::<RollbackFrameCount>().0;
+    spectator.update();
+    spectator.update();
+    let advanced = spectator.world.resource::<RollbackFrameCount>().0 - before;
+
+    assert!(before > 0, "Spectator did not start");
+    assert!(
+        advanced > 2,
+        "Spectator advanced {advanced} frame(s) while behind"
+    );
+    assert!(
+        advanced <= 2 * MAX_CATCHUP,
+        "Spectator advanced {advanced} frames"
+    );
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn it_reports_spectators_falling_behind() -> Result<(), Box<dyn std::error::Error>> {
+    const HOST_PORT: u16 = 8085;
+    const SPECTATOR_PORT: u16 = 8086;
+    const MAX_LAG: usize = 5;
+
+    let localhost = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+    let host_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .add_player(PlayerType::Local, 0)?
+        .add_player(
+            PlayerType::Spectator(SocketAddr::new(localhost, SPECTATOR_PORT)),
+            1,
+        )?
+        .start_p2p_session(UdpNonBlockingSocket::bind_to_port(HOST_PORT)?)?;
+    let mut host = create_app::<TestConfig>(host_session);
+
+    let spectator_session = SessionBuilder::<TestConfig>::new()
+        .with_num_players(1)
+        .start_spectator_session(
+            SocketAddr::new(localhost, HOST_PORT),
+            UdpNonBlockingSocket::bind_to_port(SPECTATOR_PORT)?,
+        );
+    let mut spectator = create_app_with(
+        Session::Spectator(spectator_session),
+        GgrsPlugin::<TestConfig>::default().with_spectator_max_lag(MAX_LAG),
+    );
+
+    for _ in 0..50 {
+        host.update();
+        spectator.update();
+    }
+
+    assert!(spectator
+        .world
+        .resource::<Events<GgrsSpectatorFellBehind>>()
+        .is_empty());
+
+    // the host keeps running while the spectator falls behind
+    for _ in 0..30 {
+        host.update();
+    }
+
+    spectator.update();
+
+    let events = spectator
+        .world
+        .resource::<Events<GgrsSpectatorFellBehind>>();
+    let fell_behind = events.iter_current_update_events().next();
+
+    assert!(
+        fell_behind.is_some_and(|event| event.frames_behind > MAX_LAG),
+        "Spectator did not report falling behind: {fell_behind:?}"
+    );
+
+    Ok(())
+}
+
 fn create_app<T: Config>(session: P2PSession<T>) -> App {
-    let mut app = App::new();
-    app.add_plugins(MinimalPlugins)
-        .add_plugins(InputPlugin::default())
-        .add_plugins(GgrsPlugin::<T>::default())
-        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
-            1.0 / 60.0,
-        )))
-        .insert_resource(Session::P2P(session))
+    create_app_with(Session::P2P(session), GgrsPlugin::<T>::default())
+}
+
+fn create_app_with<T: Config>(session: Session<T>, plugin: GgrsPlugin<T>) -> App {
+    let mut app = minimal_app(plugin);
+    app.add_plugins(InputPlugin::default())
+        .insert_resource(session)
         .insert_resource(FrameCount { frame: 0 })
         .add_systems(GgrsSchedule, (move_player_system, increase_frame_system))
         .add_systems(ReadInputs, read_local_inputs)
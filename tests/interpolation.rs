@@ -0,0 +1,62 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_ggrs::{prelude::*, FrameOverstep, Interpolated};
+
+mod common;
+use common::*;
+
+#[derive(Component, Clone, Copy, Default, Debug, PartialEq)]
+struct Height(f32);
+
+fn lerp_height(from: &Height, to: &Height, fraction: f32) -> Height {
+    Height(from.0 + (to.0 - from.0) * fraction)
+}
+
+fn rise(mut heights: Query<&mut Height>) {
+    for mut height in &mut heights {
+        height.0 += 1.;
+    }
+}
+
+#[test]
+fn it_interpolates_between_frames() {
+    let mut app = create_app(GgrsPlugin::default());
+
+    // Updating at 3/4 of the rollback frame rate leaves a varying fraction of a frame accumulated
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        1.0 / 45.0,
+    )))
+    .rollback_component_with_copy::<Height>()
+    .interpolate_component::<Height>(lerp_height)
+    .add_systems(Startup, |mut commands: Commands| {
+        commands.spawn(Height::default()).add_rollback();
+    })
+    .add_systems(GgrsSchedule, rise)
+    .insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+
+        let fraction = app.world.resource::<FrameOverstep>().fraction();
+        assert!((0.0..=1.0).contains(&fraction));
+
+        let Some((height, interpolated)) = app
+            .world
+            .query::<(&Height, &Interpolated<Height>)>()
+            .iter(&app.world)
+            .next()
+            .map(|(&height, &interpolated)| (height, interpolated))
+        else {
+            continue;
+        };
+
+        assert!(interpolated.0 .0 <= height.0);
+
+        if height.0 > 0. {
+            let expected = height.0 - 1. + fraction;
+            assert!(
+                (interpolated.0 .0 - expected).abs() < 1e-4,
+                "Expected {expected}, interpolated {interpolated:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,597 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::HashMap};
+use bevy_ggrs::{
+    prelude::*, start_local_p2p_sessions, ConfirmedFrameCount, DisconnectInputPolicy,
+    GgrsComponentSnapshots, GgrsInputQuality, GgrsSessionBuilder, GgrsTimingState, LocalInputs,
+    LocalPlayers, LocalSocket, NetworkConditions, PendingSession, RollbackFrameCount,
+    RollbackStats, RollbackView, SimulatedClock, SimulatedSocket,
+};
+use ggrs::{GgrsError, PlayerHandle};
+use std::time::Duration;
+
+mod common;
+use common::{minimal_app, spawn_counter, Counter};
+
+type TestConfig = GgrsConfig<u8, usize>;
+
+/// Alternates inputs every few frames, so remote predictions are regularly wrong.
+fn read_alternating_inputs(
+    mut commands: Commands,
+    local_players: Res<LocalPlayers>,
+    frame: Res<RollbackFrameCount>,
+) {
+    let inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| (handle, ((frame.0 / 5 + handle as i32) % 2) as u8))
+        .collect::<HashMap<_, _>>();
+
+    commands.insert_resource(LocalInputs::<TestConfig>(inputs));
+}
+
+fn increase_counter(mut counters: Query<&mut Counter>, inputs: Res<PlayerInputs<TestConfig>>) {
+    for mut counter in &mut counters {
+        counter.0 += inputs.iter().map(|(input, _)| *input as u32).sum::<u32>();
+    }
+}
+
+/// Every local player holds the same input
+fn read_held_inputs(mut commands: Commands, local_players: Res<LocalPlayers>) {
+    let inputs = local_players.0.iter().map(|&handle| (handle, 3)).collect();
+
+    commands.insert_resource(LocalInputs::<TestConfig>(inputs));
+}
+
+fn create_app(plugin: GgrsPlugin<TestConfig>) -> App {
+    create_app_with_inputs(plugin, read_alternating_inputs)
+}
+
+fn create_app_with_inputs<M>(
+    plugin: GgrsPlugin<TestConfig>,
+    read_inputs: impl IntoSystemConfigs<M>,
+) -> App {
+    let mut app = minimal_app(plugin);
+
+    app.rollback_component_with_copy::<Counter>()
+        .add_systems(ReadInputs, read_inputs)
+        .add_systems(GgrsSchedule, increase_counter)
+        .add_systems(Startup, spawn_counter);
+
+    app
+}
+
+fn create_apps(conditions: NetworkConditions) -> [App; 2] {
+    create_apps_with_plugin(conditions, GgrsPlugin::default)
+}
+
+fn create_apps_with_plugin(
+    conditions: NetworkConditions,
+    plugin: fn() -> GgrsPlugin<TestConfig>,
+) -> [App; 2] {
+    let mut apps = [create_app(plugin()), create_app(plugin())];
+
+    start_sessions(&mut apps, conditions, |builder| {
+        builder.with_max_prediction_window(12)
+    });
+
+    apps
+}
+
+/// The [`SimulatedClock`] shared by the sockets of both apps, advanced by [`run`].
+#[derive(Resource, Clone, Default)]
+struct NetworkClock(SimulatedClock);
+
+fn start_sessions(
+    apps: &mut [App; 2],
+    conditions: NetworkConditions,
+    configure: impl Fn(
+        GgrsSessionBuilder<TestConfig>,
+    ) -> Result<GgrsSessionBuilder<TestConfig>, GgrsError>,
+) {
+    let clock = NetworkClock::default();
+    let sessions =
+        start_local_p2p_sessions::<TestConfig>(&apps[0].world, 2, conditions, &clock.0, configure)
+            .unwrap();
+
+    for (app, session) in apps.iter_mut().zip(sessions) {
+        app.insert_resource(session).insert_resource(clock.clone());
+    }
+}
+
+/// Updates both apps `updates` times, advancing the [`NetworkClock`] by one frame each time.
+fn run(apps: &mut [App; 2], updates: usize) {
+    let clock = apps[0].world.resource::<NetworkClock>().clone();
+
+    for _ in 0..updates {
+        for app in apps.iter_mut() {
+            app.update();
+        }
+
+        clock.0.advance(Duration::from_secs_f64(1.0 / 60.0));
+    }
+}
+
+#[test]
+fn it_rolls_back_over_simulated_latency() {
+    let mut apps = create_apps(NetworkConditions {
+        latency: Duration::from_millis(30),
+        jitter: Duration::from_millis(10),
+        packet_loss: 0.0,
+        seed: 1,
+    });
+
+    run(&mut apps, 150);
+
+    for app in &apps {
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 25);
+        assert!(
+            app.world.resource::<RollbackStats>().load_count() > 0,
+            "Delayed inputs were never mispredicted"
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+struct ConfirmedFrames(Vec<i32>);
+
+#[test]
+fn it_runs_the_confirmed_schedule_for_every_frame_confirmed_at_once() {
+    // remote inputs arrive in bursts, confirming several frames in the same step
+    let mut apps = create_apps(NetworkConditions {
+        latency: Duration::from_millis(50),
+        jitter: Duration::from_millis(40),
+        packet_loss: 0.0,
+        seed: 3,
+    });
+
+    for app in &mut apps {
+        app.init_resource::<ConfirmedFrames>().add_systems(
+            GgrsConfirmedSchedule,
+            |mut frames: ResMut<ConfirmedFrames>, confirmed: Res<ConfirmedFrameCount>| {
+                frames.0.push(confirmed.0);
+            },
+        );
+    }
+
+    run(&mut apps, 150);
+
+    for app in &apps {
+        let frames = &app.world.resource::<ConfirmedFrames>().0;
+
+        assert_eq!(
+            frames.first(),
+            Some(&0),
+            "Confirmed schedule skipped frame 0"
+        );
+        assert!(frames.len() > 25, "Frames were never confirmed");
+        assert!(
+            frames.windows(2).all(|pair| pair[1] == pair[0] + 1),
+            "Confirmed schedule did not run exactly once for each frame: {frames:?}"
+        );
+        assert_eq!(
+            frames.last().copied(),
+            Some(app.world.resource::<ConfirmedFrameCount>().0)
+        );
+    }
+}
+
+#[test]
+#[should_panic(expected = "Packet loss must be between 0 and 1")]
+fn it_rejects_packet_loss_above_one() {
+    let socket = LocalSocket::connected(1).pop().unwrap();
+    let conditions = NetworkConditions {
+        packet_loss: 1.5,
+        ..default()
+    };
+
+    SimulatedSocket::<_, usize>::new(socket, conditions, SimulatedClock::default());
+}
+
+#[test]
+fn it_counts_predicted_remote_inputs() {
+    let mut apps = create_apps(NetworkConditions {
+        latency: Duration::from_millis(30),
+        ..default()
+    });
+
+    run(&mut apps, 150);
+
+    for app in &apps {
+        let local = app.world.resource::<LocalPlayers>().0[0];
+        let remote = 1 - local;
+        let quality = app.world.resource::<GgrsInputQuality>();
+
+        assert!(!quality.is_empty());
+        assert_eq!(quality.num_players(), 2);
+        assert_eq!(quality.confirmed(local), quality.len());
+        assert!(
+            quality.predicted(remote) > 0,
+            "Delayed remote inputs were never predicted"
+        );
+        assert_eq!(
+            quality.predicted(remote) + quality.confirmed(remote),
+            quality.len()
+        );
+    }
+}
+
+#[test]
+fn it_never_connects_when_every_message_is_lost() {
+    let mut apps = create_apps(NetworkConditions {
+        packet_loss: 1.0,
+        ..default()
+    });
+
+    run(&mut apps, 50);
+
+    for app in &apps {
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, 0);
+    }
+}
+
+#[test]
+fn it_stops_running_slow_within_an_update() {
+    let mut apps = create_apps(NetworkConditions {
+        packet_loss: 1.0,
+        ..default()
+    });
+
+    for app in &mut apps {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    }
+
+    run(&mut apps, 1);
+
+    // the session is never ahead, so only the first step of the update runs slow
+    let app = &mut apps[0];
+
+    GgrsTimingState {
+        run_slow: true,
+        ..default()
+    }
+    .apply(&mut app.world);
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+        10.5 / 60.0,
+    )));
+    app.update();
+
+    // 1.1 + 9 * 1 frames, rather than 9 * 1.1 frames if every step ran slow
+    let state = GgrsTimingState::capture(&app.world);
+    let remaining = state.accumulator.as_secs_f64() * 60.0;
+
+    assert!(!state.run_slow);
+    assert!(
+        (remaining - 0.4).abs() < 0.05,
+        "{remaining} frames remained after the update, expected about 0.4"
+    );
+}
+
+#[test]
+fn it_reports_the_frames_run_before_connecting() {
+    let mut apps = create_apps(NetworkConditions {
+        packet_loss: 1.0,
+        ..default()
+    });
+
+    for app in &mut apps {
+        let advanced = GgrsPlugin::<TestConfig>::run_n_frames(&mut app.world, 10);
+
+        assert_eq!(advanced, 0);
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, 0);
+    }
+}
+
+#[test]
+fn it_runs_in_lockstep_without_snapshots() {
+    let mut apps = [
+        create_app(GgrsPlugin::default().lockstep()),
+        create_app(GgrsPlugin::default().lockstep()),
+    ];
+
+    start_sessions(&mut apps, NetworkConditions::default(), Ok);
+
+    run(&mut apps, 120);
+
+    for app in &apps {
+        let session = app.world.resource::<Session<TestConfig>>();
+        assert_eq!(session.as_p2p().unwrap().max_prediction(), 0);
+
+        assert!(app.world.resource::<RollbackFrameCount>().0 > 25);
+        assert_eq!(app.world.resource::<RollbackStats>().load_count(), 0);
+
+        let snapshots = app.world.resource::<GgrsComponentSnapshots<Counter>>();
+        assert_eq!(
+            snapshots.frames().count(),
+            0,
+            "Lockstep frames were snapshot"
+        );
+    }
+}
+
+#[test]
+fn it_advances_sessions_with_zero_max_prediction() {
+    let mut apps = [
+        create_app(GgrsPlugin::default()),
+        create_app(GgrsPlugin::default()),
+    ];
+
+    start_sessions(&mut apps, NetworkConditions::default(), |builder| {
+        builder.with_max_prediction_window(0)
+    });
+
+    run(&mut apps, 120);
+
+    let frames = apps
+        .each_mut()
+        .map(|app| app.world.resource::<RollbackFrameCount>().0);
+    let counters = apps
+        .each_mut()
+        .map(|app| app.world.query::<&Counter>().single(&app.world).0);
+
+    assert!(
+        frames[0] > 25 && frames[1] > 25,
+        "Advanced {frames:?} frames"
+    );
+
+    // every frame is advanced with confirmed inputs, so peers at the same frame agree
+    if frames[0] == frames[1] {
+        assert_eq!(counters[0], counters[1]);
+    }
+}
+
+#[derive(Resource, Default)]
+struct InputReads(usize);
+
+fn count_input_reads(mut reads: ResMut<InputReads>) {
+    reads.0 += 1;
+}
+
+#[test]
+fn it_starts_pending_sessions_once_connected() {
+    let mut apps = [
+        create_app(GgrsPlugin::default()),
+        create_app(GgrsPlugin::default()),
+    ];
+
+    let clock = NetworkClock::default();
+
+    for (app, socket) in apps.iter_mut().zip(LocalSocket::connected(2)) {
+        let mut socket = Some(socket);
+        let mut polls = 0;
+
+        app.insert_resource(clock.clone())
+            .insert_resource(PendingSession::<TestConfig>::new(
+                move |world: &mut World| {
+                    // the transport takes a few updates to connect
+                    polls += 1;
+                    if polls < 5 {
+                        return None;
+                    }
+
+                    let socket = socket.take()?;
+                    let local = socket.address();
+                    let mut builder = GgrsSessionBuilder::<TestConfig>::new(world, 2).unwrap();
+
+                    for handle in 0..2 {
+                        let player = if handle == local {
+                            PlayerType::Local
+                        } else {
+                            PlayerType::Remote(handle)
+                        };
+
+                        builder = builder.add_player(player, handle).unwrap();
+                    }
+
+                    Some(builder.start_p2p_session(socket).unwrap())
+                },
+            ));
+    }
+
+    run(&mut apps, 4);
+
+    for app in &apps {
+        assert!(!app.world.contains_resource::<Session<TestConfig>>());
+        assert!(app.world.contains_resource::<PendingSession<TestConfig>>());
+    }
+
+    run(&mut apps, 120);
+
+    for app in &mut apps {
+        assert!(!app.world.contains_resource::<PendingSession<TestConfig>>());
+        assert!(
+            app.world.resource::<RollbackFrameCount>().0 > 25,
+            "The pending session did not start"
+        );
+    }
+}
+
+#[test]
+fn it_advances_p2p_sessions_without_local_players() -> Result<(), GgrsError> {
+    let mut apps = [
+        create_app(GgrsPlugin::default()),
+        create_app(GgrsPlugin::default()),
+    ];
+
+    apps[1]
+        .init_resource::<InputReads>()
+        .add_systems(ReadInputs, count_input_reads);
+
+    let clock = NetworkClock::default();
+    let mut sockets = LocalSocket::connected(2).into_iter();
+    let (host_socket, observer_socket) = (sockets.next().unwrap(), sockets.next().unwrap());
+    let (host, observer) = (host_socket.address(), observer_socket.address());
+
+    // the host plays both players, sending their confirmed inputs to the observer
+    let host_session = GgrsSessionBuilder::<TestConfig>::new(&apps[0].world, 2)?
+        .add_local_player(0)?
+        .add_local_player(1)?
+        .add_spectator(2, observer)?
+        .start_p2p_session(SimulatedSocket::new(
+            host_socket,
+            NetworkConditions::default(),
+            clock.0.clone(),
+        ))?;
+
+    let observer_session = GgrsSessionBuilder::<TestConfig>::new(&apps[1].world, 2)?
+        .add_player(PlayerType::Remote(host), 0)?
+        .add_player(PlayerType::Remote(host), 1)?
+        .start_p2p_session(SimulatedSocket::new(
+            observer_socket,
+            NetworkConditions::default(),
+            clock.0.clone(),
+        ))?;
+
+    apps[0]
+        .insert_resource(host_session)
+        .insert_resource(clock.clone());
+    apps[1]
+        .insert_resource(observer_session)
+        .insert_resource(clock);
+
+    run(&mut apps, 120);
+
+    let observer = &mut apps[1];
+    let frame = observer.world.resource::<RollbackFrameCount>().0;
+
+    assert!(frame > 25, "The observer did not advance");
+    assert!(observer.world.resource::<LocalPlayers>().0.is_empty());
+    assert_eq!(
+        observer.world.resource::<InputReads>().0,
+        0,
+        "Inputs were read without local players"
+    );
+
+    // exactly one of the two players presses their button each frame
+    let counter = observer.world.query::<&Counter>().single(&observer.world);
+    assert_eq!(counter.0, frame as u32);
+
+    Ok(())
+}
+
+#[derive(Resource, Default)]
+struct DisconnectedInputs(Vec<u8>);
+
+fn record_disconnected_inputs(
+    mut recorded: ResMut<DisconnectedInputs>,
+    inputs: Res<PlayerInputs<TestConfig>>,
+) {
+    for handle in inputs.disconnected() {
+        recorded.0.push(inputs.by_handle(handle).0);
+    }
+}
+
+#[test]
+fn it_provides_the_neutral_input_for_disconnected_players() {
+    let mut apps = create_apps_with_plugin(NetworkConditions::default(), || {
+        GgrsPlugin::default().with_disconnect_input_policy(DisconnectInputPolicy::Neutral(7))
+    });
+
+    apps[0].init_resource::<DisconnectedInputs>().add_systems(
+        GgrsSchedule,
+        record_disconnected_inputs.before(increase_counter),
+    );
+
+    run(&mut apps, 30);
+    disconnect_remote_player(&mut apps[0]);
+    run(&mut apps, 30);
+
+    let recorded = &apps[0].world.resource::<DisconnectedInputs>().0;
+
+    assert!(!recorded.is_empty(), "The remote player never disconnected");
+    assert!(recorded.iter().all(|&input| input == 7));
+}
+
+#[test]
+fn it_repeats_the_last_input_of_disconnected_players() {
+    let mut apps = [0, 1].map(|_| create_app_with_inputs(GgrsPlugin::default(), read_held_inputs));
+
+    // set on the app rather than through the plugin, so must be rolled back all the same
+    for app in &mut apps {
+        app.set_disconnect_input_policy(DisconnectInputPolicy::<TestConfig>::Repeat);
+    }
+
+    start_sessions(&mut apps, NetworkConditions::default(), |builder| {
+        builder.with_max_prediction_window(12)
+    });
+
+    apps[0].init_resource::<DisconnectedInputs>().add_systems(
+        GgrsSchedule,
+        record_disconnected_inputs.before(increase_counter),
+    );
+
+    run(&mut apps, 30);
+    disconnect_remote_player(&mut apps[0]);
+    run(&mut apps, 30);
+
+    let recorded = &apps[0].world.resource::<DisconnectedInputs>().0;
+
+    assert!(!recorded.is_empty(), "The remote player never disconnected");
+    assert!(recorded.iter().all(|&input| input == 3));
+}
+
+fn disconnect_remote_player(app: &mut App) {
+    let local = app.world.resource::<LocalPlayers>().0[0];
+    let remote = 1 - local;
+
+    let mut session = app.world.resource_mut::<Session<TestConfig>>();
+    let Session::P2P(session) = session.as_mut() else {
+        panic!("Expected a P2P session");
+    };
+    session.disconnect_player(remote).unwrap();
+}
+
+/// Alternates input with the parity of the counter, reading only rollback state
+fn follow_counter(_handle: PlayerHandle, world: &RollbackView) -> u8 {
+    world
+        .iter::<Counter>()
+        .next()
+        .map_or(0, |(_, counter)| (counter.0 % 2) as u8)
+}
+
+#[derive(Resource, Default)]
+struct AiInputChecks {
+    checked: usize,
+    mismatched: usize,
+}
+
+fn check_ai_input(
+    counters: Query<&Counter>,
+    inputs: Res<PlayerInputs<TestConfig>>,
+    mut checks: ResMut<AiInputChecks>,
+) {
+    let expected = (counters.single().0 % 2) as u8;
+
+    checks.checked += 1;
+
+    if inputs[1].0 != expected {
+        checks.mismatched += 1;
+    }
+}
+
+#[test]
+fn it_produces_ai_inputs_from_rollback_state() {
+    let mut apps = create_apps_with_plugin(
+        NetworkConditions {
+            latency: Duration::from_millis(30),
+            ..default()
+        },
+        || GgrsPlugin::default().with_ai_players([1], follow_counter),
+    );
+
+    for app in &mut apps {
+        app.init_resource::<AiInputChecks>()
+            .add_systems(GgrsSchedule, check_ai_input.before(increase_counter));
+    }
+
+    run(&mut apps, 120);
+
+    for app in &apps {
+        let checks = app.world.resource::<AiInputChecks>();
+
+        assert!(checks.checked > 25);
+        assert_eq!(
+            checks.mismatched, 0,
+            "AI inputs did not follow the rollback state"
+        );
+    }
+}
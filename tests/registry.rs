@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy_ggrs::{prelude::*, RollbackKind, RollbackRegistry, UnregisteredReflectType};
+
+#[derive(Component, Resource, Clone, Copy)]
+struct Health(u32);
+
+#[test]
+#[should_panic(expected = "has already been registered")]
+fn it_panics_on_conflicting_component_registrations() {
+    App::new()
+        .add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+        .rollback_component_with_copy::<Health>()
+        .rollback_component_with_clone::<Health>();
+}
+
+#[test]
+#[should_panic(expected = "has already been registered")]
+fn it_panics_on_conflicting_resource_registrations() {
+    App::new()
+        .add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+        .rollback_resource_with_clone::<Health>()
+        .rollback_resource_with_copy::<Health>();
+}
+
+#[test]
+fn it_allows_component_and_resource_registrations() {
+    App::new()
+        .add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+        .rollback_component_with_copy::<Health>()
+        .rollback_resource_with_copy::<Health>();
+}
+
+#[test]
+fn it_lists_registered_types() {
+    let mut app = App::new();
+    app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+        .rollback_component_with_copy::<Health>();
+
+    let registry = app.world.resource::<RollbackRegistry>();
+
+    assert!(registry.is_registered::<Health>(RollbackKind::Component));
+    assert!(!registry.is_registered::<Health>(RollbackKind::Resource));
+
+    let components = registry
+        .components()
+        .into_iter()
+        .map(|registration| registration.type_name)
+        .collect::<Vec<_>>();
+    assert!(components.contains(&std::any::type_name::<Health>()));
+    assert!(components.contains(&std::any::type_name::<Parent>()));
+}
+
+#[test]
+fn it_lists_render_only_types() {
+    let mut app = App::new();
+    app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+        .rollback_component_with_copy::<Health>()
+        .rollback_component_with_clone::<Transform>()
+        .rollback_component_with_clone::<GlobalTransform>()
+        .rollback_component_with_clone::<Visibility>()
+        .rollback_component_with_clone::<Handle<Image>>();
+
+    let render_only = app
+        .world
+        .resource::<RollbackRegistry>()
+        .render_only_types()
+        .into_iter()
+        .map(|(registration, _)| registration.type_name)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        render_only,
+        vec![
+            std::any::type_name::<Handle<Image>>(),
+            std::any::type_name::<Visibility>(),
+            std::any::type_name::<GlobalTransform>(),
+        ]
+    );
+}
+
+#[derive(Reflect, Default)]
+struct Stats {
+    strength: u32,
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+struct Character {
+    stats: Stats,
+}
+
+#[test]
+fn it_lists_unregistered_nested_reflect_types() {
+    let mut app = App::new();
+    app.add_plugins(GgrsPlugin::<GgrsConfig<u8>>::default())
+        .rollback_component_with_reflect::<Character>();
+
+    let unregistered = app
+        .world
+        .resource::<RollbackRegistry>()
+        .unregistered_reflect_types(&app.world.resource::<AppTypeRegistry>().read());
+
+    assert_eq!(
+        unregistered,
+        vec![UnregisteredReflectType {
+            rollback_type: std::any::type_name::<Character>(),
+            type_path: std::any::type_name::<Stats>(),
+        }]
+    );
+
+    app.register_type::<Stats>();
+
+    let unregistered = app
+        .world
+        .resource::<RollbackRegistry>()
+        .unregistered_reflect_types(&app.world.resource::<AppTypeRegistry>().read());
+
+    assert!(unregistered.is_empty());
+}
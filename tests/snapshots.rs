@@ -0,0 +1,1355 @@
+use bevy::{
+    ecs::entity::{EntityMapper, MapEntities},
+    prelude::*,
+};
+use bevy_ggrs::{
+    apply_snapshot, capture_snapshot, prelude::*, ComponentGroups, EntityInstantiator,
+    EntitySnapshotPlugin, GgrsComponentSnapshots, GgrsResimStats, GgrsSnapshots, GgrsWillDespawn,
+    LoadWorld, MissingSnapshotPolicy, PrevFrame, RollbackFrameCount, RollbackIds, RollbackOrdered,
+    SaveWorld, SnapshotMemoryLimit, SnapshotMemoryUsage, Strategy, WorldSnapshot,
+};
+use ggrs::GgrsRequest;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod common;
+use common::*;
+
+fn create_synctest_app(check_distance: usize) -> App {
+    let mut app = create_counter_app(GgrsPlugin::default(), 1);
+
+    app.insert_resource(synctest_session_with_check_distance(1, check_distance));
+
+    app
+}
+
+fn assert_counter_matches_frame(app: &mut App) {
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(
+        counter, frame as u32,
+        "Counter diverged from the frame count"
+    );
+}
+
+#[test]
+fn it_resimulates_from_keyframes() {
+    let mut app = create_synctest_app(3);
+    app.set_snapshot_keyframe_interval(4);
+
+    for _ in 0..40 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+}
+
+/// Discards the entity snapshot of every odd frame as soon as it is saved, as if its slot had been overwritten.
+fn discard_odd_entity_snapshots(
+    mut snapshots: ResMut<GgrsComponentSnapshots<Entity>>,
+    frame: Res<RollbackFrameCount>,
+) {
+    if frame.0 % 2 == 1 && snapshots.peek(frame.0 - 1).is_some() {
+        snapshots.rollback(frame.0 - 1);
+    }
+}
+
+#[test]
+fn it_resimulates_from_the_nearest_snapshot_when_one_is_missing() {
+    let mut app = create_synctest_app(2);
+    app.insert_resource(MissingSnapshotPolicy::Resimulate)
+        .add_systems(
+            SaveWorld,
+            discard_odd_entity_snapshots.after(SaveWorldSet::Snapshot),
+        );
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+}
+
+#[test]
+#[should_panic(expected = "no snapshot of frame")]
+fn it_panics_on_missing_snapshots_by_default() {
+    let mut app = create_synctest_app(2);
+    app.add_systems(
+        SaveWorld,
+        discard_odd_entity_snapshots.after(SaveWorldSet::Snapshot),
+    );
+
+    for _ in 0..30 {
+        app.update();
+    }
+}
+
+/// Discards every entity snapshot as soon as it is saved, leaving nothing to re-simulate from.
+fn discard_entity_snapshots(mut snapshots: ResMut<GgrsComponentSnapshots<Entity>>) {
+    snapshots.clear();
+}
+
+#[test]
+#[should_panic(expected = "no earlier snapshot can be re-simulated from")]
+fn it_reports_an_error_when_no_snapshot_can_be_resimulated_from() {
+    let mut app = create_synctest_app(2);
+    app.insert_resource(MissingSnapshotPolicy::Resimulate)
+        .set_strict_errors(true)
+        .add_systems(
+            SaveWorld,
+            discard_entity_snapshots.after(SaveWorldSet::Snapshot),
+        );
+
+    for _ in 0..30 {
+        app.update();
+    }
+}
+
+#[derive(Resource, Default)]
+struct HookCounts {
+    prepared: u32,
+    loaded: u32,
+}
+
+#[test]
+fn it_runs_save_and_load_hooks() {
+    let mut app = create_synctest_app(2);
+    app.init_resource::<HookCounts>()
+        .add_systems(
+            SaveWorld,
+            (|mut counts: ResMut<HookCounts>| counts.prepared += 1).in_set(SaveWorldSet::Prepare),
+        )
+        .add_systems(
+            LoadWorld,
+            (|mut counts: ResMut<HookCounts>| counts.loaded += 1).in_set(LoadWorldSet::PostLoad),
+        );
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let counts = app.world.resource::<HookCounts>();
+    assert!(counts.loaded > 0, "PostLoad hooks never ran");
+    assert!(
+        counts.prepared > counts.loaded,
+        "Prepare hooks should run for every save"
+    );
+}
+
+#[derive(Component, Clone, Copy, Default)]
+struct Doubled {
+    value: u32,
+    doubled: u32,
+}
+
+fn double(doubled: &mut Doubled) {
+    doubled.doubled = doubled.value * 2;
+}
+
+/// Every [`Doubled`] restored by a load, once its fixup has run
+#[derive(Resource, Default)]
+struct LoadedDoubles(Vec<Doubled>);
+
+fn record_loaded_doubles(query: Query<&Doubled>, mut loaded: ResMut<LoadedDoubles>) {
+    loaded.0.extend(query.iter().copied());
+}
+
+#[test]
+fn it_applies_post_load_fixups() {
+    let mut app = create_synctest_app(2);
+    app.init_resource::<LoadedDoubles>()
+        .rollback_component_with_copy::<Doubled>()
+        .update_component_with_post_load::<Doubled>(double)
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(Doubled::default()).add_rollback();
+        })
+        .add_systems(
+            GgrsSchedule,
+            |mut query: Query<&mut Doubled>, frame: Res<RollbackFrameCount>| {
+                // `doubled` is deliberately left stale, only the fixup updates it
+                for mut doubled in &mut query {
+                    doubled.value = frame.0 as u32;
+                }
+            },
+        )
+        .add_systems(
+            LoadWorld,
+            record_loaded_doubles.after(LoadWorldSet::PostLoad),
+        );
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let loaded = &app.world.resource::<LoadedDoubles>().0;
+    assert!(
+        loaded.iter().any(|doubled| doubled.value > 0),
+        "No frame was loaded after the value changed"
+    );
+
+    for doubled in loaded {
+        assert_eq!(
+            doubled.doubled,
+            doubled.value * 2,
+            "Fixup did not run on the loaded value"
+        );
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+struct Marker;
+
+fn toggle_marker(
+    mut commands: Commands,
+    counters: Query<(Entity, Option<&Marker>), With<Counter>>,
+) {
+    for (entity, marker) in &counters {
+        if marker.is_some() {
+            commands.entity(entity).remove::<Marker>();
+        } else {
+            commands.entity(entity).insert(Marker);
+        }
+    }
+}
+
+#[test]
+fn it_rolls_back_marker_components() {
+    let mut app = create_synctest_app(2);
+    app.rollback_component_with_copy::<Marker>()
+        .add_systems(GgrsSchedule, toggle_marker);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // The marker is toggled every frame, so its presence depends on every earlier frame
+    // having restored it correctly.
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let has_marker = app
+        .world
+        .query_filtered::<(), (With<Counter>, With<Marker>)>()
+        .iter(&app.world)
+        .count()
+        == 1;
+    assert_eq!(has_marker, frame % 2 == 1);
+}
+
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+enum Stance {
+    #[default]
+    Idle,
+    Walking {
+        steps: u32,
+    },
+    Jumping(u32),
+}
+
+impl Stance {
+    fn next(self) -> Self {
+        match self {
+            Stance::Idle => Stance::Walking { steps: 1 },
+            Stance::Walking { steps: 3 } => Stance::Jumping(3),
+            Stance::Walking { steps } => Stance::Walking { steps: steps + 1 },
+            Stance::Jumping(_) => Stance::Idle,
+        }
+    }
+}
+
+/// The stances taken so far, with a gap every third frame. Derived reflection
+/// [applies](`Reflect::apply`) a shorter list in place, overwriting its first elements and leaving
+/// the rest behind.
+#[derive(Component, Reflect, Clone, Debug, Default, PartialEq)]
+struct Trail(Vec<Option<Stance>>);
+
+impl Trail {
+    fn next(&self) -> Self {
+        let last = self.0.iter().rev().find_map(|&stance| stance);
+        let mut next = self.clone();
+        next.0
+            .push((self.0.len() % 3 != 2).then(|| last.unwrap_or_default().next()));
+        next
+    }
+}
+
+fn advance_trail(mut trails: Query<&mut Trail>) {
+    for mut trail in &mut trails {
+        *trail = trail.next();
+    }
+}
+
+#[test]
+fn it_rolls_back_enum_variant_changes_with_from_reflect() {
+    let mut app = create_synctest_app(2);
+    app.rollback_component_with_from_reflect::<Trail>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(Trail::default()).add_rollback();
+        })
+        .add_systems(GgrsSchedule, advance_trail);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // Rollbacks load snapshots of a shorter trail than the live one, whose stances are in
+    // different variants, which would leave stale stances behind if they were applied in place
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let expected = (0..frame).fold(Trail::default(), |trail, _| trail.next());
+    let trail = app.world.query::<&Trail>().single(&app.world);
+    assert_eq!(trail.0.len(), frame as usize);
+    assert_eq!(*trail, expected);
+}
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+struct LateScore(u32);
+
+fn insert_late_score(mut commands: Commands, frame: Res<RollbackFrameCount>) {
+    if frame.0 == 5 {
+        commands.insert_resource(LateScore::default());
+    }
+}
+
+fn increase_late_score(score: Option<ResMut<LateScore>>) {
+    if let Some(mut score) = score {
+        score.0 += 1;
+    }
+}
+
+#[test]
+fn it_rolls_back_resources_inserted_after_the_first_save() {
+    let mut app = create_synctest_app(2);
+    app.rollback_resource_with_copy::<LateScore>()
+        .add_systems(GgrsSchedule, (insert_late_score, increase_late_score));
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.get_resource::<LateScore>(), None);
+
+    for _ in 0..27 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // inserted at the end of frame 5, then increased every frame after it
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert_eq!(
+        app.world.get_resource::<LateScore>(),
+        Some(&LateScore(frame as u32 - 5))
+    );
+}
+
+/// Adds a growing, non-rolled back offset to every [`Counter`], guaranteeing a desync.
+fn desync_counter(mut counters: Query<&mut Counter>, mut offset: Local<u32>) {
+    *offset += 1;
+
+    for mut counter in &mut counters {
+        counter.0 += *offset;
+    }
+}
+
+#[test]
+#[should_panic(expected = "GGRS error")]
+fn it_panics_on_desync_with_strict_errors() {
+    let mut app = create_synctest_app(2);
+    app.set_strict_errors(true)
+        .checksum_component::<Counter>(|counter| counter.0 as u64)
+        .add_systems(GgrsSchedule, desync_counter.after(increase_counter));
+
+    for _ in 0..30 {
+        app.update();
+    }
+}
+
+fn grow_name(mut names: Query<&mut Name>) {
+    for mut name in &mut names {
+        let grown = format!("{}x", name.as_str());
+        name.set(grown);
+    }
+}
+
+#[test]
+fn it_rolls_back_bevy_builtins() {
+    let mut app = create_synctest_app(2);
+    app.rollback_bevy_builtins()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands
+                .spawn((Name::new(""), Transform::default()))
+                .add_rollback();
+        })
+        .add_systems(GgrsSchedule, grow_name);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // The name grows every frame, so its length depends on every earlier frame
+    // having restored it correctly.
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let name = app.world.query::<&Name>().single(&app.world);
+    assert_eq!(name.len(), frame as usize);
+}
+
+fn spawn_transform(mut commands: Commands) {
+    commands.spawn(Transform::default()).add_rollback();
+}
+
+fn move_transforms(mut transforms: Query<&mut Transform>, inputs: Res<PlayerInputs<TestConfig>>) {
+    for mut transform in &mut transforms {
+        transform.translation.x += inputs[0].0 as f32;
+    }
+}
+
+#[test]
+fn it_rolls_back_transforms_without_reflection() {
+    let mut app = create_synctest_app(2);
+    app.rollback_transform()
+        .add_systems(Startup, spawn_transform)
+        .add_systems(GgrsSchedule, move_transforms);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let transform = app.world.query::<&Transform>().single(&app.world);
+    assert_eq!(transform.translation.x, frame as f32);
+
+    // MinimalPlugins does not register Transform, so reflection can't have been used
+    let registry = app.world.resource::<AppTypeRegistry>().read();
+    assert!(
+        registry.get(std::any::TypeId::of::<Transform>()).is_none(),
+        "Transform was registered for reflection"
+    );
+}
+
+/// Adds a growing, non-rolled back offset to every [`Transform`], guaranteeing a desync.
+fn desync_transforms(mut transforms: Query<&mut Transform>, mut offset: Local<f32>) {
+    *offset += 1.0;
+
+    for mut transform in &mut transforms {
+        transform.translation.y += *offset;
+    }
+}
+
+#[test]
+#[should_panic(expected = "GGRS error")]
+fn it_checksums_transforms_rolled_back_without_reflection() {
+    let mut app = create_synctest_app(2);
+    app.set_strict_errors(true)
+        .rollback_transform()
+        .add_systems(Startup, spawn_transform)
+        .add_systems(GgrsSchedule, desync_transforms);
+
+    for _ in 0..30 {
+        app.update();
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+struct Spawned;
+
+fn spawn_on_frame_five(mut commands: Commands, frame: Res<RollbackFrameCount>) {
+    if frame.0 == 5 {
+        commands.spawn_rollback(Spawned);
+    }
+}
+
+#[test]
+fn it_removes_spawns_on_rollback() {
+    let mut app = create_synctest_app(3);
+    app.add_systems(GgrsSchedule, spawn_on_frame_five);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // Frame 5 is re-simulated several times, each rollback must remove the previous spawn
+    let spawned = app
+        .world
+        .query_filtered::<(), (With<Spawned>, With<Rollback>)>()
+        .iter(&app.world)
+        .count();
+    assert_eq!(spawned, 1);
+}
+
+fn spawn_in_update(mut commands: Commands, frame: Res<RollbackFrameCount>) {
+    if frame.0 >= 5 {
+        commands.spawn_rollback(Spawned);
+    }
+}
+
+#[test]
+#[should_panic(expected = "added to rollback outside of the GgrsSchedule")]
+fn it_panics_on_spawns_outside_the_rollback_schedules_with_strict_errors() {
+    let mut app = create_synctest_app(2);
+    app.set_strict_errors(true)
+        .add_systems(Update, spawn_in_update);
+
+    for _ in 0..30 {
+        app.update();
+    }
+}
+
+static SAVES: AtomicUsize = AtomicUsize::new(0);
+static LOADS: AtomicUsize = AtomicUsize::new(0);
+static ADVANCES: AtomicUsize = AtomicUsize::new(0);
+
+fn count_requests(request: &GgrsRequest<TestConfig>) {
+    let counter = match request {
+        GgrsRequest::SaveGameState { .. } => &SAVES,
+        GgrsRequest::LoadGameState { .. } => &LOADS,
+        GgrsRequest::AdvanceFrame { .. } => &ADVANCES,
+    };
+
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+#[test]
+fn it_observes_requests() {
+    let mut app = create_synctest_app(2);
+    app.observe_requests(count_requests);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0 as usize;
+    let loads = LOADS.load(Ordering::Relaxed);
+    assert!(loads > 0, "SyncTest should have requested loads");
+    assert!(SAVES.load(Ordering::Relaxed) >= frame);
+    assert!(
+        ADVANCES.load(Ordering::Relaxed) > frame,
+        "Re-simulated advances were not observed"
+    );
+}
+
+#[test]
+fn it_keeps_a_snapshot_with_zero_depth() {
+    let mut snapshots = GgrsSnapshots::<Counter>::default();
+    snapshots.set_depth(0);
+
+    snapshots.push(0, Counter(0)).push(1, Counter(1));
+
+    assert_eq!(snapshots.depth(), 1);
+    assert_eq!(snapshots.rollback(1).get().0, 1);
+    assert!(snapshots.peek(0).is_none());
+}
+
+#[test]
+fn it_rolls_back_rollback_ordering() {
+    let mut app = create_synctest_app(3);
+    app.add_systems(GgrsSchedule, spawn_on_frame_five);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // Re-simulating frame 5 must reuse the order it was first spawned with
+    let rollback = *app
+        .world
+        .query_filtered::<&Rollback, With<Spawned>>()
+        .single(&app.world);
+    let ordered = app.world.resource::<RollbackOrdered>();
+    assert_eq!(ordered.len(), 2);
+    assert_eq!(ordered.order(rollback), 1);
+}
+
+static STATES_SAVED: AtomicUsize = AtomicUsize::new(0);
+
+fn save_counter_state(world: &World) -> u8 {
+    STATES_SAVED.fetch_add(1, Ordering::Relaxed);
+
+    world
+        .resource::<RollbackFrameCount>()
+        .0
+        .rem_euclid(u8::MAX as i32 + 1) as u8
+}
+
+#[test]
+fn it_saves_state_into_ggrs_buffer() {
+    let mut app = create_synctest_app(2);
+    app.save_ggrs_state_with::<TestConfig>(save_counter_state);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0 as usize;
+    assert!(STATES_SAVED.load(Ordering::Relaxed) >= frame);
+}
+
+static KEYFRAME_STATES_SAVED: AtomicUsize = AtomicUsize::new(0);
+
+fn save_keyframe_state(_world: &World) -> u8 {
+    KEYFRAME_STATES_SAVED.fetch_add(1, Ordering::Relaxed);
+    0
+}
+
+#[test]
+fn it_saves_state_into_ggrs_buffer_between_keyframes() {
+    let mut app = create_synctest_app(2);
+    app.set_snapshot_keyframe_interval(4)
+        .save_ggrs_state_with::<TestConfig>(save_keyframe_state);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    // only every fourth frame is snapshot, but every frame is stored in the buffer
+    let frame = app.world.resource::<RollbackFrameCount>().0 as usize;
+    assert!(KEYFRAME_STATES_SAVED.load(Ordering::Relaxed) >= frame);
+}
+
+#[test]
+fn it_captures_and_applies_snapshots_on_demand() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .rollback_component_with_copy::<Counter>()
+        .add_systems(Startup, spawn_counter);
+
+    app.update();
+
+    let frame = capture_snapshot(&mut app.world);
+
+    app.world
+        .query::<&mut Counter>()
+        .single_mut(&mut app.world)
+        .0 = 5;
+
+    assert!(!apply_snapshot(&mut app.world, frame + 1));
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(counter, 0);
+}
+
+#[test]
+fn it_applies_owned_snapshots_after_they_would_be_discarded() {
+    let mut app = create_synctest_app(2);
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let mut checkpoint = WorldSnapshot::capture(&mut app.world);
+    let frame = checkpoint.frame();
+    let counter = app.world.query::<&Counter>().single(&app.world).0;
+    assert_eq!(frame, app.world.resource::<RollbackFrameCount>().0);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let snapshots = app.world.resource::<GgrsComponentSnapshots<Counter>>();
+    assert!(snapshots.frames().all(|stored| stored > frame));
+
+    for _ in 0..2 {
+        checkpoint.apply(&mut app.world);
+
+        assert_eq!(app.world.resource::<RollbackFrameCount>().0, frame);
+        assert_eq!(app.world.query::<&Counter>().single(&app.world).0, counter);
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+#[component(storage = "SparseSet")]
+struct SparseMarker;
+
+fn toggle_sparse_marker(
+    mut commands: Commands,
+    counters: Query<(Entity, Option<&SparseMarker>), With<Counter>>,
+) {
+    for (entity, marker) in &counters {
+        if marker.is_some() {
+            commands.entity(entity).remove::<SparseMarker>();
+        } else {
+            commands.entity(entity).insert(SparseMarker);
+        }
+    }
+}
+
+#[test]
+fn it_rolls_back_sparse_set_components() {
+    let mut app = create_synctest_app(2);
+    app.rollback_component_with_copy::<SparseMarker>()
+        .add_systems(GgrsSchedule, toggle_sparse_marker);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let has_marker = app
+        .world
+        .query_filtered::<(), (With<Counter>, With<SparseMarker>)>()
+        .iter(&app.world)
+        .count()
+        == 1;
+    assert_eq!(has_marker, frame % 2 == 1);
+}
+
+#[derive(Component, Clone, Copy, Hash)]
+struct Churn(i32);
+
+fn churn_entities(
+    mut commands: Commands,
+    frame: Res<RollbackFrameCount>,
+    rollback_ordered: Res<RollbackOrdered>,
+    churned: Query<(Entity, &Rollback), With<Churn>>,
+) {
+    let mut churned = churned.iter().collect::<Vec<_>>();
+    churned.sort_by_key(|&(_, &rollback)| rollback_ordered.order(rollback));
+
+    // Despawn the oldest entities before spawning, so freed slots are reused immediately
+    if churned.len() >= 3 {
+        for &(entity, _) in &churned[..2] {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    commands.spawn_rollback(Churn(frame.0));
+}
+
+#[test]
+fn it_checksums_interleaved_spawns_and_despawns() {
+    let mut app = create_synctest_app(3);
+    app.set_strict_errors(true)
+        .rollback_component_with_copy::<Churn>()
+        .checksum_component_with_hash::<Churn>()
+        .add_systems(GgrsSchedule, churn_entities);
+
+    // Any difference between re-simulations panics through strict errors
+    for _ in 0..40 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+}
+
+#[derive(Component, Default)]
+struct Packed(u32);
+
+/// Stores [`Packed`] as raw bytes, standing in for a serialized representation.
+struct PackedStrategy;
+
+impl Strategy for PackedStrategy {
+    type Target = Packed;
+    type Stored = [u8; 4];
+
+    fn store(target: &Packed) -> [u8; 4] {
+        target.0.to_le_bytes()
+    }
+
+    fn load(stored: &[u8; 4]) -> Packed {
+        Packed(u32::from_le_bytes(*stored))
+    }
+}
+
+fn increase_packed(mut query: Query<&mut Packed>, inputs: Res<PlayerInputs<TestConfig>>) {
+    for mut packed in &mut query {
+        packed.0 += inputs[0].0 as u32;
+    }
+}
+
+#[test]
+fn it_rolls_back_with_custom_strategies() {
+    let mut app = create_synctest_app(2);
+    app.rollback_component_with_strategy::<PackedStrategy>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(Packed::default()).add_rollback();
+        })
+        .add_systems(GgrsSchedule, increase_packed);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let packed = app.world.query::<&Packed>().single(&app.world).0;
+    assert_eq!(packed, frame as u32, "Packed diverged from the frame count");
+}
+
+#[derive(Component, Clone, Copy)]
+struct Friend(Entity);
+
+impl MapEntities for Friend {
+    fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+        self.0 = entity_mapper.get_or_reserve(self.0);
+    }
+}
+
+fn spawn_friends(mut commands: Commands) {
+    let first = commands.spawn(Counter::default()).add_rollback().id();
+    let second = commands
+        .spawn((Counter::default(), Friend(first)))
+        .add_rollback()
+        .id();
+
+    commands.entity(first).insert(Friend(second));
+}
+
+#[test]
+fn it_instantiates_stored_entities_into_a_new_world() {
+    let mut source = create_synctest_app(2);
+    source
+        .rollback_component_with_copy::<Friend>()
+        .update_component_with_map_entities::<Friend>()
+        .add_systems(Startup, spawn_friends);
+
+    for _ in 0..30 {
+        source.update();
+    }
+
+    let ordered = source.world.resource::<RollbackOrdered>().clone();
+    let mut stored = source
+        .world
+        .query::<(&Rollback, Entity, &Counter, &Friend)>()
+        .iter(&source.world)
+        .map(|(&rollback, entity, counter, friend)| {
+            let order = ordered.order(rollback);
+            (order, entity.to_bits(), counter.0, friend.0.to_bits())
+        })
+        .collect::<Vec<_>>();
+    stored.sort();
+
+    let json = serde_json::to_string(&stored).unwrap();
+    let stored: Vec<(usize, u64, u32, u64)> = serde_json::from_str(&json).unwrap();
+
+    let mut target = App::new();
+    target
+        .add_plugins(MinimalPlugins)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .rollback_component_with_copy::<Counter>()
+        .rollback_component_with_copy::<Friend>()
+        .update_component_with_map_entities::<Friend>();
+
+    target.update();
+
+    let mut instantiator = EntityInstantiator::new(&mut target.world);
+    for &(_, original, counter, friend) in &stored {
+        instantiator.spawn(
+            Entity::from_bits(original),
+            (Counter(counter), Friend(Entity::from_bits(friend))),
+        );
+    }
+    let map = instantiator.finish();
+
+    assert_eq!(stored.len(), 2);
+    assert_eq!(
+        target
+            .world
+            .query::<&Rollback>()
+            .iter(&target.world)
+            .count(),
+        stored.len()
+    );
+
+    for &(_, original, counter, friend) in &stored {
+        let entity = map[&Entity::from_bits(original)];
+
+        assert_eq!(target.world.get::<Counter>(entity).unwrap().0, counter);
+        assert_eq!(
+            target.world.get::<Friend>(entity).unwrap().0,
+            map[&Entity::from_bits(friend)],
+            "Friend was not mapped onto the instantiated entity"
+        );
+    }
+}
+
+#[test]
+fn it_retains_confirmed_snapshots_by_policy() {
+    let mut snapshots = GgrsSnapshots::<Counter>::default();
+
+    for frame in 0..30 {
+        snapshots.push(frame, Counter(frame as u32));
+    }
+
+    snapshots.confirm_retaining(25, |frame| frame % 10 == 0);
+
+    let mut frames = snapshots.frames().collect::<Vec<_>>();
+    frames.sort();
+
+    assert_eq!(frames, [0, 10, 20, 25, 26, 27, 28, 29]);
+    assert_eq!(snapshots.peek(10).unwrap().0, 10);
+}
+
+#[test]
+fn it_applies_the_snapshot_retention_policy() {
+    let mut app = create_synctest_app(2);
+    app.set_snapshot_retention(|frame, _| frame % 10 == 0);
+
+    for _ in 0..60 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 30, "Rollback schedule did not advance");
+
+    assert!(!apply_snapshot(&mut app.world, 15), "Frame 15 was retained");
+    assert!(apply_snapshot(&mut app.world, 10), "Frame 10 was discarded");
+}
+
+#[test]
+fn it_reduces_snapshot_depth_to_fit_the_memory_limit() {
+    let mut app = create_synctest_app(2);
+    app.set_snapshot_memory_limit(1);
+
+    for _ in 0..60 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let usage = app.world.resource::<SnapshotMemoryUsage>();
+    assert!(usage.total_bytes() > 1, "Snapshot memory was not measured");
+
+    let depth_limit = usage.depth_limit().expect("Snapshot depth was not limited");
+    assert!(depth_limit < 60, "Snapshot depth was not reduced");
+
+    let depth = app
+        .world
+        .resource::<GgrsComponentSnapshots<Counter>>()
+        .depth();
+    assert_eq!(depth, depth_limit);
+}
+
+#[test]
+fn it_restores_snapshot_depth_for_the_next_session() {
+    let mut app = create_synctest_app(2);
+    app.set_snapshot_memory_limit(1);
+
+    for _ in 0..60 {
+        app.update();
+    }
+
+    let usage = app.world.resource::<SnapshotMemoryUsage>();
+    assert!(
+        usage.depth_limit().is_some(),
+        "Snapshot depth was not limited"
+    );
+
+    GgrsPlugin::<TestConfig>::shutdown(&mut app.world);
+    app.world.remove_resource::<SnapshotMemoryLimit>();
+
+    let usage = app.world.resource::<SnapshotMemoryUsage>();
+    assert_eq!(usage.depth_limit(), None);
+
+    app.insert_resource(synctest_session(1));
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let depth = app
+        .world
+        .resource::<GgrsComponentSnapshots<Counter>>()
+        .depth();
+    assert_eq!(depth, 60);
+}
+
+#[derive(Component, Clone, Copy)]
+struct SpawnIndex(usize);
+
+#[derive(Resource, Default)]
+struct VisitedOrder(Vec<usize>);
+
+fn visit_in_rollback_order(
+    query: Query<(&Rollback, &SpawnIndex)>,
+    order: Res<RollbackOrdered>,
+    mut visited: ResMut<VisitedOrder>,
+) {
+    visited.0 = order
+        .sorted(query.iter(), |(rollback, _)| **rollback)
+        .into_iter()
+        .map(|(_, index)| index.0)
+        .collect();
+}
+
+#[test]
+fn it_sorts_query_items_by_rollback_order() {
+    let mut app = create_synctest_app(2);
+    app.init_resource::<VisitedOrder>()
+        .add_systems(Startup, |mut commands: Commands| {
+            for index in 0..5 {
+                commands.spawn(SpawnIndex(index)).add_rollback();
+            }
+        })
+        .add_systems(GgrsSchedule, visit_in_rollback_order);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+    assert_eq!(app.world.resource::<VisitedOrder>().0, [0, 1, 2, 3, 4]);
+}
+
+#[derive(Component)]
+struct Child;
+
+fn spawn_hierarchy(mut commands: Commands) {
+    commands
+        .spawn(TransformBundle::default())
+        .add_rollback()
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Child,
+                    TransformBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+                ))
+                .add_rollback();
+        });
+}
+
+#[test]
+fn it_propagates_transforms_after_load() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(TransformPlugin)
+        .add_plugins(GgrsPlugin::<TestConfig>::default())
+        .rollback_transform()
+        .propagate_transforms_after_load()
+        .add_systems(Startup, spawn_hierarchy);
+
+    app.update();
+
+    let frame = capture_snapshot(&mut app.world);
+
+    let mut parents = app.world.query_filtered::<&mut Transform, With<Children>>();
+    parents.single_mut(&mut app.world).translation.x = 10.0;
+
+    // Bevy propagates the move in PostUpdate
+    app.update();
+
+    let mut children = app.world.query_filtered::<&GlobalTransform, With<Child>>();
+    assert_eq!(children.single(&app.world).translation().x, 11.0);
+
+    assert!(apply_snapshot(&mut app.world, frame));
+
+    assert_eq!(
+        children.single(&app.world).translation().x,
+        1.0,
+        "GlobalTransform was not propagated after the rollback moved the parent"
+    );
+}
+
+#[test]
+fn it_converts_between_entities_and_rollback_ids() {
+    let mut app = create_synctest_app(2);
+    app.add_systems(Startup, |mut commands: Commands| {
+        for index in 0..3 {
+            commands.spawn(SpawnIndex(index)).add_rollback();
+        }
+    });
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let mut entities = app
+        .world
+        .query::<(Entity, &SpawnIndex)>()
+        .iter(&app.world)
+        .map(|(entity, index)| (index.0, entity))
+        .collect::<Vec<_>>();
+    entities.sort();
+
+    let mut state = bevy::ecs::system::SystemState::<RollbackIds>::new(&mut app.world);
+    let ids = state.get(&app.world);
+
+    let converted = entities
+        .iter()
+        .map(|&(_, entity)| ids.to_id(entity).expect("Rollback entity has no id"))
+        .collect::<Vec<_>>();
+
+    // ids follow the order entities were given a Rollback
+    assert!(
+        converted.windows(2).all(|pair| pair[0] < pair[1]),
+        "Ids {converted:?} do not follow spawn order"
+    );
+
+    for (&(_, entity), &id) in entities.iter().zip(&converted) {
+        assert_eq!(ids.to_entity(id), Some(entity));
+    }
+
+    assert_eq!(ids.to_entity(100), None);
+}
+
+#[derive(Component, Clone, Hash, PartialEq, Debug)]
+struct Tiles(Vec<u8>);
+
+/// Indices and new values of every changed tile
+struct TileChanges(Vec<(usize, u8)>);
+
+fn diff_tiles(baseline: &Tiles, value: &Tiles) -> TileChanges {
+    let changes = baseline
+        .0
+        .iter()
+        .zip(&value.0)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(index, (_, &new))| (index, new))
+        .collect();
+
+    TileChanges(changes)
+}
+
+fn apply_tiles(tiles: &mut Tiles, changes: &TileChanges) {
+    for &(index, tile) in &changes.0 {
+        tiles.0[index] = tile;
+    }
+}
+
+fn paint_tile(mut tiles: Query<&mut Tiles>, frame: Res<RollbackFrameCount>) {
+    for mut tiles in &mut tiles {
+        tiles.0[frame.0 as usize % 16] = frame.0 as u8;
+    }
+}
+
+#[test]
+fn it_rolls_back_components_using_deltas() {
+    let mut app = create_synctest_app(3);
+    app.set_strict_errors(true)
+        .rollback_component_with_delta::<Tiles, _>(diff_tiles, apply_tiles)
+        .checksum_component_with_hash::<Tiles>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(Tiles(vec![0; 16])).add_rollback();
+        })
+        .add_systems(GgrsSchedule, paint_tile);
+
+    // long enough for baselines to be refreshed at least once
+    for _ in 0..90 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let mut expected = vec![0; 16];
+
+    for painted in 1..=frame {
+        expected[painted as usize % 16] = painted as u8;
+    }
+
+    let tiles = app.world.query::<&Tiles>().single(&app.world);
+    assert_eq!(tiles.0, expected);
+}
+
+/// Stands in for a plugin's internal state, which cannot be cloned and is not rolled back itself
+#[derive(Resource, Default)]
+struct PhysicsWorld {
+    steps: u32,
+}
+
+fn step_physics(mut physics: ResMut<PhysicsWorld>, inputs: Res<PlayerInputs<TestConfig>>) {
+    physics.steps += inputs[0].0 as u32;
+}
+
+#[test]
+fn it_rolls_back_world_state_with_save_and_load_functions() {
+    let mut app = create_synctest_app(3);
+    app.init_resource::<PhysicsWorld>()
+        .rollback_world_state(
+            |world| world.resource::<PhysicsWorld>().steps,
+            |world, steps| world.resource_mut::<PhysicsWorld>().steps = *steps,
+        )
+        .add_systems(GgrsSchedule, step_physics);
+
+    for _ in 0..60 {
+        app.update();
+    }
+
+    assert_counter_matches_frame(&mut app);
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert_eq!(app.world.resource::<PhysicsWorld>().steps, frame as u32);
+}
+
+#[derive(Component, Clone, Copy, Default, Hash)]
+struct CounterDelta(u32);
+
+fn record_counter_delta(
+    mut query: Query<(&Rollback, &Counter, &mut CounterDelta)>,
+    previous: PrevFrame<Counter>,
+) {
+    for (rollback, counter, mut delta) in &mut query {
+        let previous = previous
+            .get(rollback)
+            .expect("Previous frame should be stored");
+        delta.0 = counter.0 - previous.0;
+    }
+}
+
+#[test]
+fn it_reads_values_from_the_previous_frame() {
+    let mut app = create_synctest_app(3);
+    app.set_strict_errors(true)
+        .rollback_component_with_copy::<CounterDelta>()
+        .checksum_component_with_hash::<CounterDelta>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands
+                .spawn((Counter::default(), CounterDelta::default()))
+                .add_rollback();
+        })
+        .add_systems(GgrsSchedule, record_counter_delta.after(increase_counter));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert!(app.world.resource::<RollbackFrameCount>().0 > 10);
+
+    for delta in app.world.query::<&CounterDelta>().iter(&app.world) {
+        assert_eq!(delta.0, 1);
+    }
+}
+
+#[derive(Resource, Default)]
+struct AnnouncedDespawns {
+    announced: usize,
+    still_spawned: usize,
+}
+
+fn record_despawns(
+    mut will_despawn: EventReader<GgrsWillDespawn>,
+    spawned: Query<(), With<Spawned>>,
+    mut despawns: ResMut<AnnouncedDespawns>,
+) {
+    for event in will_despawn.read() {
+        despawns.announced += event.entities.len();
+        despawns.still_spawned += spawned.iter_many(&event.entities).count();
+    }
+}
+
+#[test]
+fn it_announces_despawns_before_they_happen() {
+    let mut app = create_synctest_app(3);
+    app.init_resource::<AnnouncedDespawns>()
+        .add_systems(GgrsSchedule, spawn_on_frame_five)
+        .add_systems(
+            LoadWorld,
+            record_despawns
+                .in_set(LoadWorldSet::Entity)
+                .after(EntitySnapshotPlugin::load),
+        );
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    // rolling back to frames before 5 removes the entity spawned by the previous simulation
+    let despawns = app.world.resource::<AnnouncedDespawns>();
+    assert!(despawns.announced > 0, "No despawns were announced");
+    assert_eq!(despawns.still_spawned, despawns.announced);
+}
+
+#[derive(Component, Reflect, Default, Clone, Copy, Hash)]
+struct Stride(u32);
+
+#[derive(Component, Reflect, Default, Clone, Copy, Hash)]
+struct Armor(u32);
+
+fn take_stride(mut strides: Query<&mut Stride>, inputs: Res<PlayerInputs<TestConfig>>) {
+    for mut stride in &mut strides {
+        stride.0 += inputs[0].0 as u32;
+    }
+}
+
+#[test]
+fn it_reuses_snapshots_of_unchanged_component_groups() {
+    let mut app = create_synctest_app(3);
+    app.set_strict_errors(true)
+        .rollback_component_with_reflect_in_group::<Stride>("movement")
+        .rollback_component_with_reflect_in_group::<Armor>("stats")
+        .checksum_component_with_hash::<Stride>()
+        .checksum_component_with_hash::<Armor>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn((Stride(0), Armor(5))).add_rollback();
+        })
+        .add_systems(GgrsSchedule, take_stride);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    assert!(frame > 10, "Rollback schedule did not advance");
+
+    let (stride, armor) = app.world.query::<(&Stride, &Armor)>().single(&app.world);
+    assert_eq!(stride.0, frame as u32);
+    assert_eq!(armor.0, 5);
+
+    let groups = app.world.resource::<ComponentGroups>();
+    let movement = groups.get("movement").unwrap();
+    let stats = groups.get("stats").unwrap();
+
+    assert_eq!((movement.captured(), movement.reused()), (1, 0));
+    assert_eq!(
+        (stats.captured(), stats.reused()),
+        (0, 1),
+        "The unchanged group was captured again"
+    );
+}
+
+#[derive(Resource, Default)]
+struct AdvanceCounts {
+    new: i32,
+    resimulated: u64,
+}
+
+fn count_advances(stats: Res<GgrsResimStats>, mut counts: ResMut<AdvanceCounts>) {
+    if stats.is_resimulating() {
+        counts.resimulated += 1;
+    } else {
+        counts.new += 1;
+    }
+}
+
+#[test]
+fn it_separates_resimulated_frames_from_new_frames() {
+    let mut app = create_synctest_app(3);
+    app.init_resource::<AdvanceCounts>()
+        .add_systems(GgrsSchedule, count_advances);
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let frame = app.world.resource::<RollbackFrameCount>().0;
+    let counts = app.world.resource::<AdvanceCounts>();
+    let stats = app.world.resource::<GgrsResimStats>();
+
+    assert!(frame > 10, "Rollback schedule did not advance");
+    assert_eq!(counts.new, frame, "A re-simulated frame was counted as new");
+    assert!(counts.resimulated > 0, "The sync test never re-simulated");
+    assert_eq!(stats.total_resimulated_frames(), counts.resimulated);
+    assert!(!stats.is_resimulating());
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "test-utils")]
+
+use bevy::prelude::*;
+use bevy_ggrs::{prelude::*, test_utils::assert_in_sync};
+
+mod common;
+use common::*;
+
+#[derive(Resource)]
+struct Step(u32);
+
+fn increase_counter_by_step(
+    mut counters: Query<&mut Counter>,
+    inputs: Res<PlayerInputs<TestConfig>>,
+    step: Res<Step>,
+) {
+    for mut counter in &mut counters {
+        counter.0 += inputs[0].0 as u32 * step.0;
+    }
+}
+
+fn create_stepped_app(step: u32) -> App {
+    let mut app = minimal_app(GgrsPlugin::<TestConfig>::default());
+
+    app.insert_resource(Step(step))
+        .rollback_component_with_copy::<Counter>()
+        .checksum_component_with_hash::<Counter>()
+        .add_systems(Startup, spawn_counter)
+        .add_systems(GgrsSchedule, increase_counter_by_step);
+
+    app
+}
+
+#[test]
+fn it_passes_identical_apps() {
+    let mut app_a = create_stepped_app(1);
+    let mut app_b = create_stepped_app(1);
+
+    assert_in_sync::<TestConfig>(&mut app_a, &mut app_b, 30, |frame| vec![(frame % 3) as u8]);
+}
+
+#[test]
+#[should_panic(expected = "Checksums diverged")]
+fn it_catches_diverging_apps() {
+    let mut app_a = create_stepped_app(1);
+    let mut app_b = create_stepped_app(2);
+
+    assert_in_sync::<TestConfig>(&mut app_a, &mut app_b, 30, |_| vec![1]);
+}
@@ -0,0 +1,185 @@
+use bevy::{prelude::*, time::TimeUpdateStrategy, utils::Duration};
+use bevy_ggrs::{prelude::*, GgrsTimingState, RollbackFrameCount, TimeDilation};
+
+mod common;
+use common::*;
+
+const FPS: usize = 60;
+
+fn check_time(time: Res<Time>, frame: Res<RollbackFrameCount>) {
+    let expected_elapsed = Duration::from_nanos(frame.0 as u64 * 1_000_000_000 / FPS as u64);
+    assert_eq!(time.elapsed(), expected_elapsed, "Elapsed time drifted");
+
+    let expected_delta = 1_000_000_000 / FPS as i128;
+    let delta = time.delta().as_nanos() as i128;
+    assert!(
+        (delta - expected_delta).abs() <= 1,
+        "Delta {delta}ns does not match the rollback frame rate"
+    );
+}
+
+fn create_timed_app() -> App {
+    let mut app = create_app(GgrsPlugin::default());
+
+    app.set_rollback_schedule_fps(FPS)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / FPS as f64,
+        )))
+        .add_systems(GgrsSchedule, check_time);
+
+    app
+}
+
+#[test]
+fn it_derives_time_from_rollback_frames() {
+    let mut app = create_timed_app();
+    app.insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert!(app.world.resource::<RollbackFrameCount>().0 > 10);
+}
+
+#[test]
+fn it_resets_time_between_sessions() {
+    let mut app = create_timed_app();
+    app.insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    app.world.remove_resource::<Session<TestConfig>>();
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    app.insert_resource(synctest_session(1));
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    assert!(app.world.resource::<RollbackFrameCount>().0 > 10);
+}
+
+#[test]
+fn it_restores_the_timing_state() {
+    // updates are shorter than a frame, so time is always left over in the accumulator
+    let strategy = TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(0.7 / FPS as f64));
+
+    let mut recorded = create_timed_app();
+    recorded
+        .insert_resource(strategy.clone())
+        .insert_resource(synctest_session(1));
+
+    for _ in 0..10 {
+        recorded.update();
+    }
+
+    let state = GgrsTimingState::capture(&recorded.world);
+    assert_ne!(state.accumulator, Duration::ZERO);
+    assert_eq!(
+        GgrsTimingState::from_bytes(state.to_bytes()),
+        state,
+        "Timing state did not survive encoding"
+    );
+
+    let mut replayed = create_timed_app();
+    replayed
+        .insert_resource(strategy)
+        .insert_resource(synctest_session(1));
+
+    GgrsTimingState::from_bytes(state.to_bytes()).apply(&mut replayed.world);
+
+    let start = |app: &App| app.world.resource::<RollbackFrameCount>().0;
+    let (recorded_start, replayed_start) = (start(&recorded), start(&replayed));
+
+    for _ in 0..20 {
+        recorded.update();
+        replayed.update();
+
+        assert_eq!(
+            start(&recorded) - recorded_start,
+            start(&replayed) - replayed_start,
+            "Frames were advanced at a different cadence"
+        );
+    }
+}
+
+/// Updates `app` `updates` times while told to run slow, returning the frames advanced.
+fn advance_running_slow(app: &mut App, updates: usize) -> i32 {
+    app.insert_resource(synctest_session(1));
+
+    // a SyncTestSession is never ahead, so it keeps running slow once told to
+    GgrsTimingState {
+        run_slow: true,
+        ..default()
+    }
+    .apply(&mut app.world);
+
+    for _ in 0..updates {
+        app.update();
+    }
+
+    app.world.resource::<RollbackFrameCount>().0
+}
+
+#[test]
+fn it_keeps_advancing_slower_while_running_slow() {
+    let updates = 330;
+
+    let advanced = advance_running_slow(&mut create_timed_app(), updates);
+    let expected = updates as f64 / 1.1;
+    assert!(
+        (advanced as f64 - expected).abs() <= 2.,
+        "Advanced {advanced} frames running slow, expected about {expected}"
+    );
+
+    let mut app = create_timed_app();
+    app.set_run_slow_factor(1.5);
+
+    let advanced = advance_running_slow(&mut app, updates);
+    let expected = updates as f64 / 1.5;
+    assert!(
+        (advanced as f64 - expected).abs() <= 2.,
+        "Advanced {advanced} frames with a run slow factor of 1.5, expected about {expected}"
+    );
+}
+
+#[test]
+fn it_limits_the_time_dilation() {
+    let time_dilation = TimeDilation {
+        gain: 0.02,
+        deadband: 1,
+        max_adjustment: 0.1,
+    };
+
+    assert_eq!(time_dilation.factor(1), 1.);
+    assert_eq!(time_dilation.factor(-1), 1.);
+    assert!((time_dilation.factor(3) - 1.04).abs() < 1e-9);
+    assert!((time_dilation.factor(-3) - 0.96).abs() < 1e-9);
+    assert!((time_dilation.factor(100) - 1.1).abs() < 1e-9);
+    assert!((time_dilation.factor(-100) - 0.9).abs() < 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "Time dilation max adjustment must be at least 0 and less than 1")]
+fn it_rejects_time_dilation_stopping_frames() {
+    create_timed_app().set_time_dilation(TimeDilation {
+        max_adjustment: 1.,
+        ..default()
+    });
+}
+
+#[test]
+#[should_panic(expected = "Time dilation gain must be finite")]
+fn it_rejects_time_dilation_without_a_finite_gain() {
+    create_timed_app().set_time_dilation(TimeDilation {
+        gain: f64::NAN,
+        ..default()
+    });
+}